@@ -1,13 +1,25 @@
 pub mod easing;
 mod progress;
+mod spring;
 mod transition;
 
 pub use easing::Easing;
 pub use progress::AnimateProgress;
+pub use spring::Spring;
 pub use transition::*;
 mod animate;
 pub use animate::*;
+mod keyframes;
+pub use keyframes::*;
+mod group;
+pub use group::*;
+mod animated_switcher;
+pub use animated_switcher::*;
+mod hero;
+pub(crate) use hero::{paint_hero_flights, HeroTrack};
+pub use hero::Hero;
 mod lerp;
+mod path_morph;
 pub use lerp::Lerp;
 mod repeat;
 pub use repeat::*;
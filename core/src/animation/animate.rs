@@ -24,69 +24,260 @@ pub struct Animate<T, P: AnimateProperty> {
   animate_track: AnimateTrack,
   #[declare(skip, default = ctx.wnd_ctx().frame_scheduler())]
   frame_scheduler: FuturesLocalScheduler,
+  #[declare(skip, default = ctx.wnd_ctx().clock.clone())]
+  clock: Rc<RefCell<Box<dyn Clock>>>,
 }
 
 pub struct AnimateInfo<V> {
   from: V,
   to: V,
-  start_at: Instant,
+  // The timeline is at `anchor_pos` when `anchor_at`.
+  anchor_at: Instant,
+  anchor_pos: Duration,
+  // The velocity of the rate when the animation start.
+  velocity: f32,
   last_progress: AnimateProgress,
   // Determines if lerp value in current frame.
   already_lerp: bool,
+  reversed: bool,
+  paused: bool,
+  playback_rate: f32,
   _tick_msg_guard: Option<SubscriptionGuard<BoxSubscription<'static>>>,
 }
 
+impl<V> AnimateInfo<V> {
+  fn new(from: V, to: V, now: Instant) -> Self {
+    Self {
+      from,
+      to,
+      anchor_at: now,
+      anchor_pos: Duration::ZERO,
+      velocity: 0.,
+      last_progress: AnimateProgress::Dismissed,
+      already_lerp: false,
+      reversed: false,
+      paused: false,
+      playback_rate: 1.,
+      _tick_msg_guard: None,
+    }
+  }
+
+  /// The position of the timeline at `now`.
+  fn pos(&self, now: Instant) -> Duration {
+    if self.paused {
+      return self.anchor_pos;
+    }
+    let passed = now
+      .saturating_duration_since(self.anchor_at)
+      .mul_f32(self.playback_rate);
+    if self.reversed {
+      self.anchor_pos.saturating_sub(passed)
+    } else {
+      self.anchor_pos.saturating_add(passed)
+    }
+  }
+
+  fn anchor(&mut self, now: Instant, pos: Duration) {
+    self.anchor_at = now;
+    self.anchor_pos = pos;
+  }
+}
+
 impl<'a, T: Roc, P: AnimateProperty> StateRef<'a, Animate<T, P>>
 where
   Animate<T, P>: 'static,
 {
+  /// Run the animation to the current value of the property. If the animation
+  /// is running, it continues forward from where it is.
   pub fn run(&mut self) {
     let new_to = self.prop.get();
     // if animate is running, animate start from current value.
-    let Animate { prop, running_info, .. } = self.deref_mut();
-    if let Some(AnimateInfo { from, to, last_progress, .. }) = running_info {
-      *from = prop.calc_lerp_value(from, to, last_progress.value());
-      *to = new_to;
+    let Animate {
+      prop,
+      running_info,
+      transition,
+      clock,
+      animate_track,
+      ..
+    } = self.deref_mut();
+    if let Some(info) = running_info {
+      let rate = info.last_progress.value();
+      let current = prop.calc_lerp_value(&info.from, &info.to, rate);
+      let now = clock.borrow().now();
+      let pos = info.pos(now);
+      info.anchor(now, pos);
+      info.reversed = false;
+      info.paused = false;
+      animate_track.set_actived(true);
+      // The `Roc` tracks velocity restart from the current value and keep its
+      // velocity.
+      if let Some(velocity) = transition.velocity(pos, info.velocity) {
+        // The velocity is the rate of the distance per second, convert it to the
+        // new distance by where the value will be after a second, so the value
+        // keeps its speed in the property units.
+        let ahead = prop.calc_lerp_value(&info.from, &info.to, rate + velocity);
+        info.velocity = prop
+          .calc_lerp_rate(&current, &new_to, &ahead)
+          .unwrap_or(velocity);
+        info.anchor(now, Duration::ZERO);
+        info.last_progress = AnimateProgress::Dismissed;
+      }
+      info.from = current;
+      info.to = new_to;
     } else {
-      let animate = self.clone_stateful();
-      let ticker = self.frame_ticker.frame_tick_stream();
-      let unsub = ticker.subscribe(move |msg| match msg {
-        FrameMsg::NewFrame(_) => {}
-        FrameMsg::LayoutReady(time) => {
-          let p = animate.shallow_ref().lerp(time);
-          if matches!(p, AnimateProgress::Finish) {
-            let scheduler = animate.silent_ref().frame_scheduler.clone();
-            let animate = animate.clone();
-            observable::of(())
-              .delay(Duration::ZERO, scheduler)
-              .subscribe(move |_| {
-                animate.silent_ref().stop();
-              });
-          }
-        }
-        // use silent_ref because the state of animate change, bu no need to effect the framework.
-        FrameMsg::Finish(_) => animate.silent_ref().frame_finished(),
-      });
-      let guard = BoxSubscription::new(unsub).unsubscribe_when_dropped();
-      self.running_info = Some(AnimateInfo {
-        from: self.from.clone(),
-        to: new_to,
-        start_at: Instant::now(),
-        last_progress: AnimateProgress::Dismissed,
-        _tick_msg_guard: Some(guard),
-        already_lerp: false,
-      });
+      self.start(Duration::ZERO, false, false);
+    }
+  }
+
+  /// Reverse the direction of the animation, the running animation continues
+  /// from where it is back to its start. If the animation is not running, it
+  /// runs from its end to its start.
+  pub fn reverse(&mut self) {
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      let pos = info.pos(now);
+      info.anchor(now, pos);
+      info.reversed = !info.reversed;
+    } else if let Some(total) = self.transition.total_duration() {
+      self.start(total, true, false);
+    } else {
+      log::warn!("Can't reverse an animation that never finishes.");
+    }
+  }
+
+  /// Jump to the `progress` of the timeline, from `0.` to `1.`, the timeline
+  /// includes the delay and all the repeats of the transition. If the
+  /// animation is not running, it starts paused at the `progress`, so you can
+  /// scrub it by seeking again and again.
+  pub fn seek(&mut self, progress: f32) {
+    let Some(total) = self.transition.total_duration() else {
+      log::warn!("Can't seek an animation that never finishes.");
+      return;
+    };
+    let pos = total.mul_f32(progress.clamp(0., 1.));
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      info.anchor(now, pos);
+      // Run a frame to show the position, a paused animation stops its frames
+      // after it.
       self.animate_track.set_actived(true);
+    } else {
+      self.start(pos, false, true);
     }
   }
+
+  fn start(&mut self, pos: Duration, reversed: bool, paused: bool) {
+    let animate = self.clone_stateful();
+    let ticker = self.frame_ticker.frame_tick_stream();
+    let unsub = ticker.subscribe(move |msg| match msg {
+      FrameMsg::NewFrame(_) => {}
+      FrameMsg::LayoutReady(time) => {
+        let p = animate.shallow_ref().lerp(time);
+        if matches!(p, AnimateProgress::Finish) {
+          let scheduler = animate.silent_ref().frame_scheduler.clone();
+          let animate = animate.clone();
+          observable::of(())
+            .delay(Duration::ZERO, scheduler)
+            .subscribe(move |_| {
+              animate.silent_ref().stop();
+            });
+        }
+      }
+      // use silent_ref because the state of animate change, bu no need to effect the framework.
+      FrameMsg::Finish(_) => animate.silent_ref().frame_finished(),
+    });
+    let guard = BoxSubscription::new(unsub).unsubscribe_when_dropped();
+    let now = self.clock.borrow().now();
+    let mut info = AnimateInfo::new(self.from.clone(), self.prop.get(), now);
+    info.anchor_pos = pos;
+    info.reversed = reversed;
+    info.paused = paused;
+    info._tick_msg_guard = Some(guard);
+    self.running_info = Some(info);
+    // A paused start still runs a frame to show where it's paused.
+    self.animate_track.set_actived(true);
+  }
 }
 
 impl<T: Roc, P: AnimateProperty> Animate<T, P> {
+  /// Pause the running animation, the property keeps the value where it
+  /// paused.
+  pub fn pause(&mut self) {
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      let pos = info.pos(now);
+      info.anchor(now, pos);
+      info.paused = true;
+      self.animate_track.set_actived(false);
+    }
+  }
+
+  /// Resume the paused animation.
+  pub fn resume(&mut self) {
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      info.anchor_at = now;
+      info.paused = false;
+      self.animate_track.set_actived(true);
+    }
+  }
+
+  /// Set the speed of the running animation, `2.` runs twice as fast and
+  /// `0.5` runs half as fast. A negative rate is treated as `0.`.
+  pub fn set_playback_rate(&mut self, rate: f32) {
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      let pos = info.pos(now);
+      info.anchor(now, pos);
+      info.playback_rate = rate.max(0.);
+    }
+  }
+
+  #[inline]
+  pub fn is_paused(&self) -> bool { matches!(&self.running_info, Some(info) if info.paused) }
+
+  #[inline]
+  pub fn is_reversed(&self) -> bool { matches!(&self.running_info, Some(info) if info.reversed) }
+
+  /// The speed of the running animation, `1.` if it's not running.
+  #[inline]
+  pub fn playback_rate(&self) -> f32 {
+    self
+      .running_info
+      .as_ref()
+      .map_or(1., |info| info.playback_rate)
+  }
+
   fn lerp(&mut self, now: Instant) -> AnimateProgress {
+    let info = self
+      .running_info
+      .as_ref()
+      .expect("This animation is not running.");
+    if info.already_lerp {
+      return info.last_progress;
+    }
+    let (pos, reversed) = (info.pos(now), info.reversed);
+    let progress = self.lerp_by(pos);
+    if !reversed {
+      return progress;
+    }
+
+    // The reversed animation finished when it's back to its start.
+    let progress = match progress {
+      AnimateProgress::Finish => AnimateProgress::Between(1.),
+      AnimateProgress::Dismissed => AnimateProgress::Finish,
+      _ if pos.is_zero() => AnimateProgress::Finish,
+      p => p,
+    };
+    self.running_info.as_mut().unwrap().last_progress = progress;
+    progress
+  }
+
+  fn lerp_by(&mut self, elapsed: Duration) -> AnimateProgress {
     let AnimateInfo {
       from,
       to,
-      start_at,
+      velocity,
       last_progress,
       already_lerp,
       ..
@@ -99,8 +290,9 @@ impl<T: Roc, P: AnimateProperty> Animate<T, P> {
       return *last_progress;
     }
 
-    let elapsed = now - *start_at;
-    let progress = self.transition.rate_of_change(elapsed);
+    let progress = self
+      .transition
+      .rate_of_change_with_velocity(elapsed, *velocity);
 
     let prop = &mut self.prop;
     match progress {
@@ -126,10 +318,16 @@ impl<T: Roc, P: AnimateProperty> Animate<T, P> {
       .as_mut()
       .expect("This animation is not running.");
 
-    if !matches!(info.last_progress, AnimateProgress::Finish) {
-      self.prop.set(info.to.clone())
+    match info.last_progress {
+      AnimateProgress::Finish if info.reversed => self.prop.set(info.from.clone()),
+      AnimateProgress::Finish => {}
+      _ => self.prop.set(info.to.clone()),
     }
     info.already_lerp = false;
+    // The paused animation needs no more frames.
+    if info.paused {
+      self.animate_track.set_actived(false);
+    }
   }
 
   pub fn stop(&mut self) {
@@ -141,6 +339,37 @@ impl<T: Roc, P: AnimateProperty> Animate<T, P> {
   pub fn is_running(&self) -> bool { self.running_info.is_some() }
 }
 
+impl<T: Roc, P: AnimateProperty> AnimateCtrl for Stateful<Animate<T, P>>
+where
+  Animate<T, P>: 'static,
+{
+  fn prepare(&self) {
+    let mut this = self.silent_ref();
+    this.stop();
+    let now = this.clock.borrow().now();
+    let info = AnimateInfo::new(this.from.clone(), this.prop.get(), now);
+    this.running_info = Some(info);
+  }
+
+  #[inline]
+  fn duration(&self) -> Option<Duration> { self.state_ref().transition.total_duration() }
+
+  #[inline]
+  fn lerp_by(&self, elapsed: Duration) -> AnimateProgress { self.shallow_ref().lerp_by(elapsed) }
+
+  #[inline]
+  fn frame_finished(&self) { self.silent_ref().frame_finished() }
+
+  fn finish(&self, reversed: bool) {
+    let mut this = self.silent_ref();
+    if reversed {
+      let from = this.from.clone();
+      this.prop.set(from);
+    }
+    this.stop();
+  }
+}
+
 pub struct AnimateTrack {
   pub(crate) actived: bool,
   pub(crate) actived_cnt: Rc<RefCell<u32>>,
@@ -156,7 +385,7 @@ impl Drop for AnimateTrack {
 }
 
 impl AnimateTrack {
-  fn set_actived(&mut self, actived: bool) {
+  pub(crate) fn set_actived(&mut self, actived: bool) {
     if self.actived == actived {
       return;
     }
@@ -175,6 +404,7 @@ mod tests {
     animation::{easing, Prop},
     declare::Declare,
     state::Stateful,
+    test::ManualFrames,
   };
 
   #[test]
@@ -206,4 +436,172 @@ mod tests {
       .frame_ticker
       .emit(FrameMsg::LayoutReady(Instant::now()));
   }
+
+  #[test]
+  fn step_by_manual_clock() {
+    let mut frames = ManualFrames::default();
+    let value = Stateful::new(1.);
+    let animate = frames.build(|ctx| {
+      Animate::declare_builder()
+        .transition(
+          Transition::declare_builder()
+            .easing(easing::LINEAR)
+            .duration(Duration::from_millis(100))
+            .build(ctx),
+        )
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .from(0.)
+        .build(ctx)
+    });
+
+    let animate = Stateful::new(animate);
+    animate.state_ref().run();
+
+    assert_eq!(frames.step(50, || *value.state_ref()), 0.5);
+    assert_eq!(frames.step(25, || *value.state_ref()), 0.75);
+  }
+
+  #[test]
+  fn spring_keep_velocity_when_retarget() {
+    let mut frames = ManualFrames::default();
+    let value = Stateful::new(1.);
+    let animate = frames.build(|ctx| {
+      Animate::declare_builder()
+        .transition(Spring::declare_builder().build(ctx))
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .from(0.)
+        .build(ctx)
+    });
+
+    let animate = Stateful::new(animate);
+    animate.state_ref().run();
+
+    let mut step = |ms| frames.step(ms, || *value.state_ref());
+    let prev = step(49);
+    let before = step(1);
+    *value.state_ref() = 3.;
+    animate.state_ref().run();
+    let start = step(0);
+    let after = step(1);
+
+    // The value continues from where it is, and keeps its speed in the
+    // property units, the difference of the speeds is from the acceleration
+    // in a millisecond.
+    assert!((start - before).abs() < 1e-5);
+    let (v_before, v_after) = (before - prev, after - start);
+    assert!((v_after - v_before).abs() < v_before * 0.1);
+  }
+
+  #[test]
+  fn playback_controls() {
+    let mut frames = ManualFrames::default();
+    let value = Stateful::new(1.);
+    let animate = frames.build(|ctx| {
+      Animate::declare_builder()
+        .transition(
+          Transition::declare_builder()
+            .easing(easing::LINEAR)
+            .duration(Duration::from_millis(100))
+            .build(ctx),
+        )
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .from(0.)
+        .build(ctx)
+    });
+
+    let animate = Stateful::new(animate);
+    let modifies = Rc::new(RefCell::new(0));
+    let c_modifies = modifies.clone();
+    animate
+      .modifies()
+      .subscribe(move |_| *c_modifies.borrow_mut() += 1);
+
+    let step = |frames: &mut ManualFrames, ms| frames.step(ms, || *value.state_ref());
+
+    animate.state_ref().run();
+    assert!((step(&mut frames, 20) - 0.2).abs() < 1e-4);
+
+    let before = *modifies.borrow();
+    animate.state_ref().pause();
+    assert_eq!(*modifies.borrow(), before + 1);
+    assert!(animate.state_ref().is_paused());
+    assert!(!frames.has_actived_animate());
+    assert!((step(&mut frames, 50) - 0.2).abs() < 1e-4);
+
+    animate.state_ref().resume();
+    assert!(frames.has_actived_animate());
+    animate.state_ref().set_playback_rate(2.);
+    assert!((step(&mut frames, 10) - 0.4).abs() < 1e-4);
+
+    animate.state_ref().seek(0.8);
+    assert!((step(&mut frames, 0) - 0.8).abs() < 1e-4);
+
+    animate.state_ref().reverse();
+    assert!(animate.state_ref().is_reversed());
+    assert!((step(&mut frames, 20) - 0.4).abs() < 1e-4);
+    assert!(step(&mut frames, 25).abs() < 1e-4);
+    assert!(!animate.state_ref().is_running());
+    assert!(value.state_ref().abs() < 1e-4);
+  }
+
+  #[test]
+  fn reverse_and_seek_with_delay_and_repeat() {
+    let mut frames = ManualFrames::default();
+    let value = Stateful::new(1.);
+    let animate = frames.build(|ctx| {
+      Animate::declare_builder()
+        .transition(
+          Transition::declare_builder()
+            .easing(easing::LINEAR)
+            .delay(Duration::from_millis(100))
+            .duration(Duration::from_millis(100))
+            .repeat(2.)
+            .build(ctx),
+        )
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .from(0.)
+        .build(ctx)
+    });
+
+    let animate = Stateful::new(animate);
+    let step = |frames: &mut ManualFrames, ms| frames.step(ms, || *value.state_ref());
+
+    // The timeline is 300ms, the delay and two rounds.
+    animate.state_ref().seek(0.5);
+    assert!(animate.state_ref().is_paused());
+    assert!(frames.has_actived_animate());
+    assert!((step(&mut frames, 10) - 0.5).abs() < 1e-4);
+    // The paused animation stops the frames after the sought one.
+    assert!(!frames.has_actived_animate());
+    animate.state_ref().seek(0.9);
+    assert!((step(&mut frames, 10) - 0.7).abs() < 1e-4);
+    assert!(!frames.has_actived_animate());
+    animate.state_ref().stop();
+
+    // Reverse a not running animation runs from the end, and finishes when it's
+    // back to the delay.
+    animate.state_ref().reverse();
+    assert!((step(&mut frames, 0) - 1.).abs() < 1e-4);
+    assert!((step(&mut frames, 50) - 0.5).abs() < 1e-4);
+    assert!((step(&mut frames, 100) - 0.5).abs() < 1e-4);
+    assert!(animate.state_ref().is_running());
+    assert!(step(&mut frames, 60).abs() < 1e-4);
+    assert!(!animate.state_ref().is_running());
+  }
 }
@@ -0,0 +1,248 @@
+use crate::{
+  builtin_widgets::key::AnyKey, dynamic_widget::inspect_key, impl_query_self_only, prelude::*,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// A widget animates the switch of its child, the old child leaves with an
+/// exit animation and the new child enters with an enter animation at the same
+/// time.
+///
+/// The child of `AnimatedSwitcher` is usually a `DynWidget`, every time it
+/// generates a new child the switch happens. If the old child and the new
+/// child have the same [`Key`](KeyWidget), they are treated as the same child
+/// and no animation runs.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let w = widget! {
+///   states { flag: Stateful::new(true) }
+///   AnimatedSwitcher {
+///     switch_type: SwitchType::Slide(Vector::new(100., 0.)),
+///     on_tap: move |_| *flag = !*flag,
+///     DynWidget {
+///       dyns: if *flag {
+///         widget! { KeyWidget { key: "on", value: Some(()), Void {} } }
+///       } else {
+///         widget! { KeyWidget { key: "off", value: Some(()), Void {} } }
+///       }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct AnimatedSwitcher {
+  /// How the child switches, cross-fade by default.
+  #[declare(default)]
+  pub switch_type: SwitchType,
+  /// The transition of the enter and exit animations.
+  #[declare(default = transitions::EASE_IN_OUT.of(ctx))]
+  pub transition: Rc<Box<dyn Roc>>,
+}
+
+/// Describe how [`AnimatedSwitcher`] switches its child.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SwitchType {
+  /// The old child fades out and the new child fades in.
+  #[default]
+  Fade,
+  /// The new child slides in from the offset, and the old child slides out to
+  /// the opposite of the offset.
+  Slide(Vector),
+}
+
+// The key of the leaving child and the callback to drop it immediately.
+type Leaving = Option<(Option<Key>, Box<dyn FnOnce()>)>;
+
+impl ComposeChild for AnimatedSwitcher {
+  type Child = State<Widget>;
+
+  fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
+    let this = this.into_readonly();
+    let leaving: Rc<RefCell<Leaving>> = <_>::default();
+    match child {
+      State::Stateless(child) => switch_item(this, child, leaving),
+      State::Stateful(child) => {
+        let first = std::mem::replace(&mut *child.silent_ref(), Void.into_widget());
+        let dyns = Stateful::new(DynWidget {
+          dyns: Some(switch_item(this.clone(), first, leaving.clone())),
+        });
+        let c_dyns = dyns.clone();
+        let c_child = child.clone();
+        child.modifies().subscribe(move |_| {
+          let mut child = c_child.silent_ref();
+          let w = std::mem::replace(&mut *child, Void.into_widget());
+          // The child is spread into the switcher, no one else depends on it.
+          child.forget_modifies();
+          let item = switch_item(this.clone(), w, leaving.clone());
+          c_dyns.state_ref().dyns = Some(item);
+        });
+        dyns.into_widget()
+      }
+    }
+  }
+}
+
+fn switch_item(
+  this: Readonly<AnimatedSwitcher>,
+  child: Widget,
+  leaving: Rc<RefCell<Leaving>>,
+) -> Widget {
+  let (switch_type, transition) = {
+    let this = this.state_ref();
+    (this.switch_type, this.transition.clone())
+  };
+  let mount_leaving = leaving.clone();
+  let exit_transition = transition.clone();
+  widget! {
+    SwitchItem {
+      id: item,
+      switch_type,
+      delay_drop_until: !exit.is_running(),
+      on_mounted: move |ctx| {
+        let key = child_key(ctx.id, ctx.arena);
+        match mount_leaving.borrow_mut().take() {
+          Some((old, drop_now)) if key.is_some() && old == key => drop_now(),
+          Some(_) => enter.run(),
+          // The first child shows without animation.
+          None => {}
+        }
+      },
+      on_disposed: move |ctx| {
+        let key = child_key(ctx.id, ctx.arena);
+        let exit_animate = exit.clone_stateful();
+        *leaving.borrow_mut() = Some((key, Box::new(move || exit_animate.state_ref().stop())));
+        // Release the item before the exit animation runs, it reads the item.
+        let from = (move || {
+          enter.stop();
+          item.exiting = true;
+          std::mem::replace(&mut item.rate, 0.)
+        })();
+        exit.from = from;
+        exit.run();
+      },
+      DynWidget { dyns: child }
+    }
+    Animate {
+      id: enter,
+      transition,
+      prop: prop!(item.rate),
+      from: 0.,
+    }
+    Animate {
+      id: exit,
+      transition: exit_transition,
+      prop: prop!(item.rate),
+      from: 1.,
+    }
+  }
+}
+
+/// The widget paints the child by the switch progress.
+#[derive(Declare, SingleChild)]
+struct SwitchItem {
+  switch_type: SwitchType,
+  #[declare(default = 1.)]
+  rate: f32,
+  #[declare(default)]
+  exiting: bool,
+}
+
+impl SwitchItem {
+  fn transform(&self) -> Option<Transform> {
+    match self.switch_type {
+      SwitchType::Fade => None,
+      SwitchType::Slide(offset) => {
+        let offset = if self.exiting { -offset } else { offset } * (1. - self.rate);
+        Some(Transform::translation(offset.x, offset.y))
+      }
+    }
+  }
+}
+
+impl Render for SwitchItem {
+  #[inline]
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    ctx.assert_perform_single_child_layout(clamp)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    match self.transform() {
+      Some(transform) => ctx.painter().apply_transform(&transform),
+      None => ctx.painter().apply_alpha(self.rate),
+    };
+  }
+
+  fn can_overflow(&self) -> bool { true }
+
+  fn hit_test(&self, _: &HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: true }
+  }
+
+  fn get_transform(&self) -> Option<Transform> { self.transform() }
+}
+
+impl Query for SwitchItem {
+  impl_query_self_only!();
+}
+
+/// Find the key of the child, the key is attached to the first widget which
+/// has more than one child.
+fn child_key(id: WidgetId, arena: &TreeArena) -> Option<Key> {
+  let mut key = None;
+  let mut w = Some(id);
+  while let Some(id) = w.filter(|_| key.is_none()) {
+    inspect_key(&id, arena, |k: &dyn AnyKey| key = Some(k.key()));
+    w = id
+      .first_child(arena)
+      .filter(|c| c.next_sibling(arena).is_none());
+  }
+  key
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::time::Duration;
+
+  #[test]
+  fn switch_child_with_animation() {
+    let key = Stateful::new("on");
+    let size = Stateful::new(Size::new(1., 1.));
+    let w = widget! {
+      states { key: key.clone(), size: size.clone() }
+      AnimatedSwitcher {
+        DynWidget {
+          dyns: {
+            let (k, s) = (*key, *size);
+            widget! { KeyWidget { key: k, value: Some(()), MockBox { size: s } } }
+          }
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+    let delay_widgets = |wnd: &Window| wnd.context.delay_drop_widgets.borrow().len();
+    assert_eq!(delay_widgets(&wnd), 0);
+
+    // same key, no animation.
+    *size.state_ref() = Size::new(2., 2.);
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(delay_widgets(&wnd), 0);
+
+    *key.state_ref() = "off";
+    wnd.draw_frame();
+    // the old child keeps to run the exit animation.
+    assert_eq!(delay_widgets(&wnd), 1);
+
+    clock.advance(Duration::from_secs(2));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(delay_widgets(&wnd), 0);
+  }
+}
@@ -0,0 +1,544 @@
+use crate::{
+  prelude::*,
+  ticker::{FrameMsg, FrameTicker},
+};
+use std::{
+  cell::RefCell,
+  convert::Infallible,
+  rc::Rc,
+  time::{Duration, Instant},
+};
+
+/// An animation can be driven by the timeline of an [`AnimateGroup`].
+///
+/// The animation driven by a group not subscribes the frame ticker itself, the
+/// group tells it where its timeline is in every frame.
+pub trait AnimateCtrl {
+  /// Ready to be driven by a group, the running animation is stopped.
+  fn prepare(&self);
+
+  /// The duration of the animation, `None` if it never finishes or unknown.
+  fn duration(&self) -> Option<Duration>;
+
+  /// Update the animation to the `elapsed` of its timeline in current frame.
+  fn lerp_by(&self, elapsed: Duration) -> AnimateProgress;
+
+  /// Called after the current frame is finished.
+  fn frame_finished(&self);
+
+  /// The group stops driving the animation, `reversed` tells if the timeline
+  /// stop at its start.
+  fn finish(&self, reversed: bool);
+}
+
+/// How the animations of a [`AnimateGroup`] are arranged on its timeline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupMode {
+  /// Run the animations one after another.
+  Sequence,
+  /// Run all the animations at the same time.
+  Parallel,
+  /// Run every animation the duration after the previous one starts.
+  Stagger(Duration),
+}
+
+/// Run multiple animations as one unit, it can run, stop, reverse and seek all
+/// the animations together.
+///
+/// Every animation in the group must have a finite duration except the last
+/// one of a sequence, otherwise the animations after it never start, and the
+/// group can't reverse or seek.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let _w = widget! {
+///   states {
+///     card_opacity: Stateful::new(1.),
+///     child_offset: Stateful::new(0.),
+///   }
+///   init ctx => {
+///     let fade_transition = transitions::LINEAR.of(ctx);
+///     let slide_transition = transitions::LINEAR.of(ctx);
+///   }
+///   Void {}
+///   Animate {
+///     id: fade_in,
+///     transition: fade_transition,
+///     prop: prop!(card_opacity),
+///     from: 0.,
+///   }
+///   Animate {
+///     id: slide_in,
+///     transition: slide_transition,
+///     prop: prop!(child_offset),
+///     from: -100.,
+///   }
+///   AnimateGroup {
+///     id: group,
+///     mode: GroupMode::Sequence,
+///     animations: Vec::from([
+///       Box::new(fade_in.clone_stateful()) as Box<dyn AnimateCtrl>,
+///       Box::new(slide_in.clone_stateful()),
+///     ]),
+///   }
+///   finally {
+///     group.run();
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct AnimateGroup {
+  pub mode: GroupMode,
+  pub animations: Vec<Box<dyn AnimateCtrl>>,
+  #[declare(skip)]
+  running_info: Option<GroupInfo>,
+  #[declare(skip)]
+  reversed: bool,
+  #[declare(skip)]
+  seek_to: Option<f32>,
+  #[declare(skip)]
+  progress: Subject<'static, f32, Infallible>,
+  #[declare(skip, default = ctx.wnd_ctx().frame_ticker.clone())]
+  frame_ticker: FrameTicker,
+  #[declare(skip, default = ctx.wnd_ctx().animate_track())]
+  animate_track: AnimateTrack,
+  #[declare(skip, default = ctx.wnd_ctx().frame_scheduler())]
+  frame_scheduler: FuturesLocalScheduler,
+  #[declare(skip, default = ctx.wnd_ctx().clock.clone())]
+  clock: Rc<RefCell<Box<dyn Clock>>>,
+}
+
+struct GroupInfo {
+  // The timeline is at `anchor_pos` when `anchor_at`.
+  anchor_at: Instant,
+  anchor_pos: Duration,
+  // The start and the duration of every animation, and the total duration of
+  // the timeline, computed once when the group starts.
+  timeline: Vec<(Duration, Option<Duration>)>,
+  total: Option<Duration>,
+  last_progress: AnimateProgress,
+  // Determines if lerp value in current frame.
+  already_lerp: bool,
+  _tick_msg_guard: Option<SubscriptionGuard<BoxSubscription<'static>>>,
+}
+
+impl<'a> StateRef<'a, AnimateGroup> {
+  /// Run the animations of the group from the start, or from the end if the
+  /// group is reversed.
+  pub fn run(&mut self) {
+    let now = self.clock.borrow().now();
+    let timeline: Vec<_> = self.timeline().collect();
+    let total = total_of(&timeline);
+    let pos = match (self.seek_to.take(), total) {
+      (Some(rate), Some(total)) => total.mul_f32(rate.clamp(0., 1.)),
+      (_, Some(total)) if self.reversed => total,
+      _ => Duration::ZERO,
+    };
+
+    if let Some(info) = self.running_info.as_mut() {
+      info.anchor_at = now;
+      info.anchor_pos = pos;
+      info.timeline = timeline;
+      info.total = total;
+      return;
+    }
+
+    self.animations.iter().for_each(|a| a.prepare());
+    let group = self.clone_stateful();
+    let ticker = self.frame_ticker.frame_tick_stream();
+    let unsub = ticker.subscribe(move |msg| match msg {
+      FrameMsg::NewFrame(_) => {}
+      FrameMsg::LayoutReady(time) => {
+        let p = group.shallow_ref().lerp(time);
+        if matches!(p, AnimateProgress::Finish) {
+          let scheduler = group.silent_ref().frame_scheduler.clone();
+          let group = group.clone();
+          observable::of(())
+            .delay(Duration::ZERO, scheduler)
+            .subscribe(move |_| {
+              group.silent_ref().stop();
+            });
+        }
+      }
+      // use silent_ref because the state of group change, bu no need to effect the framework.
+      FrameMsg::Finish(_) => group.silent_ref().frame_finished(),
+    });
+    let guard = BoxSubscription::new(unsub).unsubscribe_when_dropped();
+    self.running_info = Some(GroupInfo {
+      anchor_at: now,
+      anchor_pos: pos,
+      timeline,
+      total,
+      last_progress: AnimateProgress::Dismissed,
+      already_lerp: false,
+      _tick_msg_guard: Some(guard),
+    });
+    self.animate_track.set_actived(true);
+  }
+}
+
+impl AnimateGroup {
+  /// Stop the group and all its animations.
+  pub fn stop(&mut self) {
+    if self.running_info.take().is_some() {
+      let reversed = self.reversed;
+      self.animations.iter().for_each(|a| a.finish(reversed));
+    }
+    self.animate_track.set_actived(false);
+  }
+
+  #[inline]
+  pub fn is_running(&self) -> bool { self.running_info.is_some() }
+
+  /// Reverse the direction of the group, the running group continues from
+  /// where it is to the other direction.
+  pub fn reverse(&mut self) {
+    let now = self.clock.borrow().now();
+    let pos = self.pos(now);
+    self.reversed = !self.reversed;
+    if let Some(info) = self.running_info.as_mut() {
+      info.anchor_at = now;
+      info.anchor_pos = pos;
+    }
+  }
+
+  /// Return if the group runs from the end to the start.
+  #[inline]
+  pub fn is_reversed(&self) -> bool { self.reversed }
+
+  /// Jump to the `progress` of the timeline, from `0.` to `1.`. If the group
+  /// is not running, it starts from the `progress` in the next run.
+  pub fn seek(&mut self, progress: f32) {
+    let Some(total) = self.total_duration() else {
+      log::warn!("Can't seek an animation group that never finishes.");
+      return;
+    };
+    let now = self.clock.borrow().now();
+    match self.running_info.as_mut() {
+      Some(info) => {
+        info.anchor_at = now;
+        info.anchor_pos = total.mul_f32(progress.clamp(0., 1.));
+      }
+      None => self.seek_to = Some(progress),
+    }
+  }
+
+  /// Return an observable stream of the progress of the group in every frame,
+  /// from `0.` to `1.`.
+  #[inline]
+  pub fn progress_stream(&self) -> Subject<'static, f32, Infallible> { self.progress.clone() }
+
+  /// The duration of the timeline, `None` if it never finishes.
+  pub fn total_duration(&self) -> Option<Duration> {
+    match self.running_info.as_ref() {
+      Some(info) => info.total,
+      None => total_of(&self.timeline().collect::<Vec<_>>()),
+    }
+  }
+
+  /// Return the start and the duration of every animation on the timeline.
+  fn timeline(&self) -> impl Iterator<Item = (Duration, Option<Duration>)> + '_ {
+    let mut start = Duration::ZERO;
+    self.animations.iter().enumerate().map(move |(idx, a)| {
+      let dur = a.duration();
+      let a_start = match self.mode {
+        GroupMode::Sequence => {
+          let a_start = start;
+          start += dur.unwrap_or(Duration::MAX - start);
+          a_start
+        }
+        GroupMode::Parallel => Duration::ZERO,
+        GroupMode::Stagger(offset) => offset * idx as u32,
+      };
+      (a_start, dur)
+    })
+  }
+
+  fn pos(&self, now: Instant) -> Duration {
+    let Some(info) = self.running_info.as_ref() else {
+      return Duration::ZERO;
+    };
+    let elapsed = now - info.anchor_at;
+    if self.reversed {
+      info.anchor_pos.saturating_sub(elapsed)
+    } else {
+      info.anchor_pos + elapsed
+    }
+  }
+
+  fn lerp(&mut self, now: Instant) -> AnimateProgress {
+    let info = self
+      .running_info
+      .as_ref()
+      .expect("This animation is not running.");
+    if info.already_lerp {
+      return info.last_progress;
+    }
+
+    let pos = self.pos(now);
+    let progress = self.lerp_at(pos, &info.timeline);
+    if let Some(total) = info.total {
+      let rate = if total.is_zero() {
+        1.
+      } else {
+        (pos.as_secs_f32() / total.as_secs_f32()).min(1.)
+      };
+      self.progress.next(rate);
+    }
+
+    let progress = match progress {
+      AnimateProgress::Finish if self.reversed => AnimateProgress::Between(1.),
+      AnimateProgress::Dismissed if self.reversed && pos.is_zero() => AnimateProgress::Finish,
+      p => p,
+    };
+    let info = self.running_info.as_mut().unwrap();
+    info.last_progress = progress;
+    info.already_lerp = true;
+    progress
+  }
+
+  /// Update all the animations to `pos` of the timeline, return `Finish` if
+  /// all the animations finished, or `Dismissed` if `pos` is at the start.
+  fn lerp_at(&self, pos: Duration, timeline: &[(Duration, Option<Duration>)]) -> AnimateProgress {
+    let mut finished = true;
+    self
+      .animations
+      .iter()
+      .zip(timeline)
+      .for_each(|(a, &(start, dur))| {
+        let elapsed = match dur {
+          // Make sure the animation is finished.
+          Some(dur) if matches!(start.checked_add(dur), Some(end) if pos >= end) => Duration::MAX,
+          _ => pos.saturating_sub(start),
+        };
+        if !a.lerp_by(elapsed).is_finish() {
+          finished = false;
+        }
+      });
+
+    if finished {
+      AnimateProgress::Finish
+    } else if pos.is_zero() {
+      AnimateProgress::Dismissed
+    } else {
+      AnimateProgress::Between(pos.as_secs_f32())
+    }
+  }
+
+  fn frame_finished(&mut self) {
+    if let Some(info) = self.running_info.as_mut() {
+      info.already_lerp = false;
+      self.animations.iter().for_each(|a| a.frame_finished());
+    }
+  }
+}
+
+impl AnimateCtrl for Stateful<AnimateGroup> {
+  fn prepare(&self) {
+    let mut this = self.silent_ref();
+    this.stop();
+    this.animations.iter().for_each(|a| a.prepare());
+    let timeline: Vec<_> = this.timeline().collect();
+    let now = this.clock.borrow().now();
+    this.running_info = Some(GroupInfo {
+      anchor_at: now,
+      anchor_pos: Duration::ZERO,
+      total: total_of(&timeline),
+      timeline,
+      last_progress: AnimateProgress::Dismissed,
+      already_lerp: false,
+      _tick_msg_guard: None,
+    });
+  }
+
+  #[inline]
+  fn duration(&self) -> Option<Duration> { self.state_ref().total_duration() }
+
+  fn lerp_by(&self, elapsed: Duration) -> AnimateProgress {
+    let this = self.state_ref();
+    let info = this
+      .running_info
+      .as_ref()
+      .expect("This animation is not running.");
+    this.lerp_at(elapsed, &info.timeline)
+  }
+
+  #[inline]
+  fn frame_finished(&self) {
+    let this = self.state_ref();
+    this.animations.iter().for_each(|a| a.frame_finished());
+  }
+
+  #[inline]
+  fn finish(&self, reversed: bool) {
+    let mut this = self.silent_ref();
+    this.running_info = None;
+    this.animations.iter().for_each(|a| a.finish(reversed));
+  }
+}
+
+/// The duration of the `timeline`, `None` if it never finishes.
+fn total_of(timeline: &[(Duration, Option<Duration>)]) -> Option<Duration> {
+  let mut total = Duration::ZERO;
+  for &(start, dur) in timeline {
+    total = total.max(start.checked_add(dur?)?);
+  }
+  Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{animation::Prop, declare::Declare, state::Stateful, test::ManualFrames};
+
+  fn animate(frames: &ManualFrames, value: &Stateful<f32>) -> Box<dyn AnimateCtrl> {
+    let animate = frames.build(|ctx| {
+      Animate::declare_builder()
+        .transition(
+          Transition::declare_builder()
+            .easing(easing::LINEAR)
+            .duration(Duration::from_millis(100))
+            .build(ctx),
+        )
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .from(0.)
+        .build(ctx)
+    });
+    Box::new(Stateful::new(animate))
+  }
+
+  fn new_group(
+    frames: &ManualFrames,
+    mode: GroupMode,
+    animations: Vec<Box<dyn AnimateCtrl>>,
+  ) -> Stateful<AnimateGroup> {
+    let group = frames.build(|ctx| {
+      AnimateGroup::declare_builder()
+        .mode(mode)
+        .animations(animations)
+        .build(ctx)
+    });
+    Stateful::new(group)
+  }
+
+  fn step(frames: &mut ManualFrames, ms: u64, values: &[&Stateful<f32>]) -> Vec<f32> {
+    frames.step(ms, || values.iter().map(|v| *v.state_ref()).collect())
+  }
+
+  fn assert_near(values: Vec<f32>, expect: &[f32]) {
+    assert_eq!(values.len(), expect.len());
+    values.iter().zip(expect).for_each(|(v, e)| {
+      assert!((v - e).abs() < 1e-5, "{values:?} is not near {expect:?}");
+    });
+  }
+
+  #[test]
+  fn sequence() {
+    let mut frames = ManualFrames::default();
+    let (a, b) = (Stateful::new(1.), Stateful::new(1.));
+    let animations = vec![animate(&frames, &a), animate(&frames, &b)];
+    let group = new_group(&frames, GroupMode::Sequence, animations);
+    assert_eq!(
+      group.state_ref().total_duration(),
+      Some(Duration::from_millis(200))
+    );
+
+    let progress = Rc::new(RefCell::new(0.));
+    let c_progress = progress.clone();
+    group
+      .state_ref()
+      .progress_stream()
+      .subscribe(move |p| *c_progress.borrow_mut() = p);
+
+    group.state_ref().run();
+    assert_near(step(&mut frames, 50, &[&a, &b]), &[0.5, 0.]);
+    assert_eq!(*progress.borrow(), 0.25);
+    assert_near(step(&mut frames, 100, &[&a, &b]), &[1., 0.5]);
+    assert_eq!(*progress.borrow(), 0.75);
+  }
+
+  #[test]
+  fn parallel_and_stagger() {
+    let mut frames = ManualFrames::default();
+    let (a, b) = (Stateful::new(1.), Stateful::new(1.));
+    let animations = vec![animate(&frames, &a), animate(&frames, &b)];
+    let group = new_group(&frames, GroupMode::Parallel, animations);
+    assert_eq!(
+      group.state_ref().total_duration(),
+      Some(Duration::from_millis(100))
+    );
+    group.state_ref().run();
+    assert_near(step(&mut frames, 50, &[&a, &b]), &[0.5, 0.5]);
+
+    let stagger = GroupMode::Stagger(Duration::from_millis(20));
+    let animations = vec![animate(&frames, &a), animate(&frames, &b)];
+    let group = new_group(&frames, stagger, animations);
+    assert_eq!(
+      group.state_ref().total_duration(),
+      Some(Duration::from_millis(120))
+    );
+    group.state_ref().run();
+    assert_near(step(&mut frames, 70, &[&a, &b]), &[0.7, 0.5]);
+  }
+
+  #[test]
+  fn reverse_and_seek() {
+    let mut frames = ManualFrames::default();
+    let (a, b) = (Stateful::new(1.), Stateful::new(1.));
+    let animations = vec![animate(&frames, &a), animate(&frames, &b)];
+    let group = new_group(&frames, GroupMode::Sequence, animations);
+
+    group.state_ref().seek(0.75);
+    group.state_ref().run();
+    assert_near(step(&mut frames, 0, &[&a, &b]), &[1., 0.5]);
+
+    group.state_ref().reverse();
+    assert_near(step(&mut frames, 100, &[&a, &b]), &[0.5, 0.]);
+
+    group.state_ref().seek(0.5);
+    assert_near(step(&mut frames, 20, &[&a, &b]), &[0.8, 0.]);
+
+    step(&mut frames, 100, &[&a, &b]);
+    step(&mut frames, 0, &[&a, &b]);
+    assert!(!group.state_ref().is_running());
+    // reversed to the start, keep the `from` value.
+    assert_eq!((*a.state_ref(), *b.state_ref()), (0., 0.));
+  }
+
+  #[test]
+  fn keep_frames_coming() {
+    let mut frames = ManualFrames::default();
+    let a = Stateful::new(1.);
+    let group = new_group(&frames, GroupMode::Sequence, vec![animate(&frames, &a)]);
+    group.state_ref().run();
+    assert!(frames.has_actived_animate());
+    step(&mut frames, 200, &[&a]);
+    step(&mut frames, 0, &[&a]);
+    assert!(!frames.has_actived_animate());
+    assert_eq!(*a.state_ref(), 1.);
+  }
+
+  #[test]
+  fn nested_group() {
+    let mut frames = ManualFrames::default();
+    let (a, b) = (Stateful::new(1.), Stateful::new(1.));
+    let inner = new_group(&frames, GroupMode::Parallel, vec![animate(&frames, &a)]);
+    let inner: Box<dyn AnimateCtrl> = Box::new(inner);
+    let animations = vec![animate(&frames, &b), inner];
+    let outer = new_group(&frames, GroupMode::Sequence, animations);
+    assert_eq!(
+      outer.state_ref().total_duration(),
+      Some(Duration::from_millis(200))
+    );
+
+    outer.state_ref().run();
+    assert_near(step(&mut frames, 50, &[&a, &b]), &[0., 0.5]);
+    assert_near(step(&mut frames, 100, &[&a, &b]), &[0.5, 1.]);
+  }
+}
@@ -0,0 +1,331 @@
+use crate::{impl_query_self_only, prelude::*, widget_tree::paint_subtree};
+use std::{collections::HashMap, rc::Rc};
+
+/// A widget flies from its old place to its new place when it's replaced by
+/// another `Hero` with the same `tag`, also known as shared element
+/// transition.
+///
+/// When a `Hero` is disposed and a `Hero` with the same tag is mounted in the
+/// same layout, the new one flies from the layout rect of the old one to its
+/// own. The flight is painted over the whole tree, and it interpolates the
+/// layout rect, the transform and the `border_radius` of the hero.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let w = widget! {
+///   states { detail: Stateful::new(false) }
+///   DynWidget {
+///     dyns: if *detail {
+///       widget! {
+///         Hero {
+///           tag: "avatar",
+///           Void { background: Color::RED, border_radius: Radius::all(4.) }
+///         }
+///       }
+///     } else {
+///       widget! {
+///         Hero {
+///           tag: "avatar",
+///           Void {
+///             left_anchor: 100.,
+///             background: Color::RED,
+///             border_radius: Radius::all(20.),
+///           }
+///         }
+///       }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Hero {
+  /// The heroes with the same tag are treated as the same hero.
+  #[declare(convert=into)]
+  pub tag: Key,
+  /// The transition of the flight.
+  #[declare(default = transitions::EASE_IN_OUT.of(ctx))]
+  pub transition: Rc<Box<dyn Roc>>,
+}
+
+/// The paint information of a hero in the global coordinate.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HeroSnapshot {
+  rect: Rect,
+  transform: Transform,
+  radius: Option<Radius>,
+}
+
+type StartFlight = Box<dyn FnOnce(HeroSnapshot)>;
+
+/// Track the heroes leave and enter in a layout, and the heroes in flight.
+#[derive(Default)]
+pub(crate) struct HeroTrack {
+  leaving: HashMap<Key, HeroSnapshot>,
+  entering: HashMap<Key, (WidgetId, StartFlight)>,
+  flights: Vec<WidgetId>,
+}
+
+impl HeroTrack {
+  fn leave(&mut self, tag: Key, snapshot: HeroSnapshot) {
+    if let Some((id, start)) = self.entering.remove(&tag) {
+      self.flights.push(id);
+      start(snapshot);
+    } else {
+      self.leaving.insert(tag, snapshot);
+    }
+  }
+
+  fn enter(&mut self, tag: Key, id: WidgetId, start: StartFlight) {
+    if let Some(snapshot) = self.leaving.remove(&tag) {
+      self.flights.push(id);
+      start(snapshot);
+    } else {
+      self.entering.insert(tag, (id, start));
+    }
+  }
+
+  /// The heroes not leave and enter in the same layout not fly.
+  pub(crate) fn end_layout(&mut self) {
+    self.leaving.clear();
+    self.entering.clear();
+  }
+}
+
+#[derive(Declare, SingleChild)]
+struct HeroRender {
+  #[declare(default = 1.)]
+  rate: f32,
+  #[declare(default)]
+  from: Option<HeroSnapshot>,
+}
+
+impl HeroRender {
+  fn is_flying(&self) -> bool { self.from.is_some() && self.rate < 1. }
+}
+
+impl ComposeChild for Hero {
+  type Child = Widget;
+
+  fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
+    let (tag, transition) = {
+      let this = this.into_readonly();
+      let this = this.state_ref();
+      (this.tag.clone(), this.transition.clone())
+    };
+    let leave_tag = tag.clone();
+    widget! {
+      HeroRender {
+        id: hero,
+        on_mounted: move |ctx| {
+          let start = move |from: HeroSnapshot| {
+            // Release the hero before the flight runs, it reads the hero.
+            (move || hero.from = Some(from))();
+            flight.run();
+          };
+          let track = ctx.wnd_ctx().hero_track.clone();
+          track.borrow_mut().enter(tag.clone(), ctx.id, Box::new(start));
+        },
+        on_disposed: move |ctx| {
+          let snapshot = hero_of(ctx.id, ctx.arena)
+            .and_then(|id| hero_snapshot(id, ctx.arena, ctx.store));
+          if let Some(snapshot) = snapshot {
+            let track = ctx.wnd_ctx().hero_track.clone();
+            track.borrow_mut().leave(leave_tag.clone(), snapshot);
+          }
+        },
+        DynWidget { dyns: child }
+      }
+      Animate {
+        id: flight,
+        transition,
+        prop: prop!(hero.rate),
+        from: 0.,
+      }
+    }
+  }
+}
+
+impl Render for HeroRender {
+  #[inline]
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    ctx.assert_perform_single_child_layout(clamp)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    // The hero in flight is painted over the tree, not in its place.
+    if self.is_flying() {
+      ctx.painter().apply_alpha(0.);
+    }
+  }
+
+  fn can_overflow(&self) -> bool { true }
+
+  fn hit_test(&self, _: &HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: true }
+  }
+}
+
+impl Query for HeroRender {
+  impl_query_self_only!();
+}
+
+/// Paint the heroes in flight over the tree, and forget the heroes that landed.
+pub(crate) fn paint_hero_flights(ctx: &mut PaintingCtx) {
+  let arena = ctx.arena;
+  let track = ctx.wnd_ctx.hero_track.clone();
+  track.borrow_mut().flights.retain(|id| {
+    if id.is_dropped(arena) {
+      return false;
+    }
+    let Some(hero) = hero_of(*id, arena) else { return false };
+    let mut flight = None;
+    hero
+      .assert_get(arena)
+      .query_on_first_type(QueryOrder::OutsideFirst, |h: &HeroRender| {
+        if h.is_flying() {
+          flight = h.from.clone().map(|from| (from, h.rate));
+        }
+      });
+    let Some((from, rate)) = flight else { return false };
+    let Some(to) = hero_snapshot(hero, arena, ctx.store) else { return true };
+
+    let rect = Lerp::lerp(&from.rect, &to.rect, rate);
+    let scale = |from: f32, to: f32| if to > 0. { from / to } else { 1. };
+    let painter = &mut ctx.painter;
+    painter.save();
+    painter.translate(rect.min_x(), rect.min_y());
+    painter.scale(
+      scale(rect.width(), to.rect.width()),
+      scale(rect.height(), to.rect.height()),
+    );
+    // The hero paints its own transform, replace it with the interpolated one.
+    if let Some(inverse) = to.transform.inverse() {
+      let transform = Lerp::lerp(&from.transform, &to.transform, rate);
+      painter.apply_transform(&inverse.then(&transform));
+    }
+    if from.radius.is_some() || to.radius.is_some() {
+      let radius = Lerp::lerp(
+        &from.radius.unwrap_or_default(),
+        &to.radius.unwrap_or_default(),
+        rate,
+      );
+      let clip = Rect::from_size(to.rect.size);
+      painter.clip(Path::rect_round(&clip, &radius, PathStyle::Fill));
+    }
+    let mut child = hero.first_child(arena);
+    while let Some(c) = child {
+      paint_subtree(c, ctx);
+      child = c.next_sibling(arena);
+    }
+    ctx.painter.restore();
+    true
+  });
+}
+
+/// Find the `HeroRender` from the outermost widget of the hero.
+fn hero_of(mut id: WidgetId, arena: &TreeArena) -> Option<WidgetId> {
+  loop {
+    let mut is_hero = false;
+    id.assert_get(arena)
+      .query_on_first_type(QueryOrder::OutsideFirst, |_: &HeroRender| is_hero = true);
+    if is_hero {
+      return Some(id);
+    }
+    id = id.first_child(arena)?;
+  }
+}
+
+fn hero_snapshot(hero: WidgetId, arena: &TreeArena, store: &LayoutStore) -> Option<HeroSnapshot> {
+  let pos = store.map_to_global(Point::zero(), hero, arena);
+  // The size of the hero may be cleared to relayout, but its single child has
+  // the same size.
+  let mut size = None;
+  let mut transform = None;
+  let mut radius = None;
+  let mut w = Some(hero);
+  while let Some(id) = w {
+    if size.is_none() {
+      size = store.layout_box_size(id);
+    }
+    let render = id.assert_get(arena);
+    if transform.is_none() {
+      transform = render.get_transform();
+    }
+    if radius.is_none() {
+      render.query_on_first_type(QueryOrder::OutsideFirst, |d: &BoxDecoration| {
+        radius = d.border_radius;
+      });
+    }
+    w = id
+      .first_child(arena)
+      .filter(|c| c.next_sibling(arena).is_none());
+  }
+  Some(HeroSnapshot {
+    rect: Rect::new(pos, size?),
+    transform: transform.unwrap_or_default(),
+    radius,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::time::Duration;
+
+  #[test]
+  fn fly_between_heroes() {
+    let detail = Stateful::new(false);
+    let w = widget! {
+      states { detail: detail.clone() }
+      DynWidget {
+        dyns: if *detail {
+          widget! {
+            Hero {
+              tag: "avatar",
+              MockBox {
+                size: Size::new(100., 100.),
+                left_anchor: 50.,
+                border_radius: Radius::all(4.),
+              }
+            }
+          }
+        } else {
+          widget! { Hero { tag: "avatar", MockBox { size: Size::new(10., 10.) } } }
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+    let flight_from = |wnd: &Window| {
+      let arena = &wnd.widget_tree.arena;
+      let track = wnd.context.hero_track.borrow();
+      track.flights.first().map(|id| {
+        let mut from = None;
+        hero_of(*id, arena)
+          .unwrap()
+          .assert_get(arena)
+          .query_on_first_type(QueryOrder::OutsideFirst, |h: &HeroRender| {
+            from = h.from.clone()
+          });
+        from.unwrap()
+      })
+    };
+    assert_eq!(flight_from(&wnd), None);
+
+    *detail.state_ref() = true;
+    wnd.draw_frame();
+    let from = flight_from(&wnd).unwrap();
+    assert_eq!(from.rect, Rect::new(Point::zero(), Size::new(10., 10.)));
+    assert_eq!(from.radius, None);
+
+    clock.advance(Duration::from_secs(2));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(flight_from(&wnd), None);
+  }
+}
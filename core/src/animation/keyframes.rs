@@ -0,0 +1,307 @@
+use crate::{
+  prelude::*,
+  ticker::{FrameMsg, FrameTicker},
+};
+use std::{
+  cell::RefCell,
+  rc::Rc,
+  time::{Duration, Instant},
+};
+
+use super::property::AnimateProperty;
+
+/// A stop of the [`KeyframeAnimate`].
+#[derive(Clone)]
+pub struct KeyFrame<V> {
+  /// Where the stop is in the animation, from `0.` to `1.`.
+  pub offset: f32,
+  /// The value of the property at this stop.
+  pub value: V,
+  /// The easing from the previous stop to this stop, linear if `None`.
+  pub easing: Option<Rc<dyn Easing>>,
+}
+
+impl<V> KeyFrame<V> {
+  #[inline]
+  pub fn new(offset: f32, value: V) -> Self { Self { offset, value, easing: None } }
+
+  /// Specify the easing from the previous stop to this stop.
+  #[inline]
+  pub fn with_easing(mut self, easing: impl Easing + 'static) -> Self {
+    self.easing = Some(Rc::new(easing));
+    self
+  }
+}
+
+/// Animate a property through multiple stops.
+///
+/// The `transition` decides the duration, delay and repeat of the animation,
+/// and its progress is the offset of the `keyframes`. The value of the property
+/// before the animation runs is used as the stop at `0.` and `1.` if the
+/// `keyframes` not specify them, and the property is restored to it after the
+/// animation finished.
+///
+/// The offset of the `keyframes` must be in ascending order and within `0.` to
+/// `1.`, it panics in debug build if not.
+#[derive(Declare)]
+pub struct KeyframeAnimate<T, P: AnimateProperty> {
+  pub transition: T,
+  pub prop: P,
+  pub keyframes: Vec<KeyFrame<P::Value>>,
+  #[declare(skip)]
+  running_info: Option<KeyframeInfo<P::Value>>,
+  #[declare(skip, default = ctx.wnd_ctx().frame_ticker.clone())]
+  frame_ticker: FrameTicker,
+  #[declare(skip, default = ctx.wnd_ctx().animate_track())]
+  animate_track: AnimateTrack,
+  #[declare(skip, default = ctx.wnd_ctx().frame_scheduler())]
+  frame_scheduler: FuturesLocalScheduler,
+  #[declare(skip, default = ctx.wnd_ctx().clock.clone())]
+  clock: Rc<RefCell<Box<dyn Clock>>>,
+}
+
+struct KeyframeInfo<V> {
+  origin: V,
+  start_at: Instant,
+  last_progress: AnimateProgress,
+  // Determines if lerp value in current frame.
+  already_lerp: bool,
+  _tick_msg_guard: Option<SubscriptionGuard<BoxSubscription<'static>>>,
+}
+
+impl<'a, T: Roc, P: AnimateProperty> StateRef<'a, KeyframeAnimate<T, P>>
+where
+  KeyframeAnimate<T, P>: 'static,
+{
+  /// Run the animation, restart it from the first stop if it's running.
+  pub fn run(&mut self) {
+    let now = self.clock.borrow().now();
+    if let Some(info) = self.running_info.as_mut() {
+      info.start_at = now;
+      return;
+    }
+
+    let animate = self.clone_stateful();
+    let ticker = self.frame_ticker.frame_tick_stream();
+    let unsub = ticker.subscribe(move |msg| match msg {
+      FrameMsg::NewFrame(_) => {}
+      FrameMsg::LayoutReady(time) => {
+        let p = animate.shallow_ref().lerp(time);
+        if matches!(p, AnimateProgress::Finish) {
+          let scheduler = animate.silent_ref().frame_scheduler.clone();
+          let animate = animate.clone();
+          observable::of(())
+            .delay(Duration::ZERO, scheduler)
+            .subscribe(move |_| {
+              animate.silent_ref().stop();
+            });
+        }
+      }
+      // use silent_ref because the state of animate change, bu no need to effect the framework.
+      FrameMsg::Finish(_) => animate.silent_ref().frame_finished(),
+    });
+    let guard = BoxSubscription::new(unsub).unsubscribe_when_dropped();
+    self.running_info = Some(KeyframeInfo {
+      origin: self.prop.get(),
+      start_at: now,
+      last_progress: AnimateProgress::Dismissed,
+      already_lerp: false,
+      _tick_msg_guard: Some(guard),
+    });
+    self.animate_track.set_actived(true);
+  }
+}
+
+impl<T: Roc, P: AnimateProperty> KeyframeAnimate<T, P> {
+  fn lerp(&mut self, now: Instant) -> AnimateProgress {
+    let start_at = self
+      .running_info
+      .as_ref()
+      .expect("This animation is not running.")
+      .start_at;
+    self.lerp_by(now - start_at)
+  }
+
+  fn lerp_by(&mut self, elapsed: Duration) -> AnimateProgress {
+    let KeyframeInfo {
+      origin,
+      last_progress,
+      already_lerp,
+      ..
+    } = self
+      .running_info
+      .as_mut()
+      .expect("This animation is not running.");
+
+    if *already_lerp {
+      return *last_progress;
+    }
+
+    let progress = self.transition.rate_of_change(elapsed);
+    if let AnimateProgress::Between(rate) = progress {
+      let prop = &self.prop;
+      let value = keyframes_value(origin, &self.keyframes, rate, |from, to, rate| {
+        prop.calc_lerp_value(from, to, rate)
+      });
+      self.prop.shallow_set(value);
+    }
+
+    *last_progress = progress;
+    *already_lerp = true;
+
+    progress
+  }
+
+  fn frame_finished(&mut self) {
+    let info = self
+      .running_info
+      .as_mut()
+      .expect("This animation is not running.");
+
+    if matches!(info.last_progress, AnimateProgress::Between(_)) {
+      self.prop.set(info.origin.clone())
+    }
+    info.already_lerp = false;
+  }
+
+  pub fn stop(&mut self) {
+    self.animate_track.set_actived(false);
+    self.running_info.take();
+  }
+
+  #[inline]
+  pub fn is_running(&self) -> bool { self.running_info.is_some() }
+}
+
+impl<T: Roc, P: AnimateProperty> AnimateCtrl for Stateful<KeyframeAnimate<T, P>>
+where
+  KeyframeAnimate<T, P>: 'static,
+{
+  fn prepare(&self) {
+    let mut this = self.silent_ref();
+    this.stop();
+    let start_at = this.clock.borrow().now();
+    this.running_info = Some(KeyframeInfo {
+      origin: this.prop.get(),
+      start_at,
+      last_progress: AnimateProgress::Dismissed,
+      already_lerp: false,
+      _tick_msg_guard: None,
+    });
+  }
+
+  #[inline]
+  fn duration(&self) -> Option<Duration> { self.state_ref().transition.total_duration() }
+
+  #[inline]
+  fn lerp_by(&self, elapsed: Duration) -> AnimateProgress { self.shallow_ref().lerp_by(elapsed) }
+
+  #[inline]
+  fn frame_finished(&self) { self.silent_ref().frame_finished() }
+
+  #[inline]
+  fn finish(&self, _: bool) { self.silent_ref().stop() }
+}
+
+/// Calc the value at `rate` of the `keyframes`, `origin` is the value of the
+/// stop at `0.` and `1.` if the `keyframes` not specify them.
+fn keyframes_value<V: Clone>(
+  origin: &V,
+  keyframes: &[KeyFrame<V>],
+  rate: f32,
+  lerp: impl Fn(&V, &V, f32) -> V,
+) -> V {
+  debug_assert!(
+    keyframes.iter().all(|f| (0. ..=1.).contains(&f.offset))
+      && keyframes.windows(2).all(|w| w[0].offset <= w[1].offset),
+    "The offsets of the keyframes must be ascending and within [0, 1]."
+  );
+
+  let segment_rate = |begin: f32, end: f32| {
+    if end > begin {
+      (rate - begin) / (end - begin)
+    } else {
+      1.
+    }
+  };
+
+  let mut prev = (0., origin);
+  for frame in keyframes {
+    if rate <= frame.offset {
+      let t = segment_rate(prev.0, frame.offset);
+      let t = frame.easing.as_ref().map_or(t, |e| e.easing(t));
+      return lerp(prev.1, &frame.value, t);
+    }
+    prev = (frame.offset, &frame.value);
+  }
+
+  lerp(prev.1, origin, segment_rate(prev.0, 1.))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{animation::Prop, declare::Declare, state::Stateful, test::ManualFrames};
+
+  #[test]
+  fn keyframes_lerp() {
+    let frames = vec![
+      KeyFrame::new(0.5, 10.),
+      KeyFrame::new(0.75, 0.).with_easing(easing::StepEnd(1.)),
+    ];
+    let value = |rate| keyframes_value(&2., &frames, rate, |a: &f32, b: &f32, t| a.lerp(b, t));
+
+    assert_eq!(value(0.), 2.);
+    assert_eq!(value(0.25), 6.);
+    assert_eq!(value(0.5), 10.);
+    assert_eq!(value(0.6), 0.);
+    assert_eq!(value(0.75), 0.);
+    assert_eq!(value(0.875), 1.);
+    assert_eq!(value(1.), 2.);
+  }
+
+  #[test]
+  #[should_panic(expected = "The offsets of the keyframes must be ascending")]
+  fn descending_keyframes() {
+    let frames = vec![KeyFrame::new(0.75, 10.), KeyFrame::new(0.5, 0.)];
+    keyframes_value(&2., &frames, 0.6, |a: &f32, b: &f32, t| a.lerp(b, t));
+  }
+
+  #[test]
+  #[should_panic(expected = "The offsets of the keyframes must be ascending")]
+  fn keyframe_out_of_range() {
+    let frames = vec![KeyFrame::new(1.5, 10.)];
+    keyframes_value(&2., &frames, 0.6, |a: &f32, b: &f32, t| a.lerp(b, t));
+  }
+
+  #[test]
+  fn run_keyframes() {
+    let mut frames = ManualFrames::default();
+    let value = Stateful::new(0.);
+    let animate = frames.build(|ctx| {
+      KeyframeAnimate::declare_builder()
+        .transition(
+          Transition::declare_builder()
+            .easing(easing::LINEAR)
+            .duration(Duration::from_millis(100))
+            .build(ctx),
+        )
+        .prop(Prop::new(
+          value.clone(),
+          |v| *v,
+          |v: &mut f32, n: f32| *v = n,
+        ))
+        .keyframes(vec![KeyFrame::new(0.5, 1.)])
+        .build(ctx)
+    });
+
+    let animate = Stateful::new(animate);
+    animate.state_ref().run();
+
+    let mut step = |ms| frames.step(ms, || *value.state_ref());
+    assert_eq!(step(25), 0.5);
+    assert_eq!(step(25), 1.);
+    assert_eq!(step(25), 0.5);
+    assert_eq!(step(50), 0.);
+  }
+}
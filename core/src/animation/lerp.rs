@@ -1,8 +1,8 @@
 use ribir_painter::Transform;
 
 use crate::prelude::{
-  Angle, Box2D, Brush, Color, DevicePoint, DeviceRect, DeviceSize, DeviceVector, Point, Radius,
-  Rect, Size, Vector,
+  Angle, Box2D, Brush, Color, DevicePoint, DeviceRect, DeviceSize, DeviceVector, Gradient,
+  GradientStop, Point, Radius, Rect, Size, Vector,
 };
 
 /// Linearly interpolate between two value.
@@ -10,6 +10,11 @@ use crate::prelude::{
 /// * `factor`: the percent of the distance between two value to advance.s
 pub trait Lerp {
   fn lerp(&self, to: &Self, factor: f32) -> Self;
+
+  /// The inverse of `lerp`, return the factor of `self` between `from` and
+  /// `to`, the value out of the line from `from` to `to` is projected to it.
+  /// Return `None` if the factor can't be measured.
+  fn inverse_lerp(&self, _from: &Self, _to: &Self) -> Option<f32> { None }
 }
 
 macro_rules! impl_lerp_for_integer {
@@ -20,6 +25,10 @@ macro_rules! impl_lerp_for_integer {
         fn lerp(&self, to: &Self, factor: f32) -> Self{
         (*self as f32 * (1. - factor)   +  *to as f32 * factor) as $ty
         }
+
+        fn inverse_lerp(&self, from: &Self, to: &Self) -> Option<f32> {
+          (*self as f32).inverse_lerp(&(*from as f32), &(*to as f32))
+        }
       }
     )*
   }
@@ -29,10 +38,18 @@ impl_lerp_for_integer! { i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
 
 impl Lerp for f32 {
   fn lerp(&self, to: &Self, factor: f32) -> Self { factor.mul_add(to - self, *self) }
+
+  fn inverse_lerp(&self, from: &Self, to: &Self) -> Option<f32> {
+    (from != to).then(|| (self - from) / (to - from))
+  }
 }
 
 impl Lerp for f64 {
   fn lerp(&self, to: &Self, factor: f32) -> Self { (factor as f64).mul_add(to - self, *self) }
+
+  fn inverse_lerp(&self, from: &Self, to: &Self) -> Option<f32> {
+    (from != to).then(|| ((self - from) / (to - from)) as f32)
+  }
 }
 
 impl Lerp for bool {
@@ -88,6 +105,13 @@ macro_rules! impl_lerp_for_copy_geom {
         fn lerp(&self, to: &Self, factor: f32) -> Self{
           $ty::lerp(*self, *to, factor)
         }
+
+        fn inverse_lerp(&self, from: &Self, to: &Self) -> Option<f32> {
+          let [x, y] = (*self - *from).to_array();
+          let [dx, dy] = (*to - *from).to_array();
+          let len2 = dx * dx + dy * dy;
+          (len2 > 0.).then(|| (x * dx + y * dy) / len2)
+        }
       }
     )*
   }
@@ -150,14 +174,91 @@ impl Lerp for Brush {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     match (self, to) {
       (Brush::Color(from), Brush::Color(to)) => from.lerp(to, factor).into(),
-      _ => {
-        // todo: only support pure color brush now.
-        to.clone()
+      (Brush::Gradient(from), Brush::Gradient(to)) => from.lerp(to, factor).into(),
+      (Brush::Color(from), Brush::Gradient(to)) => pure_gradient(*from, to).lerp(to, factor).into(),
+      (Brush::Gradient(from), Brush::Color(to)) => {
+        from.lerp(&pure_gradient(*to, from), factor).into()
       }
+      // An image can't mix into another brush, cross-fade them by painting in
+      // two layers.
+      _ if factor <= 0. => self.clone(),
+      _ if factor >= 1. => to.clone(),
+      _ => Brush::CrossFade {
+        from: Box::new(self.clone()),
+        to: Box::new(to.clone()),
+        factor,
+      },
+    }
+  }
+}
+
+impl Lerp for GradientStop {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    GradientStop {
+      offset: self.offset.lerp(&to.offset, factor),
+      color: self.color.lerp(&to.color, factor),
     }
   }
 }
 
+impl Lerp for Gradient {
+  /// Interpolate the stops pairwise if the gradients have the same number of
+  /// stops, otherwise resample both gradients at the offsets of all their
+  /// stops.
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let stops = if self.stops.len() == to.stops.len() {
+      self
+        .stops
+        .iter()
+        .zip(to.stops.iter())
+        .map(|(from, to)| from.lerp(to, factor))
+        .collect()
+    } else {
+      let mut offsets: Vec<f32> = self
+        .stops
+        .iter()
+        .chain(to.stops.iter())
+        .map(|s| s.offset)
+        .collect();
+      offsets.sort_by(|a, b| a.total_cmp(b));
+      offsets.dedup();
+      offsets
+        .into_iter()
+        .map(|offset| GradientStop {
+          offset,
+          color: gradient_color_at(self, offset).lerp(&gradient_color_at(to, offset), factor),
+        })
+        .collect()
+    };
+    Gradient { stops }
+  }
+}
+
+/// A gradient in the stops of `layout`, but all of them are `color`.
+fn pure_gradient(color: Color, layout: &Gradient) -> Gradient {
+  let stops = layout
+    .stops
+    .iter()
+    .map(|s| GradientStop { offset: s.offset, color })
+    .collect();
+  Gradient { stops }
+}
+
+fn gradient_color_at(gradient: &Gradient, offset: f32) -> Color {
+  let stops = &gradient.stops;
+  let idx = stops.partition_point(|s| s.offset < offset);
+  match (idx.checked_sub(1).map(|i| &stops[i]), stops.get(idx)) {
+    (Some(before), Some(after)) => {
+      let range = after.offset - before.offset;
+      before
+        .color
+        .lerp(&after.color, (offset - before.offset) / range)
+    }
+    (Some(s), None) | (None, Some(s)) => s.color,
+    (None, None) => Color::TRANSPARENT,
+  }
+}
+
 impl Lerp for Transform {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     let m11 = self.m11.lerp(&to.m11, factor);
@@ -174,6 +275,7 @@ impl Lerp for Transform {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use ribir_painter::{image::ColorFormat, PixelImage, ShallowImage, TileMode};
 
   extern crate test;
   use test::Bencher;
@@ -222,6 +324,120 @@ mod tests {
     assert!(t1 == Lerp::lerp(&t1, &t2, 0.));
   }
 
+  #[test]
+  fn inverse_lerp() {
+    assert_eq!(5.0f32.inverse_lerp(&0., &10.), Some(0.5));
+    assert_eq!(15.0f64.inverse_lerp(&10., &0.), Some(-0.5));
+    assert_eq!(3u8.inverse_lerp(&2, &6), Some(0.25));
+    assert_eq!(1.0f32.inverse_lerp(&2., &2.), None);
+
+    // projected to the line.
+    let p = Point::new(5., 3.).inverse_lerp(&Point::zero(), &Point::new(10., 0.));
+    assert_eq!(p, Some(0.5));
+    assert_eq!(Color::RED.inverse_lerp(&Color::BLUE, &Color::GREEN), None);
+  }
+
+  #[test]
+  fn lerp_brush() {
+    let red: Brush = Color::RED.into();
+    let blue: Brush = Color::BLUE.into();
+    assert_eq!(
+      red.lerp(&blue, 0.5),
+      Color::RED.lerp(&Color::BLUE, 0.5).into()
+    );
+  }
+
+  fn stop(offset: f32, color: Color) -> GradientStop { GradientStop { offset, color } }
+
+  #[test]
+  fn lerp_gradient() {
+    let from: Brush = Gradient {
+      stops: vec![stop(0., Color::RED), stop(1., Color::BLUE)],
+    }
+    .into();
+    let to: Brush = Gradient {
+      stops: vec![stop(0.2, Color::BLUE), stop(0.8, Color::RED)],
+    }
+    .into();
+    let expect: Brush = Gradient {
+      stops: vec![
+        stop(0.1, Color::RED.lerp(&Color::BLUE, 0.5)),
+        stop(0.9, Color::BLUE.lerp(&Color::RED, 0.5)),
+      ],
+    }
+    .into();
+    assert_eq!(from.lerp(&to, 0.5), expect);
+
+    // resample the stops if the gradients have different number of stops.
+    let three_stops: Brush = Gradient {
+      stops: vec![
+        stop(0., Color::RED),
+        stop(0.5, Color::GREEN),
+        stop(1., Color::BLUE),
+      ],
+    }
+    .into();
+    let Brush::Gradient(mid) = from.lerp(&three_stops, 0.5) else {
+      panic!("gradient lerp into a gradient.");
+    };
+    let offsets: Vec<_> = mid.stops.iter().map(|s| s.offset).collect();
+    assert_eq!(offsets, vec![0., 0.5, 1.]);
+    assert_eq!(
+      mid.stops[1].color,
+      Color::RED.lerp(&Color::BLUE, 0.5).lerp(&Color::GREEN, 0.5)
+    );
+  }
+
+  #[test]
+  fn lerp_color_and_gradient() {
+    let red: Brush = Color::RED.into();
+    let gradient: Brush = Gradient {
+      stops: vec![stop(0., Color::BLUE), stop(1., Color::GREEN)],
+    }
+    .into();
+    let expect: Brush = Gradient {
+      stops: vec![
+        stop(0., Color::RED.lerp(&Color::BLUE, 0.2)),
+        stop(1., Color::RED.lerp(&Color::GREEN, 0.2)),
+      ],
+    }
+    .into();
+    assert_eq!(red.lerp(&gradient, 0.2), expect);
+
+    let expect: Brush = Gradient {
+      stops: vec![
+        stop(0., Color::BLUE.lerp(&Color::RED, 0.2)),
+        stop(1., Color::GREEN.lerp(&Color::RED, 0.2)),
+      ],
+    }
+    .into();
+    assert_eq!(gradient.lerp(&red, 0.2), expect);
+  }
+
+  #[test]
+  fn cross_fade_image() {
+    let red: Brush = Color::RED.into();
+    let img = Brush::Image {
+      img: ShallowImage::new(PixelImage::new(
+        std::borrow::Cow::Borrowed(&[0; 4]),
+        1,
+        1,
+        ColorFormat::Rgba8,
+      )),
+      tile_mode: TileMode::REPEAT_BOTH,
+    };
+    assert_eq!(red.lerp(&img, 0.), red);
+    assert_eq!(red.lerp(&img, 1.), img);
+    assert_eq!(
+      red.lerp(&img, 0.3),
+      Brush::CrossFade {
+        from: Box::new(red.clone()),
+        to: Box::new(img.clone()),
+        factor: 0.3,
+      }
+    );
+  }
+
   #[test]
   fn fix_avoid_calc_overflow() {
     assert_eq!(255u8.lerp(&0u8, 0.), 255);
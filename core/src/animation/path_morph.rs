@@ -0,0 +1,376 @@
+use super::Lerp;
+use crate::prelude::{Path, PathStyle, SvgPaths, SvgRenderPath};
+use lyon_algorithms::path::{Path as LyonPath, PathEvent};
+use lyon_geom::{CubicBezierSegment, Point, QuadraticBezierSegment};
+use std::{
+  cell::RefCell,
+  collections::{hash_map::DefaultHasher, VecDeque},
+  hash::{Hash, Hasher},
+  rc::Rc,
+};
+
+type Cubic = CubicBezierSegment<f32>;
+
+/// A sub path normalized to cubic segments, so any two sub paths can morph
+/// into each other segment by segment.
+struct SubPath {
+  segments: Vec<Cubic>,
+  closed: bool,
+}
+
+/// Two paths normalized to the same sub paths and segments, the pairs are
+/// matched and their start points aligned, only need to lerp the segments to
+/// morph.
+struct MorphPair {
+  from: Vec<SubPath>,
+  to: Vec<SubPath>,
+}
+
+/// How many recent morph pairs are cached.
+const MORPH_CACHE_SIZE: usize = 8;
+
+thread_local! {
+  // An animation morphs the same pair in every frame, cache the pairs by the
+  // hash of their paths.
+  static MORPH_CACHE: RefCell<VecDeque<(u64, u64, Rc<MorphPair>)>> = <_>::default();
+}
+
+/// Morph a path into another path.
+///
+/// Both paths are normalized to cubic segments, the sub path or the segment
+/// that has no counterpart is created by collapsing into a point or splitting
+/// the longest segment. The start points of the closed sub paths are aligned to
+/// minimize the distance the points travel. The normalized paths are cached, so
+/// morphing the same paths in every frame only lerps the segments.
+impl Lerp for Path {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let path = morph_pair(&self.path, &to.path).lerp(factor);
+    let style = match (self.style, to.style) {
+      (PathStyle::Stroke(from), PathStyle::Stroke(to)) => {
+        PathStyle::Stroke(to.with_line_width(from.line_width.lerp(&to.line_width, factor)))
+      }
+      _ if factor < 0.5 => self.style,
+      _ => to.style,
+    };
+    Path { path, style }
+  }
+}
+
+/// Morph the path, the transform and the brush of a svg path.
+impl Lerp for SvgRenderPath {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let brush = match (&self.brush, &to.brush) {
+      (Some(from), Some(to)) => Some(from.lerp(to, factor)),
+      _ if factor < 0.5 => self.brush.clone(),
+      _ => to.brush.clone(),
+    };
+    SvgRenderPath {
+      path: self.path.lerp(&to.path, factor),
+      transform: self.transform.lerp(&to.transform, factor),
+      brush,
+    }
+  }
+}
+
+/// Morph the paths pairwise by their order, the path that has no counterpart
+/// grows from or shrinks to its center.
+impl Lerp for Vec<SvgRenderPath> {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let vanished = |p: &SvgRenderPath| SvgRenderPath {
+      path: Path {
+        path: LyonPath::new(),
+        style: p.path.style,
+      },
+      transform: p.transform,
+      brush: p.brush.clone(),
+    };
+    (0..self.len().max(to.len()))
+      .map(|idx| match (self.get(idx), to.get(idx)) {
+        (Some(from), Some(to)) => from.lerp(to, factor),
+        (Some(from), None) => from.lerp(&vanished(from), factor),
+        (None, Some(to)) => vanished(to).lerp(to, factor),
+        (None, None) => unreachable!(),
+      })
+      .collect()
+  }
+}
+
+impl Lerp for SvgPaths {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    SvgPaths {
+      size: Lerp::lerp(&self.size, &to.size, factor),
+      paths: self.paths.lerp(&to.paths, factor),
+    }
+  }
+}
+
+/// Return the normalized pair of `from` and `to`, from the cache if they were
+/// morphed recently.
+fn morph_pair(from: &LyonPath, to: &LyonPath) -> Rc<MorphPair> {
+  let key = (path_hash(from), path_hash(to));
+  MORPH_CACHE.with(|cache| {
+    let mut cache = cache.borrow_mut();
+    if let Some(idx) = cache.iter().position(|(f, t, _)| (*f, *t) == key) {
+      let hit = cache.remove(idx).unwrap();
+      let pair = hit.2.clone();
+      cache.push_front(hit);
+      return pair;
+    }
+
+    let pair = Rc::new(MorphPair::new(from, to));
+    cache.truncate(MORPH_CACHE_SIZE - 1);
+    cache.push_front((key.0, key.1, pair.clone()));
+    pair
+  })
+}
+
+fn path_hash(path: &LyonPath) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  let mut hash = |tag: u8, points: &[Point<f32>]| {
+    tag.hash(&mut hasher);
+    points.iter().for_each(|p| {
+      p.x.to_bits().hash(&mut hasher);
+      p.y.to_bits().hash(&mut hasher);
+    });
+  };
+  path.iter().for_each(|e| match e {
+    PathEvent::Begin { at } => hash(0, &[at]),
+    PathEvent::Line { to, .. } => hash(1, &[to]),
+    PathEvent::Quadratic { ctrl, to, .. } => hash(2, &[ctrl, to]),
+    PathEvent::Cubic { ctrl1, ctrl2, to, .. } => hash(3, &[ctrl1, ctrl2, to]),
+    PathEvent::End { close, .. } => hash(4 + close as u8, &[]),
+  });
+  hasher.finish()
+}
+
+impl MorphPair {
+  fn new(from: &LyonPath, to: &LyonPath) -> Self {
+    let mut from_subs = sub_paths(from);
+    let mut to_subs = sub_paths(to);
+    match_sub_paths(&mut from_subs, &mut to_subs);
+    from_subs
+      .iter_mut()
+      .zip(to_subs.iter_mut())
+      .for_each(|(from, to)| {
+        let cnt = from.segments.len().max(to.segments.len());
+        split_to(&mut from.segments, cnt);
+        split_to(&mut to.segments, cnt);
+        if from.closed && to.closed {
+          align_start(from, to);
+        }
+      });
+    Self { from: from_subs, to: to_subs }
+  }
+
+  fn lerp(&self, factor: f32) -> LyonPath {
+    let mut builder = LyonPath::builder();
+    self.from.iter().zip(self.to.iter()).for_each(|(from, to)| {
+      let mut segments = from
+        .segments
+        .iter()
+        .zip(to.segments.iter())
+        .map(|(f, t)| lerp_cubic(f, t, factor));
+      if let Some(first) = segments.next() {
+        builder.begin(first.from);
+        builder.cubic_bezier_to(first.ctrl1, first.ctrl2, first.to);
+        segments.for_each(|s| {
+          builder.cubic_bezier_to(s.ctrl1, s.ctrl2, s.to);
+        });
+        builder.end(if factor < 0.5 { from.closed } else { to.closed });
+      }
+    });
+    builder.build()
+  }
+}
+
+fn sub_paths(path: &LyonPath) -> Vec<SubPath> {
+  let mut subs = vec![];
+  let mut segments = vec![];
+  path.iter().for_each(|e| match e {
+    PathEvent::Begin { .. } => segments.clear(),
+    PathEvent::Line { from, to } => segments.push(line(from, to)),
+    PathEvent::Quadratic { from, ctrl, to } => {
+      segments.push(QuadraticBezierSegment { from, ctrl, to }.to_cubic())
+    }
+    PathEvent::Cubic { from, ctrl1, ctrl2, to } => segments.push(Cubic { from, ctrl1, ctrl2, to }),
+    PathEvent::End { last, first, close } => {
+      if close && last != first {
+        segments.push(line(last, first));
+      }
+      if segments.is_empty() {
+        segments.push(line(first, first));
+      }
+      subs.push(SubPath {
+        segments: std::mem::take(&mut segments),
+        closed: close,
+      });
+    }
+  });
+  subs
+}
+
+/// Append the sub paths that have no counterpart, they grow from or shrink to
+/// the center of their counterpart.
+fn match_sub_paths(from: &mut Vec<SubPath>, to: &mut Vec<SubPath>) {
+  while from.len() < to.len() {
+    let collapsed = collapse(&to[from.len()]);
+    from.push(collapsed);
+  }
+  while to.len() < from.len() {
+    let collapsed = collapse(&from[to.len()]);
+    to.push(collapsed);
+  }
+}
+
+fn collapse(sub: &SubPath) -> SubPath {
+  let sum = sub
+    .segments
+    .iter()
+    .fold(Point::zero(), |sum, s| sum + s.from.to_vector());
+  let center = sum / sub.segments.len() as f32;
+  SubPath {
+    segments: vec![line(center, center)],
+    closed: sub.closed,
+  }
+}
+
+/// Split the longest segment in half until there are `cnt` segments.
+fn split_to(segments: &mut Vec<Cubic>, cnt: usize) {
+  while segments.len() < cnt {
+    let hull_len = |s: &Cubic| {
+      (s.ctrl1 - s.from).length() + (s.ctrl2 - s.ctrl1).length() + (s.to - s.ctrl2).length()
+    };
+    let longest = segments
+      .iter()
+      .enumerate()
+      .max_by(|(_, a), (_, b)| hull_len(a).total_cmp(&hull_len(b)))
+      .map_or(0, |(idx, _)| idx);
+    let (first, second) = segments[longest].split(0.5);
+    segments[longest] = first;
+    segments.insert(longest + 1, second);
+  }
+}
+
+/// Rotate the segments of the closed sub path `to`, so the points of `from`
+/// travel the shortest distance.
+fn align_start(from: &SubPath, to: &mut SubPath) {
+  let cnt = to.segments.len();
+  let distance = |offset: usize| -> f32 {
+    from
+      .segments
+      .iter()
+      .enumerate()
+      .map(|(idx, s)| (s.from - to.segments[(idx + offset) % cnt].from).square_length())
+      .sum()
+  };
+  let best = (0..cnt).min_by(|a, b| distance(*a).total_cmp(&distance(*b)));
+  if let Some(offset) = best {
+    to.segments.rotate_left(offset);
+  }
+}
+
+fn line(from: Point<f32>, to: Point<f32>) -> Cubic {
+  Cubic {
+    from,
+    ctrl1: from.lerp(to, 1. / 3.),
+    ctrl2: from.lerp(to, 2. / 3.),
+    to,
+  }
+}
+
+fn lerp_cubic(from: &Cubic, to: &Cubic, factor: f32) -> Cubic {
+  Cubic {
+    from: from.from.lerp(to.from, factor),
+    ctrl1: from.ctrl1.lerp(to.ctrl1, factor),
+    ctrl2: from.ctrl2.lerp(to.ctrl2, factor),
+    to: from.to.lerp(to.to, factor),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::prelude::{Point, Rect, Size};
+
+  fn square(points: [Point; 4]) -> Path {
+    let mut builder = Path::builder();
+    builder
+      .begin_path(points[0])
+      .line_to(points[1])
+      .line_to(points[2])
+      .line_to(points[3])
+      .end_path(true);
+    builder.fill()
+  }
+
+  fn rect_eq(a: Rect, b: Rect) -> bool {
+    let eq = |a: f32, b: f32| (a - b).abs() < 1e-3;
+    eq(a.min_x(), b.min_x())
+      && eq(a.min_y(), b.min_y())
+      && eq(a.width(), b.width())
+      && eq(a.height(), b.height())
+  }
+
+  fn sub_path_cnt(path: &Path) -> usize {
+    path
+      .path
+      .iter()
+      .filter(|e| matches!(e, PathEvent::Begin { .. }))
+      .count()
+  }
+
+  #[test]
+  fn morph_different_segments() {
+    let rect = Rect::new(Point::zero(), Size::new(10., 10.));
+    let from = Path::rect(&rect, PathStyle::Fill);
+    let to = Path::circle(Point::new(50., 50.), 10., PathStyle::Fill);
+
+    assert!(rect_eq(from.lerp(&to, 0.).box_rect(), rect));
+    assert!(rect_eq(from.lerp(&to, 1.).box_rect(), to.box_rect()));
+    assert_eq!(sub_path_cnt(&from.lerp(&to, 0.5)), 1);
+  }
+
+  #[test]
+  fn morph_different_sub_paths() {
+    let from = Path::circle(Point::new(10., 10.), 10., PathStyle::Fill);
+    let mut builder = Path::builder();
+    builder
+      .circle(Point::new(10., 10.), 10.)
+      .circle(Point::new(50., 10.), 10.);
+    let to = builder.fill();
+
+    assert_eq!(sub_path_cnt(&from.lerp(&to, 0.5)), 2);
+    // the new circle grows from its center.
+    let start = Rect::new(Point::zero(), Size::new(50., 20.));
+    assert!(rect_eq(from.lerp(&to, 0.).box_rect(), start));
+    assert!(rect_eq(from.lerp(&to, 1.).box_rect(), to.box_rect()));
+  }
+
+  #[test]
+  fn cache_morph_pair() {
+    let from = Path::rect(
+      &Rect::new(Point::zero(), Size::new(10., 10.)),
+      PathStyle::Fill,
+    );
+    let to = Path::circle(Point::new(50., 50.), 10., PathStyle::Fill);
+    let pair = morph_pair(&from.path, &to.path);
+    assert!(Rc::ptr_eq(&pair, &morph_pair(&from.path, &to.path)));
+    assert!(!Rc::ptr_eq(&pair, &morph_pair(&to.path, &from.path)));
+  }
+
+  #[test]
+  fn align_start_points() {
+    let p = [
+      Point::new(0., 0.),
+      Point::new(10., 0.),
+      Point::new(10., 10.),
+      Point::new(0., 10.),
+    ];
+    let from = square(p);
+    // the same square starts from the opposite corner.
+    let to = square([p[2], p[3], p[0], p[1]]);
+
+    let rect = Rect::new(Point::zero(), Size::new(10., 10.));
+    assert!(rect_eq(from.lerp(&to, 0.5).box_rect(), rect));
+  }
+}
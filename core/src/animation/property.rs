@@ -19,6 +19,17 @@ pub trait Property {
 
 pub trait AnimateProperty: Property {
   fn calc_lerp_value(&self, from: &Self::Value, to: &Self::Value, rate: f32) -> Self::Value;
+
+  /// Calc the rate of the `value` between `from` and `to`, the inverse of
+  /// `calc_lerp_value`. Return `None` if the rate can't be measured.
+  fn calc_lerp_rate(
+    &self,
+    _from: &Self::Value,
+    _to: &Self::Value,
+    _value: &Self::Value,
+  ) -> Option<f32> {
+    None
+  }
 }
 
 pub struct Prop<T, G, S> {
@@ -86,6 +97,16 @@ where
   fn calc_lerp_value(&self, from: &Self::Value, to: &Self::Value, rate: f32) -> Self::Value {
     from.lerp(to, rate)
   }
+
+  #[inline]
+  fn calc_lerp_rate(
+    &self,
+    from: &Self::Value,
+    to: &Self::Value,
+    value: &Self::Value,
+  ) -> Option<f32> {
+    value.inverse_lerp(from, to)
+  }
 }
 
 impl<T, G, S, V> Prop<T, G, S>
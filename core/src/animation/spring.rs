@@ -0,0 +1,150 @@
+use crate::prelude::*;
+use std::time::Duration;
+
+/// A physics-based [`Roc`] that animates as a damped spring pulls the value
+/// from the start to the end.
+///
+/// The spring has no fixed duration, the animation finished when the spring is
+/// at rest. And it tracks its velocity, so a running animation retargeted to a
+/// new value continues at its current speed instead of restart from rest. The
+/// velocity of the value in the property units is kept if the value can be
+/// measured by [`Lerp::inverse_lerp`].
+#[derive(Declare, Clone, Debug, PartialEq)]
+pub struct Spring {
+  /// The stiffness of the spring, a stiffer spring moves faster.
+  #[declare(default = 100.)]
+  pub stiffness: f32,
+  /// The damping of the spring, the spring oscillates if it's less than the
+  /// critical damping `2 * sqrt(stiffness * mass)`.
+  #[declare(default = 10.)]
+  pub damping: f32,
+  /// The mass of the object attached to the spring.
+  #[declare(default = 1.)]
+  pub mass: f32,
+  /// The spring is at rest if both of its distance to the end and its velocity
+  /// are less than the threshold, in the rate of the distance.
+  #[declare(default = 0.001)]
+  pub rest_threshold: f32,
+}
+
+impl Spring {
+  /// Return the displacement to the end and the velocity of the spring after
+  /// `t` seconds, it starts at the displacement `-1.` with `v0`.
+  fn state_at(&self, t: f32, v0: f32) -> (f32, f32) {
+    let w0 = (self.stiffness / self.mass).sqrt();
+    let zeta = self.damping / (2. * (self.stiffness * self.mass).sqrt());
+    let x0 = -1.;
+
+    if zeta < 1. {
+      // under damped
+      let wd = w0 * (1. - zeta * zeta).sqrt();
+      let a = x0;
+      let b = (v0 + zeta * w0 * a) / wd;
+      let decay = (-zeta * w0 * t).exp();
+      let (sin, cos) = (wd * t).sin_cos();
+      let x = decay * (a * cos + b * sin);
+      let v = decay * (-zeta * w0 * (a * cos + b * sin) + wd * (b * cos - a * sin));
+      (x, v)
+    } else if (zeta - 1.).abs() < f32::EPSILON {
+      // critically damped
+      let a = x0;
+      let b = v0 + w0 * a;
+      let decay = (-w0 * t).exp();
+      (decay * (a + b * t), decay * (b - w0 * (a + b * t)))
+    } else {
+      // over damped
+      let delta = w0 * (zeta * zeta - 1.).sqrt();
+      let r1 = -zeta * w0 + delta;
+      let r2 = -zeta * w0 - delta;
+      let c2 = (v0 - r1 * x0) / (r2 - r1);
+      let c1 = x0 - c2;
+      let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+      (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+    }
+  }
+
+  fn is_rest(&self, x: f32, v: f32) -> bool {
+    x.abs() < self.rest_threshold && v.abs() < self.rest_threshold
+  }
+}
+
+impl Roc for Spring {
+  #[inline]
+  fn rate_of_change(&self, dur: Duration) -> AnimateProgress {
+    self.rate_of_change_with_velocity(dur, 0.)
+  }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    let (x, v) = self.state_at(dur.as_secs_f32(), velocity);
+    if self.is_rest(x, v) {
+      AnimateProgress::Finish
+    } else {
+      AnimateProgress::Between(1. + x)
+    }
+  }
+
+  fn velocity(&self, dur: Duration, velocity: f32) -> Option<f32> {
+    let (x, v) = self.state_at(dur.as_secs_f32(), velocity);
+    Some(if self.is_rest(x, v) { 0. } else { v })
+  }
+
+  /// The time the spring start from rest to at rest, sampled every 10ms and
+  /// `None` if it's not at rest in a minute.
+  fn total_duration(&self) -> Option<Duration> {
+    (1..=6000)
+      .map(|i| Duration::from_millis(i * 10))
+      .find(|dur| self.rate_of_change(*dur).is_finish())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn with_damping(damping: f32) -> Spring {
+    Spring {
+      stiffness: 100.,
+      damping,
+      mass: 1.,
+      rest_threshold: 0.001,
+    }
+  }
+
+  #[test]
+  fn settle() {
+    // under, critically and over damped.
+    for damping in [5., 20., 40.] {
+      let spring = with_damping(damping);
+      assert!(spring.rate_of_change(Duration::ZERO).value().abs() < f32::EPSILON);
+      assert!(spring.rate_of_change(Duration::from_secs(5)).is_finish());
+    }
+  }
+
+  #[test]
+  fn overshoot() {
+    let spring = with_damping(5.);
+    let max = (1..100)
+      .map(|i| spring.rate_of_change(Duration::from_millis(i * 10)).value())
+      .fold(0., f32::max);
+    assert!(max > 1.);
+
+    let spring = with_damping(40.);
+    let max = (1..100)
+      .map(|i| spring.rate_of_change(Duration::from_millis(i * 10)).value())
+      .fold(0., f32::max);
+    assert!(max <= 1.);
+  }
+
+  #[test]
+  fn keep_velocity() {
+    for damping in [5., 20., 40.] {
+      let spring = with_damping(damping);
+      let v = spring.velocity(Duration::ZERO, 3.).unwrap();
+      assert!((v - 3.).abs() < 1e-4);
+
+      let dt = Duration::from_millis(1);
+      let p = spring.rate_of_change_with_velocity(dt, 3.).value();
+      assert!((p - 0.003).abs() < 1e-3);
+    }
+  }
+}
@@ -21,6 +21,26 @@ pub struct Transition<E> {
 pub trait Roc {
   /// Calc the rate of change of the duration from animation start.
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress;
+
+  /// Calc the rate of change of the duration from animation start, and the
+  /// animation start with `velocity`, the change of the rate per second. The
+  /// `Roc` not driven by physics ignores the velocity.
+  #[inline]
+  fn rate_of_change_with_velocity(&self, dur: Duration, _velocity: f32) -> AnimateProgress {
+    self.rate_of_change(dur)
+  }
+
+  /// The velocity of the rate at the duration from animation start, the
+  /// animation start with `velocity`. Return `None` if the `Roc` not tracks
+  /// the velocity, the running animation will not carry its velocity when it
+  /// is retargeted.
+  #[inline]
+  fn velocity(&self, _dur: Duration, _velocity: f32) -> Option<f32> { None }
+
+  /// The duration from the animation start to finish, `None` if it never
+  /// finishes or unknown.
+  #[inline]
+  fn total_duration(&self) -> Option<Duration> { None }
 }
 
 impl<E: Easing> Roc for Transition<E> {
@@ -40,7 +60,7 @@ impl<E: Easing> Roc for Transition<E> {
     let repeat = self.repeat.unwrap_or(1.);
 
     let rounds = run_dur.as_secs_f32() / self.duration.as_secs_f32();
-    if rounds > repeat {
+    if rounds >= repeat {
       return AnimateProgress::Finish;
     }
 
@@ -48,6 +68,16 @@ impl<E: Easing> Roc for Transition<E> {
     let p = self.easing.easing(time_rate);
     AnimateProgress::Between(p)
   }
+
+  fn total_duration(&self) -> Option<Duration> {
+    let repeat = self.repeat.unwrap_or(1.);
+    let run_dur = if repeat.fract() == 0. && repeat <= u32::MAX as f32 {
+      self.duration.checked_mul(repeat as u32)?
+    } else {
+      Duration::try_from_secs_f64(self.duration.as_secs_f64() * repeat as f64).ok()?
+    };
+    self.delay.unwrap_or_default().checked_add(run_dur)
+  }
 }
 
 impl<T: Roc> Roc for Stateful<T> {
@@ -55,14 +85,77 @@ impl<T: Roc> Roc for Stateful<T> {
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress {
     self.state_ref().rate_of_change(dur)
   }
+
+  #[inline]
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    self.state_ref().rate_of_change_with_velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn velocity(&self, dur: Duration, velocity: f32) -> Option<f32> {
+    self.state_ref().velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn total_duration(&self) -> Option<Duration> { self.state_ref().total_duration() }
 }
 
 impl Roc for Box<dyn Roc> {
   #[inline]
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { self.deref().rate_of_change(dur) }
+
+  #[inline]
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    self.deref().rate_of_change_with_velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn velocity(&self, dur: Duration, velocity: f32) -> Option<f32> {
+    self.deref().velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn total_duration(&self) -> Option<Duration> { self.deref().total_duration() }
 }
 
 impl<T: Roc> Roc for Rc<T> {
   #[inline]
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { self.deref().rate_of_change(dur) }
+
+  #[inline]
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    self.deref().rate_of_change_with_velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn velocity(&self, dur: Duration, velocity: f32) -> Option<f32> {
+    self.deref().velocity(dur, velocity)
+  }
+
+  #[inline]
+  fn total_duration(&self) -> Option<Duration> { self.deref().total_duration() }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::animation::easing;
+
+  #[test]
+  fn finish_at_the_end_of_last_round() {
+    let transition = Transition {
+      delay: None,
+      duration: Duration::from_millis(100),
+      easing: easing::LINEAR,
+      repeat: Some(2.),
+    };
+    let rate = |ms| transition.rate_of_change(Duration::from_millis(ms));
+
+    assert_eq!(rate(50).value(), 0.5);
+    // The end of a round but not the last one starts the next round.
+    assert_eq!(rate(100).value(), 0.);
+    assert_eq!(rate(150).value(), 0.5);
+    // The end of the last round is finished, not the start of a new round.
+    assert!(matches!(rate(200), AnimateProgress::Finish));
+  }
 }
@@ -19,6 +19,12 @@ mod visibility;
 pub use visibility::*;
 mod ignore_pointer;
 pub use ignore_pointer::*;
+mod delay_drop;
+pub use delay_drop::*;
+mod layout_transition;
+pub use layout_transition::*;
+mod draggable;
+pub use draggable::*;
 mod void;
 pub use void::Void;
 mod unconstrained_box;
@@ -49,3 +55,5 @@ pub mod focus_node;
 pub use focus_node::*;
 pub mod focus_scope;
 pub use focus_scope::*;
+pub mod shortcut_scope;
+pub use shortcut_scope::*;
@@ -0,0 +1,72 @@
+use crate::{impl_query_self_only, prelude::*};
+
+/// A widget that delays the drop of its subtree until `delay_drop_until` is
+/// `true`.
+///
+/// When the widget is removed by a `DynWidget`, it's disposed as usual, but if
+/// `delay_drop_until` is `false` after the disposed events, it's detached from
+/// the tree and not dropped. The detached widget keeps its layout and is still
+/// painted at where its parent is, but it's not hit-testable anymore. Once
+/// `delay_drop_until` is set to `true`, the widget is dropped.
+///
+/// It's useful to run an exit animation for a widget.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let w = widget! {
+///   states { show: Stateful::new(true) }
+///   DynWidget {
+///     // the tip fades out when `show` is set to `false`.
+///     dyns: show.then(|| widget! {
+///       init ctx => {
+///         let ease_out = transitions::EASE_OUT.of(ctx);
+///       }
+///       Void {
+///         id: tip,
+///         opacity: 1.,
+///         delay_drop_until: !fade_out.is_running(),
+///         on_disposed: move |_| {
+///           tip.opacity = 0.;
+///           fade_out.run();
+///         },
+///       }
+///       Animate {
+///         id: fade_out,
+///         transition: ease_out,
+///         prop: prop!(tip.opacity),
+///         from: 1.,
+///       }
+///     })
+///   }
+/// };
+/// ```
+///
+/// Only the root of the removed subtree can delay its drop, a `DelayDrop`
+/// inside the removed subtree is dropped with its ancestors.
+#[derive(Declare, SingleChild, Default, Clone)]
+pub struct DelayDrop {
+  #[declare(builtin)]
+  pub delay_drop_until: bool,
+}
+
+impl Render for DelayDrop {
+  #[inline]
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    ctx.assert_perform_single_child_layout(clamp)
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+
+  fn can_overflow(&self) -> bool { true }
+
+  fn hit_test(&self, _: &HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: true }
+  }
+}
+
+impl Query for DelayDrop {
+  impl_query_self_only!();
+}
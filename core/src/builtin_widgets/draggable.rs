@@ -0,0 +1,176 @@
+use crate::{impl_query_self_only, prelude::*};
+use std::{any::Any, rc::Rc};
+
+/// A widget that can be dragged by the pointer and dropped on a drop target, a
+/// widget with the `on_drag_enter`, `on_drag_over`, `on_drag_leave` or
+/// `on_drop` listeners.
+///
+/// The drag starts when the pointer pressed on it moves over a slop, and the
+/// widget is painted translucent over the tree following the pointer as the
+/// feedback, or the `drag_feedback` widget if it's provided. Every drag
+/// carries a new payload created from the `drag_data`, the drop target can
+/// read it by [`DragEvent::data`] and take it in the drop event by
+/// [`DragEvent::take_data`].
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let w = widget! {
+///   states { cards: Stateful::new(vec![]) }
+///   Margin {
+///     margin: EdgeInsets::all(10.),
+///     on_drag_over: move |e| if e.data::<&str>().is_some() {
+///       e.accept();
+///     },
+///     on_drop: move |e| if let Some(card) = e.take_data::<&str>() {
+///       cards.push(card);
+///     },
+///     Void {
+///       background: Color::RED,
+///       drag_data: "red card",
+///       drag_feedback: DragFeedback::new(|| widget! {
+///         Void { background: Color::BLUE }
+///       }),
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Draggable {
+  #[declare(builtin, convert=into)]
+  pub drag_data: DragData,
+  #[declare(builtin, default, convert=strip_option)]
+  pub drag_feedback: Option<DragFeedback>,
+}
+
+/// Create the payload of a drag.
+pub struct DragData(Rc<dyn Fn() -> Box<dyn Any>>);
+
+impl DragData {
+  pub fn new(f: impl Fn() -> Box<dyn Any> + 'static) -> Self { Self(Rc::new(f)) }
+
+  pub fn create(&self) -> Box<dyn Any> { (self.0)() }
+}
+
+impl<T: Clone + 'static> From<T> for DragData {
+  fn from(value: T) -> Self { DragData::new(move || Box::new(value.clone())) }
+}
+
+/// Create the widget painted following the pointer during a drag, instead of
+/// the dragged widget.
+#[derive(Clone)]
+pub struct DragFeedback(Rc<dyn Fn() -> Widget>);
+
+impl DragFeedback {
+  pub fn new(f: impl Fn() -> Widget + 'static) -> Self { Self(Rc::new(f)) }
+
+  pub fn create(&self) -> Widget { (self.0)() }
+}
+
+impl ComposeChild for Draggable {
+  type Child = Widget;
+  fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
+    let has_feedback = match &this {
+      State::Stateless(this) => this.drag_feedback.is_some(),
+      State::Stateful(this) => this.state_ref().drag_feedback.is_some(),
+    };
+    if !has_feedback {
+      return widget! {
+        states { this: this.into_readonly() }
+        DynWidget {
+          dyns: child,
+          on_pan_start: move |e| start_drag(&this.drag_data, e),
+          on_pan_end: move |e| e.context().wnd_ctx.release_drag(),
+        }
+      };
+    }
+
+    widget! {
+      states {
+        this: this.into_readonly(),
+        dragging: Stateful::new(false),
+      }
+      FeedbackHost {
+        on_pan_start: move |e| {
+          start_drag(&this.drag_data, e);
+          *dragging = true;
+        },
+        on_pan_end: move |e| {
+          e.context().wnd_ctx.release_drag();
+          *dragging = false;
+        },
+        DynWidget { dyns: child }
+        DynWidget {
+          dyns: {
+            let feedback = this.drag_feedback.clone().filter(|_| *dragging);
+            feedback.map(|feedback| widget! {
+              FeedbackLayer {
+                on_mounted: move |ctx| ctx.wnd_ctx().set_drag_feedback(ctx.widget_id()),
+                DynWidget { dyns: feedback.create() }
+              }
+            })
+          }
+        }
+      }
+    }
+  }
+}
+
+fn start_drag(data: &DragData, e: &mut PanEvent) {
+  // The position of the pointer when it's pressed.
+  let grab_pos = e.position() - e.offset;
+  let pos = e.pointer_pos;
+  let pointer = e.pointer_id;
+  let data = data.create();
+  let ctx = e.context();
+  ctx.wnd_ctx.start_drag(ctx.id, pointer, data, grab_pos, pos);
+}
+
+/// Host the dragged widget and its feedback, the feedback is laid out but not
+/// sized in the host.
+#[derive(Declare, MultiChild)]
+struct FeedbackHost;
+
+impl Render for FeedbackHost {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let mut size = ZERO_SIZE;
+    let mut layouter = ctx.first_child_layouter();
+    if let Some(mut l) = layouter {
+      size = l.perform_widget_layout(clamp);
+      layouter = l.into_next_sibling();
+    }
+    while let Some(mut l) = layouter {
+      l.perform_widget_layout(BoxClamp { min: ZERO_SIZE, max: INFINITY_SIZE });
+      layouter = l.into_next_sibling();
+    }
+    size
+  }
+
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+/// The layer of the drag feedback, it's not painted and hit in the tree but
+/// painted over the tree following the pointer.
+#[derive(Declare, SingleChild)]
+struct FeedbackLayer;
+
+impl Render for FeedbackLayer {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    ctx.assert_perform_single_child_layout(clamp)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) { ctx.painter().apply_alpha(0.); }
+
+  fn hit_test(&self, _: &HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: false }
+  }
+}
+
+impl Query for FeedbackHost {
+  impl_query_self_only!();
+}
+
+impl Query for FeedbackLayer {
+  impl_query_self_only!();
+}
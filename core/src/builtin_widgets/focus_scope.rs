@@ -13,6 +13,32 @@ pub struct FocusScope {
   /// Defalut value is false, then the FocusScope widget can't be focused
   #[declare(default)]
   pub can_focus: bool,
+
+  /// If true, the arrow keys move the focus to the nearest focusable widget in
+  /// the direction within the scope, by the layout rects of the widgets.
+  /// Default value is false, the focus only moves in the tab order.
+  #[declare(default)]
+  pub directional: bool,
+
+  /// If true, the directional navigation wraps to the farthest widget in the
+  /// same row or column when there is no widget in the direction.
+  /// Default value is false.
+  #[declare(default)]
+  pub wrap: bool,
+
+  /// If true, the focus is confined to the scope after it's mounted, the Tab
+  /// cycles in the scope and the focus requests to the widgets out of it are
+  /// ignored, use it for the modal dialogs and menus. The last mounted trap
+  /// scope takes effect if there are several.
+  /// Default value is false.
+  #[declare(default)]
+  pub trap: bool,
+
+  /// If true, the focus is restored to the widget focused before the scope is
+  /// mounted when the scope is disposed with the focus in it.
+  /// Default value is false.
+  #[declare(default)]
+  pub restore_focus: bool,
 }
 
 impl ComposeChild for FocusScope {
@@ -164,4 +190,150 @@ mod tests {
       assert_eq!(dispatcher.focusing(), Some(id1));
     }
   }
+
+  #[test]
+  fn directional_navigation() {
+    let size = Size::new(10., 10.);
+    let grid = |directional, wrap| {
+      widget! {
+        FocusScope {
+          directional, wrap,
+          MockMulti {
+            MockBox { size, auto_focus: true }
+            MockBox { size, tab_index: 0 }
+            MockBox { size, tab_index: 0, left_anchor: -20., top_anchor: 20. }
+            MockBox { size, tab_index: 0, left_anchor: -10., top_anchor: 20. }
+          }
+        }
+      }
+    };
+
+    let mut wnd = Window::default_mock(grid(true, false).into_widget(), None);
+    wnd.draw_frame();
+    let Window { dispatcher, widget_tree, .. } = &mut wnd;
+    let arena = &widget_tree.arena;
+    let boxes = widget_tree
+      .root()
+      .first_child(arena)
+      .unwrap()
+      .children(arena)
+      .collect::<Vec<_>>();
+    let focus_box = |w: WidgetId| {
+      boxes
+        .iter()
+        .position(|b| w.ancestors(arena).any(|p| p == *b))
+    };
+    let mut step = |dir| {
+      dispatcher.directional_focus_widget(dir, widget_tree);
+      dispatcher.focusing().and_then(focus_box)
+    };
+
+    assert_eq!(step(FocusDirection::Right), Some(1));
+    assert_eq!(step(FocusDirection::Down), Some(3));
+    assert_eq!(step(FocusDirection::Left), Some(2));
+    assert_eq!(step(FocusDirection::Up), Some(0));
+    // Keep the focus if no widget in the direction.
+    assert_eq!(step(FocusDirection::Left), Some(0));
+
+    let mut wnd = Window::default_mock(grid(true, true).into_widget(), None);
+    wnd.draw_frame();
+    let Window { dispatcher, widget_tree, .. } = &mut wnd;
+    let first = dispatcher.focusing();
+    dispatcher.directional_focus_widget(FocusDirection::Left, widget_tree);
+    assert_ne!(dispatcher.focusing(), first);
+    dispatcher.directional_focus_widget(FocusDirection::Right, widget_tree);
+    assert_eq!(dispatcher.focusing(), first);
+
+    let mut wnd = Window::default_mock(grid(false, false).into_widget(), None);
+    wnd.draw_frame();
+    let Window { dispatcher, widget_tree, .. } = &mut wnd;
+    let first = dispatcher.focusing();
+    dispatcher.directional_focus_widget(FocusDirection::Right, widget_tree);
+    assert_eq!(dispatcher.focusing(), first);
+  }
+
+  #[test]
+  fn trap_and_restore() {
+    use std::{cell::RefCell, rc::Rc};
+
+    type Ids = Rc<RefCell<Vec<(&'static str, WidgetId)>>>;
+    fn focus_box(ids: &Ids, name: &'static str) -> Widget {
+      let ids = ids.clone();
+      let size = Size::new(10., 10.);
+      widget! {
+        MockBox {
+          size,
+          tab_index: 0,
+          on_mounted: move |ctx| ids.borrow_mut().push((name, ctx.widget_id())),
+        }
+      }
+    }
+
+    let open = Stateful::new(false);
+    let ids = Ids::default();
+    let c_ids = ids.clone();
+    let dialog_ids = ids.clone();
+    let w = widget! {
+      states { open: open.clone() }
+      MockMulti {
+        DynWidget { dyns: focus_box(&c_ids, "button") }
+        DynWidget {
+          dyns: open.then(|| {
+            let ids = dialog_ids.clone();
+            widget! {
+              FocusScope {
+                trap: true,
+                restore_focus: true,
+                MockMulti {
+                  DynWidget { dyns: focus_box(&ids, "ok") }
+                  DynWidget { dyns: focus_box(&ids, "cancel") }
+                }
+              }
+            }
+          })
+        }
+        DynWidget { dyns: focus_box(&c_ids, "other") }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+    let focused = |wnd: &Window| {
+      let focusing = wnd.dispatcher.focusing();
+      ids
+        .borrow()
+        .iter()
+        .find(|(_, id)| Some(*id) == focusing)
+        .map(|(name, _)| *name)
+    };
+    let tab = |wnd: &mut Window| {
+      let Window { dispatcher, widget_tree, .. } = wnd;
+      dispatcher.next_focus_widget(widget_tree);
+      wnd.draw_frame();
+    };
+
+    tab(&mut wnd);
+    assert_eq!(focused(&wnd), Some("button"));
+
+    *open.state_ref() = true;
+    wnd.draw_frame();
+    tab(&mut wnd);
+    assert_eq!(focused(&wnd), Some("ok"));
+    tab(&mut wnd);
+    assert_eq!(focused(&wnd), Some("cancel"));
+    tab(&mut wnd);
+    assert_eq!(focused(&wnd), Some("ok"));
+
+    // The focus requests out of the trap are ignored.
+    let other = ids.borrow().iter().find(|(n, _)| *n == "other").unwrap().1;
+    let controller = wnd.widget_tree.wnd_ctx.focus_controller();
+    controller.request_focus(other);
+    wnd.draw_frame();
+    assert_eq!(focused(&wnd), Some("ok"));
+
+    *open.state_ref() = false;
+    wnd.draw_frame();
+    assert_eq!(focused(&wnd), Some("button"));
+    tab(&mut wnd);
+    assert_eq!(focused(&wnd), Some("other"));
+  }
 }
@@ -3,10 +3,16 @@ use crate::prelude::*;
 pub struct HasFocus {
   #[declare(skip, default)]
   focused: bool,
+  #[declare(skip, default)]
+  by_keyboard: bool,
 }
 
 impl HasFocus {
   pub fn has_focus(&self) -> bool { self.focused }
+
+  /// Return if the widget has focus and the focus is moved by the keyboard,
+  /// see [`FocusController::focus_visible`].
+  pub fn focus_visible(&self) -> bool { self.focused && self.by_keyboard }
 }
 
 impl ComposeChild for HasFocus {
@@ -16,7 +22,10 @@ impl ComposeChild for HasFocus {
       states {this: this.into_writable()}
       DynWidget {
         dyns: child,
-        on_focus_in: move|_| this.focused = true,
+        on_focus_in: move |e| {
+          this.focused = true;
+          this.by_keyboard = e.context().focus_controller().focus_visible();
+        },
         on_focus_out: move |_| this.focused = false,
       }
     }
@@ -0,0 +1,207 @@
+use crate::{animation::AnimateTrack, impl_query_self_only, prelude::*};
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+/// A widget that animates its painted position and size when its layout rect
+/// changed.
+///
+/// After every layout, the rect that its parent placed it at is compared with
+/// the rect of the last layout, if it changed, the widget is painted from the
+/// old rect to the new rect with the `auto_layout_transition`. Only the
+/// painting is animated, the layout and the hit test use the new rect
+/// immediately.
+///
+/// The widget must live across the layouts to know its old rect, so use it
+/// with a [`Key`](KeyWidget) when its parent regenerates the children, like
+/// reorder a list.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let w = widget! {
+///   states { left: Stateful::new(0.) }
+///   init ctx => {
+///     let ease_in_out = transitions::EASE_IN_OUT.of(ctx);
+///   }
+///   Margin {
+///     margin: EdgeInsets::only_left(*left),
+///     on_tap: move |_| *left += 100.,
+///     // the box moves smoothly when the margin of its parent changed.
+///     Void {
+///       background: Color::RED,
+///       auto_layout_transition: ease_in_out,
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare, SingleChild)]
+pub struct LayoutTransition {
+  #[declare(builtin)]
+  pub auto_layout_transition: Rc<Box<dyn Roc>>,
+  #[declare(skip, default)]
+  flight: RefCell<LayoutFlight>,
+}
+
+/// The rect the widget flies from and when it starts, and the rect it flies
+/// to.
+#[derive(Default)]
+struct LayoutFlight {
+  to: Option<Rect>,
+  from: Option<(Rect, Instant)>,
+  track: Option<AnimateTrack>,
+}
+
+impl LayoutTransition {
+  fn rate(&self, start_at: Instant, now: Instant) -> AnimateProgress {
+    let dur = now.saturating_duration_since(start_at);
+    self.auto_layout_transition.rate_of_change(dur)
+  }
+
+  /// Fly to `rect` if it's not the rect of the last layout, and land when the
+  /// flight finished.
+  fn layout_changed(&self, rect: Rect, wnd_ctx: &WindowCtx) {
+    let now = wnd_ctx.now();
+    let mut flight = self.flight.borrow_mut();
+    let flight = &mut *flight;
+    if flight.to != Some(rect) {
+      if let Some(last) = flight.to {
+        // Fly from where it's painted now if it's still in flight.
+        let from = match flight.from {
+          Some((from, start_at)) => Lerp::lerp(&from, &last, self.rate(start_at, now).value()),
+          None => last,
+        };
+        flight.from = Some((from, now));
+        flight
+          .track
+          .get_or_insert_with(|| wnd_ctx.animate_track())
+          .set_actived(true);
+      }
+      flight.to = Some(rect);
+    }
+
+    if matches!(flight.from, Some((_, start_at)) if self.rate(start_at, now).is_finish()) {
+      flight.from = None;
+      flight.track = None;
+    }
+  }
+}
+
+/// Compare the rect of every layout transition with its last rect after the
+/// layout, the changed ones start to fly.
+pub(crate) fn end_layout_transitions(arena: &TreeArena, store: &LayoutStore, wnd_ctx: &WindowCtx) {
+  wnd_ctx.layout_transitions.borrow_mut().retain(|id| {
+    if id.is_dropped(arena) {
+      return false;
+    }
+    let rect = store
+      .layout_info(*id)
+      .and_then(|info| info.size.map(|size| Rect::new(info.pos, size)));
+    if let Some(rect) = rect {
+      id.assert_get(arena)
+        .query_on_first_type(QueryOrder::OutsideFirst, |t: &LayoutTransition| {
+          t.layout_changed(rect, wnd_ctx)
+        });
+    }
+    true
+  });
+}
+
+impl Render for LayoutTransition {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let mut transitions = ctx.wnd_ctx.layout_transitions.borrow_mut();
+    if !transitions.contains(&ctx.id) {
+      transitions.push(ctx.id);
+    }
+    drop(transitions);
+    ctx.assert_perform_single_child_layout(clamp)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let flight = self.flight.borrow();
+    let (Some((from, start_at)), Some(rect)) = (flight.from, flight.to) else {
+      return;
+    };
+    let progress = self.rate(start_at, ctx.wnd_ctx.now());
+    if !progress.is_finish() {
+      let painted = Lerp::lerp(&from, &rect, progress.value());
+      let scale = |painted: f32, to: f32| if to > 0. { painted / to } else { 1. };
+      let painter = ctx.painter();
+      painter.translate(
+        painted.min_x() - rect.min_x(),
+        painted.min_y() - rect.min_y(),
+      );
+      painter.scale(
+        scale(painted.width(), rect.width()),
+        scale(painted.height(), rect.height()),
+      );
+    }
+  }
+
+  fn can_overflow(&self) -> bool { true }
+
+  fn hit_test(&self, _: &HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: true }
+  }
+}
+
+impl Query for LayoutTransition {
+  impl_query_self_only!();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::time::Duration;
+
+  #[test]
+  fn fly_to_new_layout_rect() {
+    let width = Stateful::new(10.);
+    let w = widget! {
+      states { width: width.clone() }
+      init ctx => {
+        let linear = transitions::LINEAR.of(ctx);
+      }
+      MockMulti {
+        MockBox { size: Size::new(*width, 10.) }
+        MockBox {
+          size: Size::new(10., 10.),
+          auto_layout_transition: linear,
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+
+    let flight_from = |wnd: &Window| {
+      let arena = &wnd.widget_tree.arena;
+      let mut from = None;
+      wnd.widget_tree.root().descendants(arena).for_each(|id| {
+        id.assert_get(arena).query_on_first_type(
+          QueryOrder::OutsideFirst,
+          |t: &LayoutTransition| {
+            from = t.flight.borrow().from.map(|(rect, _)| rect);
+          },
+        );
+      });
+      from
+    };
+    assert_eq!(flight_from(&wnd), None);
+
+    // The flight starts from the layout, not waits for the paint.
+    *width.state_ref() = 30.;
+    wnd.layout();
+    assert_eq!(
+      flight_from(&wnd),
+      Some(Rect::new(Point::new(10., 0.), Size::new(10., 10.)))
+    );
+    assert!(wnd.context.has_actived_animate());
+
+    clock.advance(Duration::from_secs(2));
+    wnd.draw_frame();
+    assert_eq!(flight_from(&wnd), None);
+    assert!(!wnd.context.has_actived_animate());
+  }
+}
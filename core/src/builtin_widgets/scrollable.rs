@@ -1,4 +1,11 @@
-use crate::prelude::*;
+use crate::{data_widget::compose_child_as_data_widget, impl_query_self_only, prelude::*};
+
+/// The distance to the edge of the view that the dragging pointer in scrolls
+/// the view.
+const AUTO_SCROLL_EDGE: f32 = 24.;
+/// The maximum distance scrolled for every drag dispatch.
+const AUTO_SCROLL_STEP: f32 = 8.;
+
 /// Enumerate to describe which direction allow widget to scroll.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Hash)]
 pub enum Scrollable {
@@ -30,8 +37,9 @@ pub struct ScrollableWidget {
 impl ComposeChild for ScrollableWidget {
   type Child = Widget;
   fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
-    widget! {
-      states { this: this.into_writable() }
+    let this = this.into_writable();
+    let w = widget! {
+      states { this: this.clone() }
       init ctx => {
         let smooth_scroll = transitions::SMOOTH_SCROLL.of(ctx);
       }
@@ -65,16 +73,18 @@ impl ComposeChild for ScrollableWidget {
           .subscribe(move |v| this.page = v);
       }
 
-    }
+    };
+    compose_child_as_data_widget(w, State::Stateful(this))
   }
 }
 
+impl Query for ScrollableWidget {
+  impl_query_self_only!();
+}
+
 impl ScrollableWidget {
   #[inline]
-  pub fn jump_to(&mut self, top_left: Point) {
-    let min = self.scroll_view_size() - self.scroll_content_size();
-    self.scroll_pos = top_left.clamp(min.to_vector().to_point(), Point::zero());
-  }
+  pub fn jump_to(&mut self, top_left: Point) { self.scroll_pos = self.clamp_scroll_pos(top_left); }
   #[inline]
   pub fn scroll_view_size(&self) -> Size { self.page }
 
@@ -93,7 +103,19 @@ impl ScrollableWidget {
     }
   }
 
+  /// The scroll position that the view auto scrolls to when the dragging
+  /// pointer is at `pos` of the view.
+  pub(crate) fn auto_scroll_pos(&self, pos: Point) -> Point {
+    let new = self.scroll_pos_by(auto_scroll_delta(pos, self.page));
+    self.clamp_scroll_pos(new)
+  }
+
   fn validate_scroll(&mut self, delta: Point) {
+    let new = self.scroll_pos_by(delta);
+    self.jump_to(new);
+  }
+
+  fn scroll_pos_by(&self, delta: Point) -> Point {
     let mut new = self.scroll_pos;
     if self.scrollable != Scrollable::X {
       new.y += delta.y;
@@ -101,16 +123,39 @@ impl ScrollableWidget {
     if self.scrollable != Scrollable::Y {
       new.x += delta.x;
     }
-    self.jump_to(new);
+    new
+  }
+
+  fn clamp_scroll_pos(&self, pos: Point) -> Point {
+    let min = self.scroll_view_size() - self.scroll_content_size();
+    pos.clamp(min.to_vector().to_point(), Point::zero())
   }
 }
 
+/// Scroll the view when the dragging pointer is near its edge, the nearer the
+/// faster.
+fn auto_scroll_delta(pos: Point, page: Size) -> Point {
+  let step = |pos: f32, len: f32| {
+    if pos < AUTO_SCROLL_EDGE {
+      (AUTO_SCROLL_EDGE - pos.max(0.)) / AUTO_SCROLL_EDGE * AUTO_SCROLL_STEP
+    } else if pos > len - AUTO_SCROLL_EDGE {
+      -(AUTO_SCROLL_EDGE - (len - pos).max(0.)) / AUTO_SCROLL_EDGE * AUTO_SCROLL_STEP
+    } else {
+      0.
+    }
+  };
+  Point::new(step(pos.x, page.width), step(pos.y, page.height))
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::test::{layout_position_by_path, MockBox};
+  use crate::test::{layout_position_by_path, mouse_input, mouse_move, MockBox, MockMulti};
 
   use super::*;
-  use winit::event::{DeviceId, ModifiersState, MouseScrollDelta, TouchPhase, WindowEvent};
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{
+    DeviceId, ElementState, ModifiersState, MouseScrollDelta, TouchPhase, WindowEvent,
+  };
 
   fn test_assert(scrollable: Scrollable, delta_x: f32, delta_y: f32, expect_x: f32, expect_y: f32) {
     let w = widget! {
@@ -159,4 +204,60 @@ mod tests {
     test_assert(Scrollable::Both, -10000., -10000., -900., -900.);
     test_assert(Scrollable::Both, 100., 100., 0., 0.);
   }
+
+  #[test]
+  fn auto_scroll_when_drag_near_edge() {
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 1000.),
+        scrollable: Scrollable::Y,
+        drag_data: 0,
+      }
+    };
+    let mut wnd = Window::default_mock(w, Some(Size::new(100., 100.)));
+    wnd.draw_frame();
+
+    mouse_move(&mut wnd, 50., 50.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_move(&mut wnd, 50., 70.);
+    wnd.layout();
+    assert_eq!(layout_position_by_path(&wnd, &[0, 0, 0, 0]).y, 0.);
+
+    mouse_move(&mut wnd, 50., 96.);
+    wnd.layout();
+    assert!(layout_position_by_path(&wnd, &[0, 0, 0, 0]).y < 0.);
+  }
+
+  #[test]
+  fn scrollable_not_drop_target() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2) = (records.clone(), records.clone());
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          drag_data: 0,
+        }
+        MockBox {
+          size: Size::new(50., 100.),
+          on_drag_enter: move |e| e.accept(),
+          on_drag_leave: move |_| r1.borrow_mut().push("leave"),
+          on_drop: move |_| r2.borrow_mut().push("drop"),
+          MockBox {
+            size: Size::new(50., 1000.),
+            scrollable: Scrollable::Y,
+          }
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, Some(Size::new(100., 100.)));
+    wnd.draw_frame();
+
+    mouse_move(&mut wnd, 10., 10.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_move(&mut wnd, 70., 40.);
+    mouse_move(&mut wnd, 70., 50.);
+    mouse_input(&mut wnd, ElementState::Released);
+    assert_eq!(&*records.borrow(), &["drop"]);
+  }
 }
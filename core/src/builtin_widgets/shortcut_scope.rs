@@ -0,0 +1,39 @@
+use crate::{data_widget::compose_child_as_data_widget, impl_query_self_only, prelude::*};
+
+/// A widget that binds the shortcuts to the actions only for its subtree, the
+/// bindings shadow the bindings of the outer scopes and the app when the focus
+/// is in it.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// let mut bindings = KeyBindings::default();
+/// bindings.bind("Ctrl+K Ctrl+C".parse().unwrap(), "comment");
+/// let w = widget! {
+///   ShortcutScope {
+///     bindings,
+///     on_action: move |e| if e.action == "comment" {
+///       e.prevent_default();
+///     },
+///     Void { tab_index: 0 }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone, Default)]
+pub struct ShortcutScope {
+  #[declare(default)]
+  pub bindings: KeyBindings,
+}
+
+impl ComposeChild for ShortcutScope {
+  type Child = Widget;
+  #[inline]
+  fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
+    compose_child_as_data_widget(child, this)
+  }
+}
+
+impl Query for ShortcutScope {
+  impl_query_self_only!();
+}
@@ -0,0 +1,114 @@
+use std::{borrow::Cow, collections::HashMap, error::Error};
+
+/// The format name of the plain text in the clipboard.
+pub const TEXT_FORMAT: &str = "text/plain";
+
+/// The clipboard abstraction of the application, the data is stored by its
+/// format name, so other formats like rich text or image can be supported
+/// without change the interface.
+pub trait Clipboard {
+  /// Read the plain text in the clipboard.
+  fn read_text(&mut self) -> Result<String, Box<dyn Error>>;
+
+  /// Write the plain text to the clipboard, the old content is replaced.
+  fn write_text(&mut self, text: &str) -> Result<(), Box<dyn Error>>;
+
+  /// Read the data of the `format` in the clipboard.
+  fn read(&mut self, format: &str) -> Result<Cow<'_, [u8]>, Box<dyn Error>>;
+
+  /// Write the data with its `format` to the clipboard, the old content is
+  /// replaced.
+  fn write(&mut self, format: &str, data: &[u8]) -> Result<(), Box<dyn Error>>;
+
+  /// Clear the content of the clipboard.
+  fn clear(&mut self) -> Result<(), Box<dyn Error>>;
+}
+
+/// The clipboard of the operating system.
+pub struct SystemClipboard(arboard::Clipboard);
+
+impl SystemClipboard {
+  pub fn new() -> Result<Self, Box<dyn Error>> { Ok(Self(arboard::Clipboard::new()?)) }
+}
+
+impl Clipboard for SystemClipboard {
+  fn read_text(&mut self) -> Result<String, Box<dyn Error>> { Ok(self.0.get_text()?) }
+
+  fn write_text(&mut self, text: &str) -> Result<(), Box<dyn Error>> { Ok(self.0.set_text(text)?) }
+
+  fn read(&mut self, format: &str) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
+    if format == TEXT_FORMAT {
+      Ok(Cow::Owned(self.read_text()?.into_bytes()))
+    } else {
+      Err(format!("clipboard format `{format}` is not supported.").into())
+    }
+  }
+
+  fn write(&mut self, format: &str, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    if format == TEXT_FORMAT {
+      self.write_text(std::str::from_utf8(data)?)
+    } else {
+      Err(format!("clipboard format `{format}` is not supported.").into())
+    }
+  }
+
+  fn clear(&mut self) -> Result<(), Box<dyn Error>> { Ok(self.0.clear()?) }
+}
+
+/// A clipboard only keep its content in memory, not share with other
+/// applications. It's useful for test or the platform without a clipboard.
+#[derive(Default)]
+pub struct MockClipboard {
+  data: HashMap<String, Vec<u8>>,
+}
+
+impl Clipboard for MockClipboard {
+  fn read_text(&mut self) -> Result<String, Box<dyn Error>> {
+    let data = self.read(TEXT_FORMAT)?;
+    Ok(String::from_utf8(data.into_owned())?)
+  }
+
+  fn write_text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+    self.write(TEXT_FORMAT, text.as_bytes())
+  }
+
+  fn read(&mut self, format: &str) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
+    self
+      .data
+      .get(format)
+      .map(|data| Cow::Borrowed(data.as_slice()))
+      .ok_or_else(|| format!("clipboard has no content of `{format}`.").into())
+  }
+
+  fn write(&mut self, format: &str, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    self.data.clear();
+    self.data.insert(format.to_string(), data.to_vec());
+    Ok(())
+  }
+
+  fn clear(&mut self) -> Result<(), Box<dyn Error>> {
+    self.data.clear();
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mock_clipboard() {
+    let mut clipboard = MockClipboard::default();
+    assert!(clipboard.read_text().is_err());
+
+    clipboard.write_text("hello").unwrap();
+    assert_eq!(clipboard.read_text().unwrap(), "hello");
+
+    clipboard.write("image/png", &[1, 2, 3]).unwrap();
+    assert!(clipboard.read_text().is_err());
+    assert_eq!(&*clipboard.read("image/png").unwrap(), &[1, 2, 3]);
+
+    clipboard.clear().unwrap();
+    assert!(clipboard.read("image/png").is_err());
+  }
+}
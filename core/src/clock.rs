@@ -0,0 +1,61 @@
+use std::{
+  cell::Cell,
+  rc::Rc,
+  time::{Duration, Instant},
+};
+
+/// The source of time of a window, the animations, the frame ticker and the
+/// multi tap events all read the current time from it. So replace it with a
+/// [`ManualClock`] can make them deterministic in tests.
+pub trait Clock {
+  /// Return the current time.
+  fn now(&self) -> Instant;
+}
+
+/// The clock use the real time of the system.
+#[derive(Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  #[inline]
+  fn now(&self) -> Instant { Instant::now() }
+}
+
+/// A clock that only goes forward when you tell it, its clones share the same
+/// time.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::clock::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::default();
+/// let start = clock.now();
+/// clock.advance(Duration::from_millis(50));
+/// assert_eq!(clock.now() - start, Duration::from_millis(50));
+/// ```
+#[derive(Clone)]
+pub struct ManualClock {
+  now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+  /// Create a clock start at `now`.
+  pub fn new(now: Instant) -> Self { Self { now: Rc::new(Cell::new(now)) } }
+
+  /// Move the time of the clock forward by `dur`.
+  pub fn advance(&self, dur: Duration) { self.now.set(self.now.get() + dur); }
+
+  /// Set the time of the clock.
+  pub fn set(&self, now: Instant) { self.now.set(now); }
+}
+
+impl Default for ManualClock {
+  fn default() -> Self { Self::new(Instant::now()) }
+}
+
+impl Clock for ManualClock {
+  #[inline]
+  fn now(&self) -> Instant { self.now.get() }
+}
@@ -4,7 +4,11 @@ use std::{
   sync::{Arc, RwLock},
 };
 
-use crate::builtin_widgets::Theme;
+use crate::{
+  builtin_widgets::Theme,
+  clipboard::{Clipboard, MockClipboard, SystemClipboard},
+  events::KeyBindings,
+};
 
 use ::ribir_text::shaper::TextShaper;
 pub use futures::task::SpawnError;
@@ -23,6 +27,9 @@ pub struct AppContext {
   pub reorder: TextReorder,
   pub typography_store: TypographyStore,
   pub executor: Executor,
+  pub clipboard: Rc<RefCell<dyn Clipboard>>,
+  /// The app-wide key bindings, remap them by [`KeyBindings::load_config`].
+  pub key_bindings: Rc<RefCell<KeyBindings>>,
 }
 
 #[derive(Clone)]
@@ -61,6 +68,14 @@ impl Default for AppContext {
     let reorder = TextReorder::default();
     let typography_store = TypographyStore::new(reorder.clone(), font_db.clone(), shaper.clone());
 
+    let clipboard: Rc<RefCell<dyn Clipboard>> = match SystemClipboard::new() {
+      Ok(clipboard) => Rc::new(RefCell::new(clipboard)),
+      Err(err) => {
+        log::warn!("System clipboard is not available, use an in-memory clipboard instead: {err}");
+        Rc::new(RefCell::new(MockClipboard::default()))
+      }
+    };
+
     AppContext {
       font_db,
       app_theme: <_>::default(),
@@ -68,6 +83,8 @@ impl Default for AppContext {
       reorder,
       typography_store,
       executor: <_>::default(),
+      clipboard,
+      key_bindings: Rc::new(RefCell::new(KeyBindings::app_default())),
     }
   }
 }
@@ -1,6 +1,6 @@
 use super::{define_widget_context, WidgetCtxImpl, WindowCtx};
 use crate::{
-  events::dispatcher::DispatchInfo,
+  events::{dispatcher::DispatchInfo, FocusController, PointerId},
   widget::{LayoutStore, TreeArena},
   widget_tree::WidgetId,
 };
@@ -18,4 +18,31 @@ impl<'a> EventCtx<'a> {
 
   #[inline]
   pub fn modifiers(&self) -> ModifiersState { self.info.modifiers() }
+
+  /// Return the controller of the focus of the window.
+  #[inline]
+  pub fn focus_controller(&self) -> FocusController { self.wnd_ctx.focus_controller() }
+
+  /// Capture the pointer `id` to the widget of this context, the move and up
+  /// events of the pointer are dispatched to the widget wherever the pointer
+  /// is, and the pointer enter and leave events are not fired. The capture is
+  /// released when the pointer is released.
+  #[inline]
+  pub fn set_pointer_capture(&mut self, id: PointerId) {
+    self.info.set_pointer_capture(id, self.id)
+  }
+
+  /// Release the capture of the pointer `id` if it's captured by the widget of
+  /// this context.
+  pub fn release_pointer_capture(&mut self, id: PointerId) {
+    if self.has_pointer_capture(id) {
+      self.info.remove_pointer_capture(id);
+    }
+  }
+
+  /// Return if the pointer `id` is captured by the widget of this context.
+  #[inline]
+  pub fn has_pointer_capture(&self, id: PointerId) -> bool {
+    self.info.pointer_capture(id) == Some(self.id)
+  }
 }
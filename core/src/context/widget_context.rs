@@ -10,6 +10,8 @@ use super::WindowCtx;
 
 /// common action for all context of widget.
 pub trait WidgetContext {
+  /// Return the id of the widget of this context.
+  fn widget_id(&self) -> WidgetId;
   /// Return parent of widget of this context.
   fn parent(&self) -> Option<WidgetId>;
   /// Return parent of widget `w`.
@@ -64,6 +66,9 @@ pub(crate) trait WidgetCtxImpl {
 }
 
 impl<T: WidgetCtxImpl> WidgetContext for T {
+  #[inline]
+  fn widget_id(&self) -> WidgetId { self.id() }
+
   #[inline]
   fn parent(&self) -> Option<WidgetId> { self.id().parent(self.tree_arena()) }
 
@@ -2,15 +2,20 @@ use futures::{
   task::{LocalSpawnExt, SpawnError},
   Future,
 };
-use ribir_painter::TypographyStore;
+use ribir_painter::{Point, TypographyStore};
 use rxrust::{scheduler::FuturesLocalScheduler, subject::Subject};
 use std::{cell::RefCell, convert::Infallible, rc::Rc, time::Instant};
 
 use super::AppContext;
 use crate::{
-  animation::AnimateTrack,
+  animation::{AnimateTrack, HeroTrack},
   builtin_widgets::Theme,
-  events::focus_mgr::{FocusManager, FocusType, FocustHandle},
+  clipboard::Clipboard,
+  clock::{Clock, SystemClock},
+  events::{
+    focus_mgr::{FocusController, FocusManager, FocusType, FocustHandle},
+    DragSession,
+  },
   ticker::{FrameMsg, FrameTicker},
   widget::{TreeArena, WidgetId},
 };
@@ -22,6 +27,23 @@ pub struct WindowCtx {
   pub(crate) app_ctx: AppContext,
   pub(crate) actived_animates: Rc<RefCell<u32>>,
   pub(crate) frame_scheduler: FuturesLocalScheduler,
+  pub(crate) ime_request: Rc<RefCell<ImeRequest>>,
+  pub(crate) clock: Rc<RefCell<Box<dyn Clock>>>,
+  pub(crate) delay_drop_widgets: Rc<RefCell<Vec<DelayDropWidget>>>,
+  pub(crate) hero_track: Rc<RefCell<HeroTrack>>,
+  pub(crate) layout_transitions: Rc<RefCell<Vec<WidgetId>>>,
+  pub(crate) drag: Rc<RefCell<Option<DragSession>>>,
+}
+
+/// A disposed widget that delay to drop, with the parent it's painted in.
+pub(crate) type DelayDropWidget = (Option<WidgetId>, WidgetId);
+
+/// The modify of the input method that widgets requested, apply to the native
+/// window later.
+#[derive(Default)]
+pub(crate) struct ImeRequest {
+  pub(crate) allowed: Option<bool>,
+  pub(crate) pos: Option<Point>,
 }
 
 impl WindowCtx {
@@ -34,6 +56,12 @@ impl WindowCtx {
       frame_ticker: FrameTicker::default(),
       actived_animates: Rc::new(RefCell::new(0)),
       frame_scheduler,
+      ime_request: <_>::default(),
+      clock: Rc::new(RefCell::new(Box::new(SystemClock))),
+      delay_drop_widgets: <_>::default(),
+      hero_track: <_>::default(),
+      layout_transitions: <_>::default(),
+      drag: <_>::default(),
     }
   }
 
@@ -50,22 +78,53 @@ impl WindowCtx {
 
   pub fn typography_store(&self) -> &TypographyStore { &self.app_ctx.typography_store }
 
+  /// Allow the input method of the window or not, the widget that accepts the
+  /// text input should allow it when it's focused.
+  pub fn set_ime_allowed(&self, allowed: bool) {
+    self.ime_request.borrow_mut().allowed = Some(allowed);
+  }
+
+  /// Set the position of the candidate window of the input method, `pos` is in
+  /// the window coordinate, usually the bottom left of the caret.
+  pub fn set_ime_pos(&self, pos: Point) { self.ime_request.borrow_mut().pos = Some(pos); }
+
+  pub(crate) fn take_ime_request(&self) -> ImeRequest { self.ime_request.take() }
+
+  /// Return the clipboard of the application.
+  #[inline]
+  pub fn clipboard(&self) -> Rc<RefCell<dyn Clipboard>> { self.app_ctx.clipboard.clone() }
+
+  /// Return the current time of the window clock, use it instead of
+  /// `Instant::now()` to keep the time logic testable.
+  #[inline]
+  pub fn now(&self) -> Instant { self.clock.borrow().now() }
+
+  /// Replace the clock of the window, all the time of the window will read from
+  /// the new clock. For example, set a [`ManualClock`] to step the animations
+  /// by hand in tests.
+  ///
+  /// [`ManualClock`]: crate::clock::ManualClock
+  pub fn set_clock(&self, clock: impl Clock + 'static) {
+    *self.clock.borrow_mut() = Box::new(clock);
+  }
+
   pub fn frame_tick_stream(&self) -> Subject<'static, FrameMsg, Infallible> {
     self.frame_ticker.frame_tick_stream()
   }
 
   pub(crate) fn begin_frame(&mut self) {
-    self.frame_ticker.emit(FrameMsg::NewFrame(Instant::now()));
+    let now = self.now();
+    self.frame_ticker.emit(FrameMsg::NewFrame(now));
   }
   pub(crate) fn layout_ready(&mut self) {
-    self
-      .frame_ticker
-      .emit(FrameMsg::LayoutReady(Instant::now()));
+    let now = self.now();
+    self.frame_ticker.emit(FrameMsg::LayoutReady(now));
   }
 
   pub(crate) fn end_frame(&mut self) {
     self.app_ctx.end_frame();
-    self.frame_ticker.emit(FrameMsg::Finish(Instant::now()));
+    let now = self.now();
+    self.frame_ticker.emit(FrameMsg::Finish(now));
   }
 
   pub(crate) fn next_focus(&self, arena: &TreeArena) {
@@ -76,6 +135,9 @@ impl WindowCtx {
     self.focus_mgr.borrow_mut().prev_focus(arena);
   }
 
+  /// Return the controller of the focus of the window.
+  pub fn focus_controller(&self) -> FocusController { FocusManager::controller(&self.focus_mgr) }
+
   pub(crate) fn focus_handle(&self, wid: WidgetId) -> FocustHandle {
     FocusManager::focus_handle(&self.focus_mgr, wid)
   }
@@ -2,7 +2,7 @@ use crate::{
   builtin_widgets::key::AnyKey,
   impl_proxy_query, impl_query_self_only,
   prelude::*,
-  widget::widget_id::{dispose_nodes, empty_node, split_arena},
+  widget::widget_id::{dispatch_disposed, drop_subtree, empty_node, split_arena},
 };
 use std::{cell::RefCell, collections::HashMap};
 
@@ -147,13 +147,14 @@ impl<D> DynRender<D> {
       DynWidgetGenInfo::DynDepth(depth) => {
         assert_eq!(new_widgets.len(), 1);
 
-        dispose_nodes(
-          iter_single_child(*sign, arena, *depth),
-          arena,
-          store,
-          wnd_ctx,
-        );
+        iter_single_child(*sign, arena, *depth)
+          .for_each(|w| dispatch_disposed(w, arena, store, wnd_ctx));
         let old_sign = refresh_sign_id(*sign, &mut new_widgets, arena);
+        // The old widget may delay to drop and paint with its layout, so the layout
+        // information follow it.
+        if let Some(info) = store.remove(*sign) {
+          *store.layout_info_or_default(old_sign) = info;
+        }
         let declare_child_parent = single_down(old_sign, arena, *depth as isize - 1);
         let (new_leaf, down_level) = down_to_leaf(*sign, arena);
 
@@ -164,10 +165,20 @@ impl<D> DynRender<D> {
           declare_child_parent
             .children(arena1)
             .for_each(|c| new_leaf.append(c, arena2));
+          // The declared children have moved to the new widget, the old widget
+          // delay to drop keeps an empty child to layout.
+          if old_sign.need_delay_drop(arena) {
+            let empty = empty_node(arena);
+            declare_child_parent.append(empty, arena);
+          }
         }
 
         old_sign.insert_after(*sign, arena);
-        old_sign.remove_subtree(arena);
+        drop_subtree(old_sign, arena, store, wnd_ctx);
+        if !old_sign.is_dropped(arena) {
+          // The widget delay to drop may lose its size by this relayout.
+          dirty_set.borrow_mut().insert(old_sign);
+        }
 
         let mut w = *sign;
         loop {
@@ -182,29 +193,42 @@ impl<D> DynRender<D> {
       }
 
       DynWidgetGenInfo::WholeSubtree(siblings) => {
-        let mut remove = Some(*sign);
-        (0..*siblings).for_each(|_| {
-          let o = remove.unwrap();
-          remove = o.next_sibling(arena);
-          dispose_nodes(o.descendants(arena), arena, store, wnd_ctx);
-          if o != *sign {
-            o.remove_subtree(arena);
-          }
-        });
+        let mut cursor = Some(*sign);
+        let old_siblings = (0..*siblings)
+          .map(|_| {
+            let o = cursor.unwrap();
+            cursor = o.next_sibling(arena);
+            o.descendants(arena)
+              .for_each(|w| dispatch_disposed(w, arena, store, wnd_ctx));
+            o
+          })
+          .collect::<Vec<_>>();
 
         let old_sign = refresh_sign_id(*sign, &mut new_widgets, arena);
+        // The old widget may delay to drop and paint with its layout, so the layout
+        // information follow it.
+        if let Some(info) = store.remove(*sign) {
+          *store.layout_info_or_default(old_sign) = info;
+        }
         let mut cursor = old_sign;
         new_widgets.iter().rev().for_each(|n| {
           cursor.insert_before(*n, arena);
           cursor = *n;
         });
 
+        old_siblings.into_iter().for_each(|o| {
+          let o = if o == *sign { old_sign } else { o };
+          drop_subtree(o, arena, store, wnd_ctx);
+          if !o.is_dropped(arena) {
+            // The widget delay to drop may lose its size by this relayout.
+            dirty_set.borrow_mut().insert(o);
+          }
+        });
+
         new_widgets
           .iter()
           .for_each(|n| n.on_mounted_subtree(arena, store, wnd_ctx, dirty_set));
         *siblings = new_widgets.len();
-
-        old_sign.remove_subtree(arena);
       }
     };
     // Place the dynRender back in node.
@@ -280,7 +304,7 @@ impl<D: 'static> Query for DynWidget<D> {
   impl_query_self_only!();
 }
 
-fn inspect_key(id: &WidgetId, tree: &TreeArena, mut cb: impl FnMut(&dyn AnyKey)) {
+pub(crate) fn inspect_key(id: &WidgetId, tree: &TreeArena, mut cb: impl FnMut(&dyn AnyKey)) {
   #[allow(clippy::borrowed_box)]
   id.assert_get(tree).query_on_first_type(
     QueryOrder::OutsideFirst,
@@ -571,4 +595,95 @@ mod tests {
         .all(|item| expect_vec.contains(item))
     );
   }
+
+  #[test]
+  fn delay_drop_widgets() {
+    let child = Stateful::new(true);
+    let child_until = Stateful::new(false);
+    let w = widget! {
+      states { child: child.clone(), child_until: child_until.clone() }
+      MockMulti {
+        DynWidget {
+          dyns: child.then(move || widget! {
+            MockMulti {
+              delay_drop_until: *child_until,
+              MockBox {
+                size: Size::zero(),
+                // only the root of the removed subtree can delay to drop.
+                delay_drop_until: false,
+              }
+            }
+          })
+        }
+      }
+    };
+
+    let scheduler = FuturesLocalSchedulerPool::default().spawner();
+    let mut tree = WidgetTree::new(w, WindowCtx::new(AppContext::default(), scheduler));
+    tree.layout(Size::zero());
+    let cnt = tree.count();
+
+    *child.state_ref() = false;
+    tree.layout(Size::zero());
+    let delay_widgets = tree.wnd_ctx.delay_drop_widgets.borrow().clone();
+    assert_eq!(delay_widgets.len(), 1);
+    let (parent, delay) = delay_widgets[0];
+    assert_eq!(parent, Some(tree.root()));
+    assert!(!delay.is_dropped(&tree.arena));
+    assert!(tree.store.layout_box_size(delay).is_some());
+    // the delay drop widget with its subtree is detached, and an empty node
+    // replace it.
+    let detached = delay.descendants(&tree.arena).count();
+    assert_eq!(tree.count(), cnt - detached + 1);
+
+    *child_until.state_ref() = true;
+    tree.layout(Size::zero());
+    assert!(tree.wnd_ctx.delay_drop_widgets.borrow().is_empty());
+    assert!(delay.is_dropped(&tree.arena));
+  }
+
+  #[test]
+  fn delay_drop_dyn_depth() {
+    let trigger = Stateful::new(0);
+    let delay = Stateful::new(DelayDrop { delay_drop_until: false });
+    let c_delay = delay.clone();
+    let w = widget! {
+      states { trigger: trigger.clone() }
+      MockMulti {
+        DynWidget {
+          dyns: {
+            let _ = *trigger;
+            c_delay.clone()
+          },
+          MockBox { size: Size::zero() }
+        }
+      }
+    };
+
+    let scheduler = FuturesLocalSchedulerPool::default().spawner();
+    let mut tree = WidgetTree::new(w, WindowCtx::new(AppContext::default(), scheduler));
+    tree.layout(Size::zero());
+    let cnt = tree.count();
+    let declared = tree.root().first_child(&tree.arena).unwrap();
+    let declared = declared.single_child(&tree.arena).unwrap();
+
+    *trigger.state_ref() += 1;
+    tree.layout(Size::zero());
+    let delay_widgets = tree.wnd_ctx.delay_drop_widgets.borrow().clone();
+    assert_eq!(delay_widgets.len(), 1);
+    let (parent, old) = delay_widgets[0];
+    assert_eq!(parent, Some(tree.root()));
+    assert!(!old.is_dropped(&tree.arena));
+    assert!(tree.store.layout_box_size(old).is_some());
+    // the declared child moves to the new widget.
+    let new = tree.root().first_child(&tree.arena).unwrap();
+    assert_eq!(new.single_child(&tree.arena), Some(declared));
+    assert_ne!(old.single_child(&tree.arena), Some(declared));
+    assert_eq!(tree.count(), cnt);
+
+    delay.state_ref().delay_drop_until = true;
+    tree.layout(Size::zero());
+    assert!(tree.wnd_ctx.delay_drop_widgets.borrow().is_empty());
+    assert!(old.is_dropped(&tree.arena));
+  }
 }
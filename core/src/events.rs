@@ -15,9 +15,20 @@ mod keyboard;
 pub use keyboard::*;
 mod character;
 pub use character::*;
+mod ime;
+pub use ime::*;
 mod wheel;
 pub use wheel::*;
+mod gestures;
+pub use gestures::*;
+mod drag_drop;
+pub use drag_drop::*;
+mod file_drop;
+pub use file_drop::*;
+mod shortcut;
+pub use shortcut::*;
 pub(crate) mod focus_mgr;
+pub use focus_mgr::{FocusChange, FocusController, FocusDirection};
 mod listener_impl_helper;
 use self::dispatcher::DispatchInfo;
 
@@ -45,7 +56,8 @@ impl EventCommon {
   /// this has been changed along the way through retargeting.
   #[inline]
   pub fn current_target(&self) -> WidgetId { self.current_target }
-  /// Prevent event bubbling to parent.
+  /// Prevent event bubbling to parent. In the capture phase, it also prevents
+  /// the event from being dispatched to the descendants and bubbling.
   #[inline]
   pub fn stop_bubbling(&mut self) { self.cancel_bubble = true }
   /// Return it the event is canceled to bubble to parent.
@@ -2,9 +2,11 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::{prelude::*, widget_tree::WidgetTree};
 use ::ribir_text::PIXELS_PER_EM;
-use winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event::{
+  DeviceId, ElementState, Force, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+};
 
-use super::focus_mgr::FocusManager;
+use super::{focus_mgr::FocusManager, FileHover, GestureArena};
 
 pub(crate) struct Dispatcher {
   pub(crate) focus_mgr: Rc<RefCell<FocusManager>>,
@@ -12,6 +14,23 @@ pub(crate) struct Dispatcher {
   pub(crate) info: DispatchInfo,
   pub(crate) entered_widgets: Vec<WidgetId>,
   pub(crate) pointer_down_uid: Option<WidgetId>,
+  pub(crate) gestures: GestureArena,
+  /// The touch pointers pressed on the window, in the order they are pressed.
+  pub(crate) touches: Vec<TouchPointer>,
+  pub(crate) file_hover: FileHover,
+  /// The key chords pressed that begin a multi-stroke shortcut.
+  pub(crate) pending_chords: Vec<KeyChord>,
+}
+
+/// The state of a touch pointer from it's pressed to released.
+pub(crate) struct TouchPointer {
+  /// The identifier of the finger from the platform.
+  touch_id: u64,
+  id: PointerId,
+  pos: Point,
+  force: Option<Force>,
+  down_on: Option<WidgetId>,
+  entered_widgets: Vec<WidgetId>,
 }
 
 impl Dispatcher {
@@ -22,6 +41,10 @@ impl Dispatcher {
       info: <_>::default(),
       entered_widgets: vec![],
       pointer_down_uid: None,
+      gestures: <_>::default(),
+      touches: vec![],
+      file_hover: <_>::default(),
+      pending_chords: vec![],
     }
   }
 }
@@ -31,10 +54,15 @@ pub(crate) struct DispatchInfo {
   mouse_button: (Option<DeviceId>, MouseButtons),
   /// The current global position (relative to window) of mouse
   cursor_pos: Point,
+  /// The global position of the pointer that is dispatching if it's not the
+  /// mouse cursor, like a touch or a dragging pointer.
+  pub(crate) pointer_pos: Option<Point>,
   /// Cursor icon try to set to window.
   cursor_icon: Option<CursorIcon>,
   /// The current state of the keyboard modifiers
   modifiers: ModifiersState,
+  /// The pointers captured by the widgets.
+  pointer_captures: Vec<(PointerId, WidgetId)>,
 }
 
 impl Dispatcher {
@@ -56,7 +84,12 @@ impl Dispatcher {
       WindowEvent::ReceivedCharacter(c) => {
         self.dispatch_received_char(c, tree);
       }
+      WindowEvent::Ime(ime) => self.dispatch_ime(ime, tree),
       WindowEvent::MouseWheel { delta, .. } => self.dispatch_wheel(delta, tree, wnd_factor),
+      WindowEvent::Touch(touch) => self.dispatch_touch(touch, tree, wnd_factor),
+      WindowEvent::HoveredFile(path) => self.dispatch_hovered_file(path, tree),
+      WindowEvent::DroppedFile(path) => self.dispatch_dropped_file(path, tree),
+      WindowEvent::HoveredFileCancelled => self.dispatch_hovered_file_cancelled(tree),
       _ => log::info!("not processed event {:?}", event),
     }
   }
@@ -74,8 +107,14 @@ impl Dispatcher {
           common: EventCommon::new(focus, tree, &self.info),
         };
         match input.state {
-          ElementState::Pressed => tree.bubble_event::<KeyDownListener>(&mut event),
-          ElementState::Released => tree.bubble_event::<KeyUpListener>(&mut event),
+          ElementState::Pressed => {
+            tree.capture_event::<KeyDownCaptureListener>(&mut event);
+            tree.bubble_event::<KeyDownListener>(&mut event);
+          }
+          ElementState::Released => {
+            tree.capture_event::<KeyUpCaptureListener>(&mut event);
+            tree.bubble_event::<KeyUpListener>(&mut event);
+          }
         };
 
         event.common.prevent_default
@@ -98,32 +137,47 @@ impl Dispatcher {
     }
   }
 
-  pub fn shortcut_process(
-    &mut self,
-    key: VirtualKeyCode,
-    state: ElementState,
-    tree: &mut WidgetTree,
-  ) {
-    if key == VirtualKeyCode::Tab && ElementState::Pressed == state {
-      if self.info.modifiers.contains(ModifiersState::SHIFT) {
-        self.prev_focus_widget(tree);
-      } else {
-        self.next_focus_widget(tree);
+  pub fn dispatch_ime(&mut self, ime: Ime, tree: &mut WidgetTree) {
+    match ime {
+      Ime::Preedit(preedit, cursor) => {
+        if let Some(focus) = self.focusing() {
+          let mut event = ImePreeditEvent {
+            preedit,
+            cursor,
+            common: EventCommon::new(focus, tree, &self.info),
+          };
+          tree.bubble_event::<ImePreeditListener>(&mut event);
+        }
       }
+      // The committed text is received as the chars typed.
+      Ime::Commit(text) => text
+        .chars()
+        .for_each(|c| self.dispatch_received_char(c, tree)),
+      Ime::Enabled | Ime::Disabled => {}
     }
   }
 
   pub fn cursor_move_to(&mut self, position: Point, tree: &mut WidgetTree) {
     self.info.cursor_pos = position;
-    self.pointer_enter_leave_dispatch(tree);
-    if let Some(mut event) = self.pointer_event_for_hit_widget(tree) {
+    // The hover state is frozen during the pointer is captured.
+    if self.captured_widget(PointerId::MOUSE, tree).is_none() {
+      self.mouse_enter_leave_dispatch(tree);
+    }
+    if let Some(mut event) = self.mouse_event_for_target(tree) {
+      tree.capture_event::<PointerMoveCaptureListener>(&mut event);
       tree.bubble_event::<PointerMoveListener>(&mut event);
     }
+    self
+      .gestures
+      .pointer_move(PointerId::MOUSE, position, tree, &self.info);
+    self.drag_dispatch(Some((PointerId::MOUSE, position)), tree);
   }
 
   pub fn on_cursor_left(&mut self, tree: &mut WidgetTree) {
     self.info.cursor_pos = Point::new(-1., -1.);
-    self.pointer_enter_leave_dispatch(tree);
+    if self.captured_widget(PointerId::MOUSE, tree).is_none() {
+      self.mouse_enter_leave_dispatch(tree);
+    }
   }
 
   pub fn dispatch_mouse_input(
@@ -148,13 +202,27 @@ impl Dispatcher {
           // only the last button release emit event.
           if self.info.mouse_button.1.is_empty() {
             self.info.mouse_button.0 = None;
-            let mut release_event = self.pointer_event_for_hit_widget(tree)?;
-            tree.bubble_event::<PointerUpListener>(&mut release_event);
+            let release_event = self.mouse_event_for_target(tree);
+            if let Some(mut release_event) = release_event.clone() {
+              tree.capture_event::<PointerUpCaptureListener>(&mut release_event);
+              tree.bubble_event::<PointerUpListener>(&mut release_event);
+            }
+            if self.info.remove_pointer_capture(PointerId::MOUSE) {
+              self.mouse_enter_leave_dispatch(tree);
+            }
+            let claimed = self.gestures.pointer_up(PointerId::MOUSE, tree, &self.info);
+            self.drag_dispatch(Some((PointerId::MOUSE, self.info.cursor_pos)), tree);
 
-            let tap_on = self
-              .pointer_down_uid
-              .take()?
-              .lowest_common_ancestor(release_event.target(), &tree.arena)?;
+            let down_on = self.pointer_down_uid.take()?;
+            // The tap is rejected if a gesture claimed the pointer.
+            if claimed {
+              return None;
+            }
+            let tap_on = down_on.lowest_common_ancestor(release_event?.target(), &tree.arena)?;
+            let pos = self.info.cursor_pos;
+            if let Some(single_tap) = self.gestures.tap(PointerId::MOUSE, tap_on, pos, tree) {
+              self.single_tap_dispatch(single_tap, tree);
+            }
             let mut tap_event = PointerEvent::from_mouse(tap_on, tree, &self.info);
 
             tree.bubble_event::<TapListener>(&mut tap_event);
@@ -189,49 +257,248 @@ impl Dispatcher {
     }
   }
 
+  pub fn dispatch_touch(&mut self, touch: Touch, tree: &mut WidgetTree, wnd_factor: f64) {
+    let pos = touch.location.to_logical::<f32>(wnd_factor);
+    let pos = Point::new(pos.x, pos.y);
+    let idx = self.touches.iter().position(|t| t.touch_id == touch.id);
+    let idx = match (idx, touch.phase) {
+      (Some(idx), _) => idx,
+      (None, TouchPhase::Started) => {
+        self.touches.push(TouchPointer {
+          touch_id: touch.id,
+          id: PointerId::new_unique(),
+          pos,
+          force: touch.force,
+          down_on: None,
+          entered_widgets: vec![],
+        });
+        self.touches.len() - 1
+      }
+      (None, _) => return,
+    };
+    let pointer = &mut self.touches[idx];
+    pointer.pos = pos;
+    pointer.force = touch.force;
+
+    self.info.pointer_pos = Some(pos);
+    match touch.phase {
+      TouchPhase::Started => self.touch_down(idx, tree),
+      TouchPhase::Moved => self.touch_move(idx, tree),
+      TouchPhase::Ended => self.touch_up(idx, tree),
+      TouchPhase::Cancelled => self.touch_cancel(idx, tree),
+    }
+    if matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+      self.touch_enter_leave_dispatch(idx, None, tree);
+      self.touches.remove(idx);
+    }
+    self.info.pointer_pos = None;
+  }
+
   pub fn take_cursor_icon(&mut self) -> Option<CursorIcon> { self.info.cursor_icon.take() }
 
   fn bubble_mouse_down(&mut self, tree: &mut WidgetTree) {
-    let event = self.pointer_event_for_hit_widget(tree);
+    let event = self.mouse_event_for_target(tree);
     self.pointer_down_uid = event.as_ref().map(|e| e.target());
-    let nearest_focus = self.pointer_down_uid.and_then(|wid| {
+    self.focus_pointer_down(self.pointer_down_uid, tree);
+    if let Some(mut event) = event {
+      tree.capture_event::<PointerDownCaptureListener>(&mut event);
+      tree.bubble_event::<PointerDownListener>(&mut event);
+      let pos = self.info.cursor_pos;
+      self
+        .gestures
+        .pointer_down(PointerId::MOUSE, event.target(), pos, tree, &self.info);
+    }
+  }
+
+  /// Focus the nearest focus widget of the widget that pointer pressed on.
+  fn focus_pointer_down(&mut self, down_on: Option<WidgetId>, tree: &mut WidgetTree) {
+    self.set_focus_visible(false);
+    let nearest_focus = down_on.and_then(|wid| {
       wid.ancestors(&tree.arena).find(|id| {
         id.get(&tree.arena)
           .map_or(false, |w| w.contain_type::<FocusNode>())
       })
     });
     if let Some(focus_id) = nearest_focus {
+      if self.focus_mgr.borrow().is_trapped_out(focus_id) {
+        return;
+      }
       self.focus(focus_id, tree);
     } else {
       self.blur(tree);
     }
-    if let Some(mut event) = event {
+  }
+
+  fn touch_down(&mut self, idx: usize, tree: &mut WidgetTree) {
+    let hit = self.hit_widget(tree);
+    self.touch_enter_leave_dispatch(idx, hit, tree);
+    self.touches[idx].down_on = hit;
+    self.focus_pointer_down(hit, tree);
+    if let Some(target) = hit {
+      let mut event = self.touch_event(idx, target, tree);
+      tree.capture_event::<PointerDownCaptureListener>(&mut event);
       tree.bubble_event::<PointerDownListener>(&mut event);
+      let TouchPointer { id, pos, .. } = self.touches[idx];
+      self
+        .gestures
+        .pointer_down(id, target, pos, tree, &self.info);
+    }
+  }
+
+  fn touch_move(&mut self, idx: usize, tree: &mut WidgetTree) {
+    let captured = self.captured_widget(self.touches[idx].id, tree);
+    if captured.is_none() {
+      let hit = self.hit_widget(tree);
+      self.touch_enter_leave_dispatch(idx, hit, tree);
+    }
+    if let Some(target) = captured.or_else(|| self.hit_widget(tree)) {
+      let mut event = self.touch_event(idx, target, tree);
+      tree.capture_event::<PointerMoveCaptureListener>(&mut event);
+      tree.bubble_event::<PointerMoveListener>(&mut event);
+    }
+    let TouchPointer { id, pos, .. } = self.touches[idx];
+    self.gestures.pointer_move(id, pos, tree, &self.info);
+    self.drag_dispatch(Some((id, pos)), tree);
+  }
+
+  fn touch_up(&mut self, idx: usize, tree: &mut WidgetTree) {
+    let TouchPointer { id, pos, .. } = self.touches[idx];
+    let hit = self
+      .captured_widget(id, tree)
+      .or_else(|| self.hit_widget(tree));
+    if let Some(target) = hit {
+      let mut event = self.touch_event(idx, target, tree);
+      tree.capture_event::<PointerUpCaptureListener>(&mut event);
+      tree.bubble_event::<PointerUpListener>(&mut event);
+    }
+    self.info.remove_pointer_capture(id);
+    let claimed = self.gestures.pointer_up(id, tree, &self.info);
+    self.drag_dispatch(Some((id, pos)), tree);
+    // The tap is rejected if a gesture claimed the pointer.
+    let tap_on = self.touches[idx]
+      .down_on
+      .zip(hit)
+      .filter(|_| !claimed)
+      .and_then(|(down_on, hit)| down_on.lowest_common_ancestor(hit, &tree.arena));
+    if let Some(tap_on) = tap_on {
+      if let Some(single_tap) = self.gestures.tap(id, tap_on, pos, tree) {
+        self.single_tap_dispatch(single_tap, tree);
+      }
+      let mut event = self.touch_event(idx, tap_on, tree);
+      tree.bubble_event::<TapListener>(&mut event);
+    }
+  }
+
+  fn touch_cancel(&mut self, idx: usize, tree: &mut WidgetTree) {
+    let down_on = self.touches[idx]
+      .down_on
+      .filter(|w| !w.is_dropped(&tree.arena));
+    if let Some(target) = down_on {
+      let mut event = self.touch_event(idx, target, tree);
+      tree.bubble_event::<PointerCancelListener>(&mut event);
+    }
+    let id = self.touches[idx].id;
+    self.info.remove_pointer_capture(id);
+    self.gestures.pointer_up(id, tree, &self.info);
+    self.cancel_drag(id, tree);
+  }
+
+  fn touch_event(&self, idx: usize, target: WidgetId, tree: &WidgetTree) -> PointerEvent {
+    let TouchPointer { id, force, .. } = self.touches[idx];
+    // The first pressed touch is the primary pointer.
+    PointerEvent::from_touch(id, force, idx == 0, target, tree, &self.info)
+  }
+
+  /// Create the pointer event of the pointer `id`.
+  fn pointer_event(&self, id: PointerId, target: WidgetId, tree: &WidgetTree) -> PointerEvent {
+    match self.touches.iter().position(|t| t.id == id) {
+      Some(idx) => self.touch_event(idx, target, tree),
+      None => PointerEvent::from_mouse(target, tree, &self.info),
+    }
+  }
+
+  pub fn check_long_press(&mut self, tree: &mut WidgetTree) {
+    let now = tree.wnd_ctx.now();
+    if let Some((id, target, pos)) = self.gestures.check_long_press(now) {
+      self.info.pointer_pos = self.touches.iter().any(|t| t.id == id).then_some(pos);
+      let mut event = self.pointer_event(id, target, tree);
+      tree.bubble_event::<LongPressListener>(&mut event);
+      self.info.pointer_pos = None;
     }
   }
 
-  fn pointer_enter_leave_dispatch(&mut self, tree: &mut WidgetTree) {
+  pub fn check_single_tap(&mut self, tree: &mut WidgetTree) {
+    let now = tree.wnd_ctx.now();
+    if let Some(single_tap) = self.gestures.check_single_tap(now) {
+      self.single_tap_dispatch(single_tap, tree);
+    }
+  }
+
+  fn single_tap_dispatch(
+    &mut self,
+    (id, target, pos): (PointerId, WidgetId, Point),
+    tree: &mut WidgetTree,
+  ) {
+    if target.is_dropped(&tree.arena) {
+      return;
+    }
+    self.info.pointer_pos = Some(pos);
+    let mut event = self.pointer_event(id, target, tree);
+    tree.bubble_event::<SingleTapListener>(&mut event);
+    self.info.pointer_pos = None;
+  }
+
+  fn mouse_enter_leave_dispatch(&mut self, tree: &mut WidgetTree) {
     let new_hit = self.hit_widget(tree);
+    let mut entered = std::mem::take(&mut self.entered_widgets);
+    self.enter_leave_dispatch(&mut entered, new_hit, tree, |target, tree, info| {
+      PointerEvent::from_mouse(target, tree, info)
+    });
+    self.entered_widgets = entered;
+  }
 
+  fn touch_enter_leave_dispatch(
+    &mut self,
+    idx: usize,
+    new_hit: Option<WidgetId>,
+    tree: &mut WidgetTree,
+  ) {
+    let mut entered = std::mem::take(&mut self.touches[idx].entered_widgets);
+    let TouchPointer { id, force, .. } = self.touches[idx];
+    self.enter_leave_dispatch(&mut entered, new_hit, tree, |target, tree, info| {
+      PointerEvent::from_touch(id, force, idx == 0, target, tree, info)
+    });
+    self.touches[idx].entered_widgets = entered;
+  }
+
+  /// Fire the leave events to the widgets in `entered_widgets` but not the
+  /// ancestors of `new_hit`, and the enter events to the ancestors of `new_hit`
+  /// that not entered, then update the `entered_widgets`.
+  fn enter_leave_dispatch(
+    &self,
+    entered_widgets: &mut Vec<WidgetId>,
+    new_hit: Option<WidgetId>,
+    tree: &WidgetTree,
+    new_event: impl Fn(WidgetId, &WidgetTree, &DispatchInfo) -> PointerEvent,
+  ) {
     let arena = &tree.arena;
     let already_entered_start = new_hit
       .and_then(|new_hit| {
-        self
-          .entered_widgets
+        entered_widgets
           .iter()
           .position(|e| e.ancestors_of(new_hit, arena))
       })
-      .unwrap_or(self.entered_widgets.len());
+      .unwrap_or(entered_widgets.len());
 
     let mut already_entered = vec![];
-    self.entered_widgets[already_entered_start..].clone_into(&mut already_entered);
+    entered_widgets[already_entered_start..].clone_into(&mut already_entered);
 
     // fire leave
-    self.entered_widgets[..already_entered_start]
+    entered_widgets[..already_entered_start]
       .iter()
       .filter(|w| !w.is_dropped(arena))
       .for_each(|l| {
-        let mut event = PointerEvent::from_mouse(*l, tree, &self.info);
+        let mut event = new_event(*l, tree, &self.info);
         l.assert_get(arena).query_all_type(
           |pointer: &PointerLeaveListener| {
             pointer.dispatch(&mut event);
@@ -241,8 +508,8 @@ impl Dispatcher {
         );
       });
 
-    let new_enter_end = self.entered_widgets.get(already_entered_start).cloned();
-    self.entered_widgets.clear();
+    let new_enter_end = entered_widgets.get(already_entered_start).cloned();
+    entered_widgets.clear();
 
     // fire new entered
     if let Some(hit_widget) = new_hit {
@@ -253,17 +520,17 @@ impl Dispatcher {
           if obj.contain_type::<PointerEnterListener>()
             || obj.contain_type::<PointerLeaveListener>()
           {
-            self.entered_widgets.push(w);
+            entered_widgets.push(w);
           }
         } else {
           break;
         }
       }
 
-      self.entered_widgets.iter().rev().for_each(|w| {
+      entered_widgets.iter().rev().for_each(|w| {
         let obj = w.assert_get(arena);
         if obj.contain_type::<PointerEnterListener>() {
-          let mut event = PointerEvent::from_mouse(*w, tree, &self.info);
+          let mut event = new_event(*w, tree, &self.info);
           obj.query_all_type(
             |pointer: &PointerEnterListener| {
               pointer.dispatch(&mut event);
@@ -273,11 +540,11 @@ impl Dispatcher {
           );
         }
       });
-      self.entered_widgets.extend(already_entered);
+      entered_widgets.extend(already_entered);
     }
   }
 
-  fn hit_widget(&self, tree: &WidgetTree) -> Option<WidgetId> {
+  pub(crate) fn hit_widget(&self, tree: &WidgetTree) -> Option<WidgetId> {
     fn down_coordinate(id: WidgetId, pos: Point, tree: &WidgetTree) -> Option<(WidgetId, Point)> {
       let WidgetTree { arena, store, wnd_ctx, .. } = tree;
 
@@ -296,7 +563,7 @@ impl Dispatcher {
       }
     }
 
-    let mut current = down_coordinate(tree.root(), self.info.cursor_pos, tree);
+    let mut current = down_coordinate(tree.root(), self.info.global_pos(), tree);
     let mut hit = current;
     while let Some((id, pos)) = current {
       hit = current;
@@ -307,9 +574,20 @@ impl Dispatcher {
     hit.map(|(w, _)| w)
   }
 
-  fn pointer_event_for_hit_widget(&mut self, tree: &WidgetTree) -> Option<PointerEvent> {
+  /// Return the widget that captured the pointer `id` if it's not dropped.
+  fn captured_widget(&self, id: PointerId, tree: &WidgetTree) -> Option<WidgetId> {
     self
-      .hit_widget(tree)
+      .info
+      .pointer_capture(id)
+      .filter(|w| !w.is_dropped(&tree.arena))
+  }
+
+  /// Create the mouse event for the widget that captured the mouse, or the
+  /// widget under the cursor if the mouse is not captured.
+  fn mouse_event_for_target(&mut self, tree: &WidgetTree) -> Option<PointerEvent> {
+    self
+      .captured_widget(PointerId::MOUSE, tree)
+      .or_else(|| self.hit_widget(tree))
       .map(|target| PointerEvent::from_mouse(target, tree, &self.info))
   }
 }
@@ -325,13 +603,69 @@ impl DispatchInfo {
   pub fn modifiers(&self) -> ModifiersState { self.modifiers }
 
   #[inline]
-  pub fn global_pos(&self) -> Point { self.cursor_pos }
+  pub fn global_pos(&self) -> Point { self.pointer_pos.unwrap_or(self.cursor_pos) }
 
   #[inline]
   pub fn mouse_buttons(&self) -> MouseButtons { self.mouse_button.1 }
+
+  /// Return the widget that captured the pointer `id`.
+  pub fn pointer_capture(&self, id: PointerId) -> Option<WidgetId> {
+    self
+      .pointer_captures
+      .iter()
+      .find(|(p, _)| *p == id)
+      .map(|(_, w)| *w)
+  }
+
+  pub fn set_pointer_capture(&mut self, id: PointerId, wid: WidgetId) {
+    self.remove_pointer_capture(id);
+    self.pointer_captures.push((id, wid));
+  }
+
+  /// Remove the capture of the pointer `id`, return if it's captured.
+  pub fn remove_pointer_capture(&mut self, id: PointerId) -> bool {
+    let len = self.pointer_captures.len();
+    self.pointer_captures.retain(|(p, _)| *p != id);
+    len != self.pointer_captures.len()
+  }
 }
 
 impl WidgetTree {
+  /// Dispatch the event from the root to the target, the capture phase before
+  /// the event bubbles. If the event is stopped in the capture phase, the
+  /// widgets after the current target and the bubble phase will not receive it.
+  pub(crate) fn capture_event<Ty>(&mut self, event: &mut Ty::Event)
+  where
+    Ty: EventListener + 'static,
+  {
+    self.capture_event_with(event, |listener: &Ty, event| listener.dispatch(event));
+  }
+
+  pub(crate) fn capture_event_with<Ty, D, E>(&self, event: &mut E, mut dispatcher: D)
+  where
+    D: FnMut(&Ty, &mut E),
+    E: std::borrow::BorrowMut<EventCommon>,
+    Ty: 'static,
+  {
+    let target = event.borrow().target;
+    let mut path = target.ancestors(&self.arena).collect::<Vec<_>>();
+    path.reverse();
+    for w in path {
+      event.borrow_mut().current_target = w;
+      w.assert_get(&self.arena).query_all_type(
+        |listener: &Ty| {
+          dispatcher(listener, event);
+          !event.borrow().bubbling_canceled()
+        },
+        QueryOrder::OutsideFirst,
+      );
+      if event.borrow().bubbling_canceled() {
+        break;
+      }
+    }
+    event.borrow_mut().current_target = target;
+  }
+
   pub(crate) fn bubble_event<Ty>(&mut self, event: &mut Ty::Event)
   where
     Ty: EventListener + 'static,
@@ -345,6 +679,10 @@ impl WidgetTree {
     E: std::borrow::BorrowMut<EventCommon>,
     Ty: 'static,
   {
+    // The event is stopped in the capture phase.
+    if event.borrow().bubbling_canceled() {
+      return;
+    }
     loop {
       let current_target = event.borrow().current_target;
       current_target.assert_get(&self.arena).query_all_type(
@@ -824,4 +1162,252 @@ mod tests {
 
     assert_eq!(hit, None);
   }
+
+  #[test]
+  fn capture_before_bubble() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let stop_capture = Rc::new(RefCell::new(false));
+    let (r1, r2, r3, r4) = (
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+    );
+    let stop = stop_capture.clone();
+    let w = widget! {
+      MockMulti {
+        on_pointer_down_capture: move |e| {
+          r1.borrow_mut().push("parent capture");
+          if *stop.borrow() {
+            e.stop_bubbling();
+          }
+        },
+        on_pointer_down: move |_| r2.borrow_mut().push("parent bubble"),
+        MockBox {
+          size: Size::new(100., 30.),
+          on_pointer_down_capture: move |_| r3.borrow_mut().push("child capture"),
+          on_pointer_down: move |_| r4.borrow_mut().push("child bubble"),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    let modifiers = ModifiersState::default();
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (1., 1.).into(),
+      modifiers,
+    });
+    let click = |wnd: &mut Window| {
+      for state in [ElementState::Pressed, ElementState::Released] {
+        wnd.processes_native_event(WindowEvent::MouseInput {
+          device_id,
+          state,
+          button: MouseButton::Left,
+          modifiers,
+        });
+      }
+    };
+
+    click(&mut wnd);
+    assert_eq!(
+      &*records.borrow(),
+      &[
+        "parent capture",
+        "child capture",
+        "child bubble",
+        "parent bubble"
+      ]
+    );
+
+    records.borrow_mut().clear();
+    *stop_capture.borrow_mut() = true;
+    click(&mut wnd);
+    assert_eq!(&*records.borrow(), &["parent capture"]);
+  }
+
+  #[test]
+  fn multi_touch_pointers() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3, r4) = (
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+    );
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_down: move |e| r1.borrow_mut().push(
+            ("a down", e.id, e.position(), e.point_type.clone(), e.is_primary)
+          ),
+          on_tap: move |e| r2.borrow_mut().push(
+            ("a tap", e.id, e.position(), e.point_type.clone(), e.is_primary)
+          ),
+        }
+        MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_down: move |e| r3.borrow_mut().push(
+            ("b down", e.id, e.position(), e.point_type.clone(), e.is_primary)
+          ),
+          on_tap: move |e| r4.borrow_mut().push(
+            ("b tap", e.id, e.position(), e.point_type.clone(), e.is_primary)
+          ),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 2, TouchPhase::Started, 60., 20.);
+    touch(&mut wnd, 2, TouchPhase::Ended, 60., 20.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 10., 10.);
+
+    let records = records.borrow();
+    let (first, second) = (records[0].1, records[1].1);
+    assert_ne!(first, second);
+    assert_ne!(first, PointerId::MOUSE);
+    assert_eq!(
+      &*records,
+      &[
+        (
+          "a down",
+          first,
+          Point::new(10., 10.),
+          PointerType::Touch,
+          true
+        ),
+        (
+          "b down",
+          second,
+          Point::new(10., 20.),
+          PointerType::Touch,
+          false
+        ),
+        (
+          "b tap",
+          second,
+          Point::new(10., 20.),
+          PointerType::Touch,
+          false
+        ),
+        (
+          "a tap",
+          first,
+          Point::new(10., 10.),
+          PointerType::Touch,
+          true
+        ),
+      ]
+    );
+    assert!(wnd.dispatcher.touches.is_empty());
+  }
+
+  #[test]
+  fn touch_enter_leave() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2) = (records.clone(), records.clone());
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_enter: move |e| r1.borrow_mut().push(("enter", e.id)),
+          on_pointer_leave: move |e| r2.borrow_mut().push(("leave", e.id)),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 2, TouchPhase::Started, 20., 10.);
+    // the first touch moves out, the second still in.
+    touch(&mut wnd, 1, TouchPhase::Moved, 80., 10.);
+    touch(&mut wnd, 2, TouchPhase::Cancelled, 20., 10.);
+
+    let records = records.borrow();
+    let (first, second) = (records[0].1, records[1].1);
+    assert_eq!(
+      &*records,
+      &[
+        ("enter", first),
+        ("enter", second),
+        ("leave", first),
+        ("leave", second),
+      ]
+    );
+  }
+
+  #[test]
+  fn pointer_capture() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3, r4, r5) = (
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+    );
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_down: move |e| {
+            let id = e.id;
+            e.context().set_pointer_capture(id);
+          },
+          on_pointer_move: move |_| r1.borrow_mut().push("a move"),
+          on_pointer_up: move |_| r2.borrow_mut().push("a up"),
+        }
+        MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_enter: move |_| r3.borrow_mut().push("b enter"),
+          on_pointer_move: move |_| r4.borrow_mut().push("b move"),
+          on_pointer_up: move |_| r5.borrow_mut().push("b up"),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    let modifiers = ModifiersState::default();
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (10., 10.).into(),
+      modifiers,
+    });
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::MouseInput {
+      device_id,
+      state: ElementState::Pressed,
+      button: MouseButton::Left,
+      modifiers,
+    });
+    records.borrow_mut().clear();
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (70., 10.).into(),
+      modifiers,
+    });
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::MouseInput {
+      device_id,
+      state: ElementState::Released,
+      button: MouseButton::Left,
+      modifiers,
+    });
+    // The hover state updates after the capture released.
+    assert_eq!(&*records.borrow(), &["a move", "a up", "b enter"]);
+
+    records.borrow_mut().clear();
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 70., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 70., 10.);
+    assert_eq!(&*records.borrow(), &["a move", "a up"]);
+  }
 }
@@ -0,0 +1,548 @@
+use std::{any::Any, convert::Infallible};
+
+use super::dispatcher::{DispatchInfo, Dispatcher};
+use crate::{
+  animation::AnimateTrack, data_widget::compose_child_as_data_widget,
+  impl_compose_child_for_listener, impl_listener, impl_query_self_only, prelude::*,
+  widget_tree::{paint_subtree, WidgetTree},
+};
+
+/// The opacity of the dragged widget painted following the pointer.
+const DRAG_FEEDBACK_OPACITY: f32 = 0.6;
+
+/// The event of drag and drop, dispatched to the drop target that the dragging
+/// pointer is over.
+#[derive(Debug)]
+pub struct DragEvent {
+  data: Option<Box<dyn Any>>,
+  accepted: bool,
+  pub common: EventCommon,
+}
+
+impl DragEvent {
+  /// The payload of the drag if it's type `T`.
+  pub fn data<T: 'static>(&self) -> Option<&T> { self.data.as_ref().and_then(|d| d.downcast_ref()) }
+
+  /// Take the payload of the drag if it's type `T`, usually in the drop event.
+  pub fn take_data<T: 'static>(&mut self) -> Option<T> {
+    match self.data.take()?.downcast() {
+      Ok(data) => Some(*data),
+      Err(data) => {
+        self.data = Some(data);
+        None
+      }
+    }
+  }
+
+  /// Accept the drag, the drop event is only dispatched to the target that
+  /// accepts the drag.
+  #[inline]
+  pub fn accept(&mut self) { self.accepted = true }
+
+  /// Reject the drag that accepted before.
+  #[inline]
+  pub fn reject(&mut self) { self.accepted = false }
+
+  /// Return if the target accepts the drag.
+  #[inline]
+  pub fn is_accepted(&self) -> bool { self.accepted }
+}
+
+/// Fired when the dragging pointer enters the drop target.
+#[derive(Declare)]
+pub struct DragEnterListener {
+  #[declare(builtin, convert=custom)]
+  on_drag_enter: MutRefItemSubject<'static, DragEvent, Infallible>,
+}
+
+/// Fired when the dragging pointer is over the drop target, bubbles from the
+/// drop target.
+#[derive(Declare)]
+pub struct DragOverListener {
+  #[declare(builtin, convert=custom)]
+  on_drag_over: MutRefItemSubject<'static, DragEvent, Infallible>,
+}
+
+/// Fired when the dragging pointer leaves the drop target, or releases over it
+/// without acceptance.
+#[derive(Declare)]
+pub struct DragLeaveListener {
+  #[declare(builtin, convert=custom)]
+  on_drag_leave: MutRefItemSubject<'static, DragEvent, Infallible>,
+}
+
+/// Fired when the dragging pointer releases over the drop target that accepts
+/// the drag, bubbles from the drop target.
+#[derive(Declare)]
+pub struct DropListener {
+  #[declare(builtin, convert=custom)]
+  on_drop: MutRefItemSubject<'static, DragEvent, Infallible>,
+}
+
+impl_listener!(
+  DragEnterListener,
+  DragEnterListenerDeclarer,
+  on_drag_enter,
+  DragEvent,
+  drag_enter_stream
+);
+impl_compose_child_for_listener!(DragEnterListener);
+
+impl_listener!(
+  DragOverListener,
+  DragOverListenerDeclarer,
+  on_drag_over,
+  DragEvent,
+  drag_over_stream
+);
+impl_compose_child_for_listener!(DragOverListener);
+
+impl_listener!(
+  DragLeaveListener,
+  DragLeaveListenerDeclarer,
+  on_drag_leave,
+  DragEvent,
+  drag_leave_stream
+);
+impl_compose_child_for_listener!(DragLeaveListener);
+
+impl_listener!(
+  DropListener,
+  DropListenerDeclarer,
+  on_drop,
+  DragEvent,
+  drop_stream
+);
+impl_compose_child_for_listener!(DropListener);
+
+/// A drag in progress, from a [`Draggable`] starts to drag to the pointer
+/// released.
+pub(crate) struct DragSession {
+  /// The dragged widget, painted following the pointer as the feedback if
+  /// there isn't a feedback widget.
+  source: WidgetId,
+  /// The layer of the feedback widget provided by the [`Draggable`].
+  feedback: Option<WidgetId>,
+  /// The pointer that drags, the other pointers can't move or drop the drag.
+  pointer: PointerId,
+  data: Option<Box<dyn Any>>,
+  /// Where the pointer grabs the source, in the source coordinate.
+  grab_pos: Point,
+  /// The global position of the dragging pointer.
+  pos: Point,
+  /// The drop target the pointer is over.
+  target: Option<WidgetId>,
+  accepted: bool,
+  released: bool,
+  /// Keep the frames running to paint the feedback and dispatch the drag over.
+  _track: AnimateTrack,
+}
+
+impl WindowCtx {
+  pub(crate) fn start_drag(
+    &self,
+    source: WidgetId,
+    pointer: PointerId,
+    data: Box<dyn Any>,
+    grab_pos: Point,
+    pos: Point,
+  ) {
+    let mut track = self.animate_track();
+    track.set_actived(true);
+    *self.drag.borrow_mut() = Some(DragSession {
+      source,
+      feedback: None,
+      pointer,
+      data: Some(data),
+      grab_pos,
+      pos,
+      target: None,
+      accepted: false,
+      released: false,
+      _track: track,
+    });
+  }
+
+  /// Paint the widget of the `layer` following the pointer instead of the
+  /// dragged widget.
+  pub(crate) fn set_drag_feedback(&self, layer: WidgetId) {
+    if let Some(session) = self.drag.borrow_mut().as_mut() {
+      session.feedback = Some(layer);
+    }
+  }
+
+  /// Release the dragging pointer, the drag drops at the next dispatch.
+  pub(crate) fn release_drag(&self) {
+    if let Some(session) = self.drag.borrow_mut().as_mut() {
+      session.released = true;
+    }
+  }
+}
+
+impl Dispatcher {
+  /// Dispatch the drag events to the drop target that the dragging pointer is
+  /// over, and drop the drag if the pointer is released. The position of the
+  /// dragging pointer is updated if `pointer` provides it, a position of other
+  /// pointers is ignored.
+  pub fn drag_dispatch(&mut self, pointer: Option<(PointerId, Point)>, tree: &mut WidgetTree) {
+    let drag = tree.wnd_ctx.drag.clone();
+    if let Some((id, _)) = pointer {
+      if !drag.borrow().as_ref().is_some_and(|s| s.pointer == id) {
+        return;
+      }
+    }
+    // Take the session out, so the handlers can start a new drag.
+    let Some(mut session) = drag.borrow_mut().take() else {
+      return;
+    };
+    if let Some((_, pos)) = pointer {
+      session.pos = pos;
+    }
+    let pointer_pos = self.info.pointer_pos.replace(session.pos);
+
+    let hit = self.hit_widget(tree);
+    if let Some(hit) = hit.filter(|_| !session.released) {
+      auto_scroll(hit, session.pos, tree);
+    }
+    let arena = &tree.arena;
+    let target = hit.and_then(|hit| hit.ancestors(arena).find(|w| is_drop_target(*w, arena)));
+    if target != session.target {
+      if let Some(old) = session.target.filter(|w| !w.is_dropped(arena)) {
+        target_dispatch::<DragLeaveListener>(old, &mut session, tree, &self.info);
+      }
+      session.accepted = false;
+      session.target = target;
+      if let Some(target) = target {
+        target_dispatch::<DragEnterListener>(target, &mut session, tree, &self.info);
+      }
+    }
+
+    match (target, session.released) {
+      (Some(target), false) => {
+        let mut e = drag_event(target, &mut session, tree, &self.info);
+        tree.bubble_event::<DragOverListener>(&mut e);
+        session.data = e.data.take();
+        session.accepted = e.accepted;
+      }
+      (Some(target), true) if session.accepted => {
+        let mut e = drag_event(target, &mut session, tree, &self.info);
+        tree.bubble_event::<DropListener>(&mut e);
+      }
+      (Some(target), true) => {
+        target_dispatch::<DragLeaveListener>(target, &mut session, tree, &self.info);
+      }
+      (None, _) => {}
+    }
+
+    if !session.released {
+      drag.borrow_mut().get_or_insert(session);
+    }
+    self.info.pointer_pos = pointer_pos;
+  }
+
+  /// Cancel the drag of the `pointer` without dropping it.
+  pub fn cancel_drag(&mut self, pointer: PointerId, tree: &mut WidgetTree) {
+    let drag = tree.wnd_ctx.drag.clone();
+    if !drag.borrow().as_ref().is_some_and(|s| s.pointer == pointer) {
+      return;
+    }
+    let Some(mut session) = drag.borrow_mut().take() else {
+      return;
+    };
+    if let Some(target) = session.target.filter(|w| !w.is_dropped(&tree.arena)) {
+      target_dispatch::<DragLeaveListener>(target, &mut session, tree, &self.info);
+    }
+  }
+}
+
+/// Scroll the scrollable widgets of the hit path that the dragging pointer is
+/// near their edge.
+fn auto_scroll(hit: WidgetId, pos: Point, tree: &WidgetTree) {
+  hit.ancestors(&tree.arena).for_each(|id| {
+    id.assert_get(&tree.arena).query_on_first_type(
+      QueryOrder::OutsideFirst,
+      |s: &Stateful<ScrollableWidget>| {
+        let pos = tree.store.map_from_global(pos, id, &tree.arena);
+        let scroll_pos = s.state_ref().auto_scroll_pos(pos);
+        if scroll_pos != s.state_ref().scroll_pos {
+          s.state_ref().scroll_pos = scroll_pos;
+        }
+      },
+    );
+  });
+}
+
+fn is_drop_target(id: WidgetId, arena: &TreeArena) -> bool {
+  let w = id.assert_get(arena);
+  w.contain_type::<DragEnterListener>()
+    || w.contain_type::<DragOverListener>()
+    || w.contain_type::<DragLeaveListener>()
+    || w.contain_type::<DropListener>()
+}
+
+fn drag_event(
+  target: WidgetId,
+  session: &mut DragSession,
+  tree: &WidgetTree,
+  info: &DispatchInfo,
+) -> DragEvent {
+  DragEvent {
+    data: session.data.take(),
+    accepted: session.accepted,
+    common: EventCommon::new(target, tree, info),
+  }
+}
+
+/// Dispatch the event only to the `target`, not bubble.
+fn target_dispatch<L: EventListener<Event = DragEvent> + 'static>(
+  target: WidgetId,
+  session: &mut DragSession,
+  tree: &WidgetTree,
+  info: &DispatchInfo,
+) {
+  let mut e = drag_event(target, session, tree, info);
+  target.assert_get(&tree.arena).query_all_type(
+    |l: &L| {
+      l.dispatch(&mut e);
+      !e.bubbling_canceled()
+    },
+    QueryOrder::InnerFirst,
+  );
+  session.data = e.data.take();
+  session.accepted = e.accepted;
+}
+
+/// Paint the feedback widget, or the dragged widget if there isn't a feedback,
+/// over the tree where the pointer grabs it.
+pub(crate) fn paint_drag_feedback(ctx: &mut PaintingCtx) {
+  let drag = ctx.wnd_ctx.drag.clone();
+  let drag = drag.borrow();
+  let Some(session) = drag.as_ref() else { return };
+  let arena = ctx.arena;
+  let feedback = session
+    .feedback
+    .filter(|layer| !layer.is_dropped(arena))
+    .and_then(|layer| layer.first_child(arena));
+  let (widget, alpha) = match feedback {
+    Some(feedback) => (feedback, 1.),
+    None => (session.source, DRAG_FEEDBACK_OPACITY),
+  };
+  if widget.is_dropped(arena) {
+    return;
+  }
+  let Some(rect) = ctx.widget_box_rect(widget) else {
+    return;
+  };
+  let offset = session.pos - session.grab_pos - rect.origin.to_vector();
+  let painter = &mut ctx.painter;
+  painter.save();
+  painter.translate(offset.x, offset.y);
+  painter.apply_alpha(alpha);
+  paint_subtree(widget, ctx);
+  ctx.painter.restore();
+}
+
+impl std::borrow::Borrow<EventCommon> for DragEvent {
+  #[inline]
+  fn borrow(&self) -> &EventCommon { &self.common }
+}
+
+impl std::borrow::BorrowMut<EventCommon> for DragEvent {
+  #[inline]
+  fn borrow_mut(&mut self) -> &mut EventCommon { &mut self.common }
+}
+
+impl std::ops::Deref for DragEvent {
+  type Target = EventCommon;
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.common }
+}
+
+impl std::ops::DerefMut for DragEvent {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.common }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{ElementState, TouchPhase};
+
+  fn drag(wnd: &mut Window, from: Point, to: Point) {
+    mouse_move(wnd, from.x, from.y);
+    mouse_input(wnd, ElementState::Pressed);
+    let mid = from.lerp(to, 0.5);
+    mouse_move(wnd, mid.x, mid.y);
+    mouse_move(wnd, to.x, to.y);
+    mouse_input(wnd, ElementState::Released);
+  }
+
+  #[test]
+  fn drag_and_drop() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3, r4) = (
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+    );
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          drag_data: 5,
+        }
+        MockBox {
+          size: Size::new(50., 50.),
+          on_drag_enter: move |e| {
+            r1.borrow_mut().push(format!("enter {:?}", e.data::<i32>()));
+            e.accept();
+          },
+          on_drag_over: move |_| r2.borrow_mut().push("over".to_string()),
+          on_drag_leave: move |_| r3.borrow_mut().push("leave".to_string()),
+          on_drop: move |e| r4.borrow_mut().push(format!("drop {:?}", e.take_data::<i32>())),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    drag(&mut wnd, Point::new(10., 10.), Point::new(70., 10.));
+    assert_eq!(
+      &*records.borrow(),
+      &["enter Some(5)", "over", "drop Some(5)"]
+    );
+    assert!(wnd.context.drag.borrow().is_none());
+    assert!(!wnd.context.has_actived_animate());
+
+    // drop out of the target.
+    records.borrow_mut().clear();
+    drag(&mut wnd, Point::new(10., 10.), Point::new(70., 100.));
+    assert!(records.borrow().is_empty());
+  }
+
+  #[test]
+  fn reject_drag() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2) = (records.clone(), records.clone());
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          drag_data: "text",
+        }
+        MockBox {
+          size: Size::new(50., 50.),
+          on_drag_over: move |e| if e.data::<i32>().is_some() {
+            e.accept();
+          } else {
+            e.reject();
+          },
+          on_drag_leave: move |_| r1.borrow_mut().push("leave"),
+          on_drop: move |_| r2.borrow_mut().push("drop"),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    drag(&mut wnd, Point::new(10., 10.), Point::new(70., 10.));
+    assert_eq!(&*records.borrow(), &["leave"]);
+  }
+
+  #[test]
+  fn only_dragging_pointer_moves_drag() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let r = records.clone();
+    let w = widget! {
+      MockMulti {
+        MockBox {
+          size: Size::new(50., 50.),
+          drag_data: 0,
+        }
+        MockBox {
+          size: Size::new(50., 50.),
+          on_drag_enter: move |e| e.accept(),
+          on_drop: move |_| r.borrow_mut().push("drop"),
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    touch(&mut wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&mut wnd, 1, TouchPhase::Moved, 30., 10.);
+    let drag_pos = |wnd: &Window| wnd.context.drag.borrow().as_ref().map(|s| s.pos);
+    assert_eq!(drag_pos(&wnd), Some(Point::new(30., 10.)));
+
+    // another finger moves and releases over the drop target.
+    touch(&mut wnd, 2, TouchPhase::Started, 60., 10.);
+    touch(&mut wnd, 2, TouchPhase::Moved, 70., 10.);
+    touch(&mut wnd, 2, TouchPhase::Ended, 70., 10.);
+    assert_eq!(drag_pos(&wnd), Some(Point::new(30., 10.)));
+    assert!(records.borrow().is_empty());
+
+    touch(&mut wnd, 1, TouchPhase::Moved, 70., 10.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 70., 10.);
+    assert_eq!(&*records.borrow(), &["drop"]);
+  }
+
+  #[test]
+  fn drag_feedback_follows_pointer() {
+    let w = widget! {
+      MockBox {
+        size: Size::new(50., 50.),
+        drag_data: 0,
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    mouse_move(&mut wnd, 10., 10.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_move(&mut wnd, 30., 20.);
+    assert!(wnd.context.has_actived_animate());
+    {
+      let drag = wnd.context.drag.borrow();
+      let session = drag.as_ref().unwrap();
+      assert_eq!(session.grab_pos, Point::new(10., 10.));
+      assert_eq!(session.pos, Point::new(30., 20.));
+    }
+    mouse_move(&mut wnd, 40., 40.);
+    wnd.draw_frame();
+    assert_eq!(
+      wnd.context.drag.borrow().as_ref().map(|s| s.pos),
+      Some(Point::new(40., 40.))
+    );
+  }
+
+  #[test]
+  fn drag_feedback_widget() {
+    let w = widget! {
+      MockBox {
+        size: Size::new(50., 50.),
+        drag_data: 0,
+        drag_feedback: DragFeedback::new(|| MockBox { size: Size::new(10., 10.) }.into_widget()),
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+    let count = wnd.widget_count();
+
+    mouse_move(&mut wnd, 10., 10.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_move(&mut wnd, 30., 20.);
+    wnd.draw_frame();
+    let feedback = wnd.context.drag.borrow().as_ref().and_then(|s| s.feedback);
+    let feedback = feedback.and_then(|layer| layer.first_child(&wnd.widget_tree.arena));
+    let size = feedback.and_then(|w| wnd.widget_tree.store.layout_box_size(w));
+    assert_eq!(size, Some(Size::new(10., 10.)));
+
+    mouse_input(&mut wnd, ElementState::Released);
+    wnd.draw_frame();
+    assert!(wnd.context.drag.borrow().is_none());
+    assert_eq!(wnd.widget_count(), count);
+  }
+}
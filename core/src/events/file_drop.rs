@@ -0,0 +1,188 @@
+use std::{convert::Infallible, path::PathBuf};
+
+use super::dispatcher::Dispatcher;
+use crate::{
+  data_widget::compose_child_as_data_widget, impl_compose_child_for_listener, impl_listener,
+  impl_query_self_only, prelude::*, widget_tree::WidgetTree,
+};
+
+/// The event of the files dragged from the system into the window, dispatched
+/// to the widget under the cursor and bubbles.
+///
+/// The system reports the files one by one, so the hover and drop events are
+/// fired for every file with its path, and the hover cancel event is fired
+/// once with all the hovered files. The position of the files is the cursor
+/// position, read it by [`EventCommon::position`].
+#[derive(Debug, Clone)]
+pub struct FileDragEvent {
+  pub paths: Vec<PathBuf>,
+  pub common: EventCommon,
+}
+
+/// Fired when a file is dragged over the window.
+#[derive(Declare)]
+pub struct FileHoverListener {
+  #[declare(builtin, convert=custom)]
+  on_file_hover: MutRefItemSubject<'static, FileDragEvent, Infallible>,
+}
+
+/// Fired when the hovered files leave the window without dropping.
+#[derive(Declare)]
+pub struct FileHoverCancelListener {
+  #[declare(builtin, convert=custom)]
+  on_file_hover_cancel: MutRefItemSubject<'static, FileDragEvent, Infallible>,
+}
+
+/// Fired when a file is dropped on the window.
+#[derive(Declare)]
+pub struct FileDropListener {
+  #[declare(builtin, convert=custom)]
+  on_file_drop: MutRefItemSubject<'static, FileDragEvent, Infallible>,
+}
+
+impl_listener!(
+  FileHoverListener,
+  FileHoverListenerDeclarer,
+  on_file_hover,
+  FileDragEvent,
+  file_hover_stream
+);
+impl_compose_child_for_listener!(FileHoverListener);
+
+impl_listener!(
+  FileHoverCancelListener,
+  FileHoverCancelListenerDeclarer,
+  on_file_hover_cancel,
+  FileDragEvent,
+  file_hover_cancel_stream
+);
+impl_compose_child_for_listener!(FileHoverCancelListener);
+
+impl_listener!(
+  FileDropListener,
+  FileDropListenerDeclarer,
+  on_file_drop,
+  FileDragEvent,
+  file_drop_stream
+);
+impl_compose_child_for_listener!(FileDropListener);
+
+/// The files hovering over the window and the widget they are dispatched to.
+#[derive(Default)]
+pub(crate) struct FileHover {
+  target: Option<WidgetId>,
+  paths: Vec<PathBuf>,
+}
+
+impl Dispatcher {
+  pub fn dispatch_hovered_file(&mut self, path: PathBuf, tree: &mut WidgetTree) {
+    let target = self.hit_widget(tree);
+    self.file_hover.target = target;
+    self.file_hover.paths.push(path.clone());
+    if let Some(target) = target {
+      let mut e = FileDragEvent {
+        paths: vec![path],
+        common: EventCommon::new(target, tree, &self.info),
+      };
+      tree.bubble_event::<FileHoverListener>(&mut e);
+    }
+  }
+
+  pub fn dispatch_dropped_file(&mut self, path: PathBuf, tree: &mut WidgetTree) {
+    let hover = &mut self.file_hover;
+    hover.paths.retain(|p| p != &path);
+    if hover.paths.is_empty() {
+      hover.target = None;
+    }
+    if let Some(target) = self.hit_widget(tree) {
+      let mut e = FileDragEvent {
+        paths: vec![path],
+        common: EventCommon::new(target, tree, &self.info),
+      };
+      tree.bubble_event::<FileDropListener>(&mut e);
+    }
+  }
+
+  pub fn dispatch_hovered_file_cancelled(&mut self, tree: &mut WidgetTree) {
+    let FileHover { target, paths } = std::mem::take(&mut self.file_hover);
+    let target = target
+      .filter(|w| !w.is_dropped(&tree.arena))
+      .or_else(|| self.hit_widget(tree));
+    if let Some(target) = target {
+      let mut e = FileDragEvent {
+        paths,
+        common: EventCommon::new(target, tree, &self.info),
+      };
+      tree.bubble_event::<FileHoverCancelListener>(&mut e);
+    }
+  }
+}
+
+impl std::borrow::Borrow<EventCommon> for FileDragEvent {
+  #[inline]
+  fn borrow(&self) -> &EventCommon { &self.common }
+}
+
+impl std::borrow::BorrowMut<EventCommon> for FileDragEvent {
+  #[inline]
+  fn borrow_mut(&mut self) -> &mut EventCommon { &mut self.common }
+}
+
+impl std::ops::Deref for FileDragEvent {
+  type Target = EventCommon;
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.common }
+}
+
+impl std::ops::DerefMut for FileDragEvent {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.common }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{DeviceId, ModifiersState, WindowEvent};
+
+  #[test]
+  fn file_drop_bubble() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3) = (records.clone(), records.clone(), records.clone());
+    let w = widget! {
+      MockMulti {
+        on_file_hover: move |e| r1.borrow_mut().push(("hover", e.paths.clone())),
+        on_file_hover_cancel: move |e| r2.borrow_mut().push(("cancel", e.paths.clone())),
+        on_file_drop: move |e| r3.borrow_mut().push(("drop", e.paths.clone())),
+        MockBox { size: Size::new(100., 100.) }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id: unsafe { DeviceId::dummy() },
+      position: (10., 10.).into(),
+      modifiers: ModifiersState::default(),
+    });
+    let (a, b) = (PathBuf::from("a.csv"), PathBuf::from("b.csv"));
+    wnd.processes_native_event(WindowEvent::HoveredFile(a.clone()));
+    wnd.processes_native_event(WindowEvent::HoveredFile(b.clone()));
+    wnd.processes_native_event(WindowEvent::HoveredFileCancelled);
+    wnd.processes_native_event(WindowEvent::HoveredFile(a.clone()));
+    wnd.processes_native_event(WindowEvent::DroppedFile(a.clone()));
+
+    assert_eq!(
+      &*records.borrow(),
+      &[
+        ("hover", vec![a.clone()]),
+        ("hover", vec![b.clone()]),
+        ("cancel", vec![a.clone(), b]),
+        ("hover", vec![a.clone()]),
+        ("drop", vec![a]),
+      ]
+    );
+    assert!(wnd.dispatcher.file_hover.paths.is_empty());
+  }
+}
@@ -1,22 +1,90 @@
-use crate::{prelude::*, widget_tree::TreeArena};
+use crate::{
+  prelude::*,
+  widget_tree::{LayoutStore, TreeArena},
+};
 
 use indextree::{Arena, NodeId};
+use rxrust::prelude::*;
 use std::{
   cell::RefCell,
   cmp::Ordering,
   collections::{HashMap, HashSet},
+  convert::Infallible,
   rc::Rc,
 };
 
 use super::dispatcher::Dispatcher;
 
-#[derive(Debug)]
 pub(crate) struct FocusManager {
   /// store current focusing node, and its position in tab_orders.
   focusing: Option<WidgetId>,
+  /// If the focus is moved by the keyboard, the focus should be visible.
+  focus_visible: bool,
   node_ids: HashMap<WidgetId, NodeId>,
   arena: Arena<FocusNodeInfo>,
   root: NodeId,
+  focus_changed: Subject<'static, FocusChange, Infallible>,
+  /// The mounted scopes that trap or restore the focus, in the mounted order.
+  modal_scopes: Vec<ModalScope>,
+}
+
+#[derive(Debug)]
+struct ModalScope {
+  wid: WidgetId,
+  trap: bool,
+  /// The widget to restore the focus to when the scope is disposed.
+  restore: Option<WidgetId>,
+}
+
+impl std::fmt::Debug for FocusManager {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("FocusManager")
+      .field("focusing", &self.focusing)
+      .field("focus_visible", &self.focus_visible)
+      .field("node_ids", &self.node_ids)
+      .field("arena", &self.arena)
+      .field("root", &self.root)
+      .field("modal_scopes", &self.modal_scopes)
+      .finish()
+  }
+}
+
+/// Control the focus of the window, request the focus to a widget, blur it and
+/// observe the focus changes.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// let field = Rc::new(Cell::new(None));
+/// let c_field = field.clone();
+/// let w = widget! {
+///   Margin {
+///     margin: EdgeInsets::all(10.),
+///     on_tap: move |e| if let Some(id) = field.get() {
+///       e.context().focus_controller().request_focus(id);
+///     },
+///     Void {
+///       tab_index: 0,
+///       on_mounted: move |ctx| c_field.set(Some(ctx.widget_id())),
+///     }
+///   }
+/// };
+/// ```
+#[derive(Clone)]
+pub struct FocusController {
+  mgr: Rc<RefCell<FocusManager>>,
+}
+
+/// The focus of the window is changed from `old` to `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusChange {
+  pub old: Option<WidgetId>,
+  pub new: Option<WidgetId>,
+  /// If the new focus is moved by the keyboard, see
+  /// [`FocusController::focus_visible`].
+  pub visible: bool,
 }
 
 pub struct FocustHandle {
@@ -34,6 +102,31 @@ impl FocustHandle {
   }
 }
 
+impl FocusController {
+  /// Request to move the focus to the focusable widget `wid`, the focus
+  /// changes before the next frame is painted.
+  pub fn request_focus(&self, wid: WidgetId) { self.mgr.borrow_mut().focus_to(Some(wid)); }
+
+  /// Remove the focus from the focused widget.
+  pub fn blur(&self) { self.mgr.borrow_mut().focus_to(None); }
+
+  /// Return the focused widget.
+  pub fn focusing(&self) -> Option<WidgetId> { self.mgr.borrow().focusing }
+
+  /// Return if the focus should be visible, it's true if the focus is moved by
+  /// the keyboard and false if by the pointer. The themes draw the focus ring
+  /// only if it's true.
+  pub fn focus_visible(&self) -> bool {
+    let mgr = self.mgr.borrow();
+    mgr.focusing.is_some() && mgr.focus_visible
+  }
+
+  /// Return an observable stream of the focus changes.
+  pub fn focus_changed_stream(&self) -> Subject<'static, FocusChange, Infallible> {
+    self.mgr.borrow().focus_changed.clone()
+  }
+}
+
 impl Default for FocusManager {
   fn default() -> Self {
     let mut arena = Arena::new();
@@ -43,9 +136,12 @@ impl Default for FocusManager {
     });
     Self {
       focusing: None,
+      focus_visible: false,
       node_ids: HashMap::<WidgetId, NodeId>::new(),
       arena,
       root,
+      focus_changed: <_>::default(),
+      modal_scopes: vec![],
     }
   }
 }
@@ -58,6 +154,15 @@ bitflags! {
   }
 }
 
+/// The direction to move the focus in the space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+  Up,
+  Down,
+  Left,
+  Right,
+}
+
 #[derive(Debug)]
 pub(crate) struct FocusNodeInfo {
   pub focus_type: FocusType,
@@ -90,6 +195,19 @@ impl FocusManager {
       self.insert_node(*parent, node_id, wid, arena);
     }
 
+    if focus_type == FocusType::SCOPE {
+      let scope = self.focus_scope_node(Some(wid), arena);
+      if scope.trap || scope.restore_focus {
+        let restore = self.focusing.filter(|_| scope.restore_focus);
+        self
+          .modal_scopes
+          .push(ModalScope { wid, trap: scope.trap, restore });
+        if scope.trap && self.focusing.is_some_and(|f| !self.is_inside(f, wid)) {
+          self.focusing = None;
+        }
+      }
+    }
+
     if focus_type == FocusType::NODE && self.focusing.is_none() && auto_focus {
       self.focusing = Some(wid);
     }
@@ -99,10 +217,29 @@ impl FocusManager {
     FocustHandle { mgr: this.clone(), wid }
   }
 
+  pub(crate) fn controller(this: &Rc<RefCell<Self>>) -> FocusController {
+    FocusController { mgr: this.clone() }
+  }
+
   pub(crate) fn remove_focus_node(&mut self, wid: WidgetId, focus_type: FocusType) {
     if Some(wid) == self.focusing && focus_type.intersects(FocusType::NODE) {
       self.focusing = None;
     }
+    if focus_type.intersects(FocusType::SCOPE) {
+      if let Some(idx) = self.modal_scopes.iter().position(|s| s.wid == wid) {
+        let scope = self.modal_scopes.remove(idx);
+        let focus_in = match self.focusing {
+          Some(focusing) => self.is_inside(focusing, wid),
+          None => true,
+        };
+        if let Some(restore) = scope
+          .restore
+          .filter(|w| focus_in && self.node_ids.contains_key(w))
+        {
+          self.focusing = Some(restore);
+        }
+      }
+    }
     if let Some(id) = self.node_ids.get(&wid) {
       let node = self.arena[*id].get_mut();
       assert!(node.focus_type.intersects(focus_type));
@@ -113,7 +250,35 @@ impl FocusManager {
     }
   }
 
-  pub fn focus_to(&mut self, wid: Option<WidgetId>) { self.focusing = wid; }
+  pub fn focus_to(&mut self, wid: Option<WidgetId>) {
+    if !wid.is_some_and(|wid| self.is_trapped_out(wid)) {
+      self.focusing = wid;
+    }
+  }
+
+  /// Return if the focus of `wid` is forbidden by the active trap scope.
+  pub(crate) fn is_trapped_out(&self, wid: WidgetId) -> bool {
+    self
+      .active_trap()
+      .is_some_and(|trap| !self.is_inside(wid, trap))
+  }
+
+  fn active_trap(&self) -> Option<WidgetId> {
+    self
+      .modal_scopes
+      .iter()
+      .rev()
+      .find(|s| s.trap)
+      .map(|s| s.wid)
+  }
+
+  /// Return if the focus node `wid` is the scope `scope` or in it.
+  fn is_inside(&self, wid: WidgetId, scope: WidgetId) -> bool {
+    match (self.node_ids.get(&wid), self.node_ids.get(&scope)) {
+      (Some(node), Some(scope)) => node.ancestors(&self.arena).any(|n| n == *scope),
+      _ => false,
+    }
+  }
 
   pub(crate) fn next_focus(&mut self, arena: &TreeArena) -> Option<WidgetId> {
     self.focus_move_circle(false, arena)
@@ -133,19 +298,114 @@ impl FocusManager {
     wid
   }
 
+  /// Move the focus to the nearest focusable widget in the `dir` from the
+  /// focusing widget, within its nearest focus scope that opts in the
+  /// directional navigation. The focus keeps if no widget in the direction.
+  pub(crate) fn focus_in_direction(
+    &mut self,
+    dir: FocusDirection,
+    arena: &TreeArena,
+    store: &LayoutStore,
+  ) -> Option<WidgetId> {
+    let focusing = self.focusing?;
+    let node_id = *self.node_ids.get(&focusing)?;
+    let scope_id = node_id
+      .ancestors(&self.arena)
+      .skip(1)
+      .find(|id| self.assert_get(*id).focus_type.intersects(FocusType::SCOPE))?;
+    let scope = self.focus_scope_node(self.assert_get(scope_id).wid, arena);
+    if !scope.directional {
+      return None;
+    }
+
+    let global_rect = |wid: WidgetId| {
+      let size = store.layout_box_size(wid)?;
+      let pos = store.map_to_global(Point::zero(), wid, arena);
+      Some(dir.oriented(Rect::new(pos, size)))
+    };
+    let current = global_rect(focusing)?;
+    let mut nodes = vec![];
+    self.collect_focus_nodes_in_scope(scope_id, arena, &mut nodes);
+    let candidates = nodes
+      .into_iter()
+      .filter_map(|id| self.get(id).and_then(|n| n.wid))
+      .filter(|wid| *wid != focusing)
+      .filter_map(|wid| global_rect(wid).map(|rect| (wid, rect)));
+
+    let center = |r: &Rect| r.center();
+    // The gap between the two rects in the minor axis, zero if they are in the
+    // same row or column.
+    let minor_gap = |r: &Rect| (r.min_y() - current.max_y()).max(current.min_y() - r.max_y());
+    let minor_offset = |r: &Rect| (center(r).y - center(&current).y).abs();
+    let ahead = candidates
+      .clone()
+      .filter(|(_, r)| center(r).x > center(&current).x && r.max_x() > current.max_x())
+      .map(|(wid, r)| {
+        let gap = minor_gap(&r).max(0.);
+        let distance = (r.min_x() - current.max_x()).max(0.) + gap * 2.;
+        (wid, (gap > 0., distance, minor_offset(&r)))
+      })
+      .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let next = ahead.map(|(wid, _)| wid).or_else(|| {
+      scope
+        .wrap
+        .then(|| {
+          candidates
+            .filter(|(_, r)| minor_gap(r) <= 0. && center(r).x < center(&current).x)
+            .map(|(wid, r)| (wid, (r.min_x(), minor_offset(&r))))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(wid, _)| wid)
+        })
+        .flatten()
+    })?;
+    self.focusing = Some(next);
+    Some(next)
+  }
+
+  fn collect_focus_nodes_in_scope(
+    &self,
+    scope_id: NodeId,
+    arena: &TreeArena,
+    nodes: &mut Vec<NodeId>,
+  ) {
+    self
+      .collect_tab_index_in_scope(scope_id, false, arena)
+      .into_iter()
+      .for_each(|(_, id, focus_type)| {
+        if focus_type == FocusType::SCOPE {
+          self.collect_focus_nodes_in_scope(id, arena, nodes);
+        } else {
+          nodes.push(id);
+        }
+      });
+  }
+
   fn focus_step(
     &mut self,
     focusing: Option<WidgetId>,
     backward: bool,
     arena: &TreeArena,
   ) -> Option<WidgetId> {
+    let trap = self
+      .active_trap()
+      .and_then(|w| self.node_ids.get(&w))
+      .copied();
+    let focusing = focusing.filter(|f| !self.is_trapped_out(*f));
     let mut node_id = focusing.and_then(|id| self.node_ids.get(&id)).copied();
-    let mut scope_id = node_id.and_then(|id| self.scope_id(id)).or(Some(self.root));
+    let mut scope_id = node_id
+      .and_then(|id| self.scope_id(id))
+      .or(trap)
+      .or(Some(self.root));
     loop {
       scope_id?;
       let next = self.focus_step_in_scope(scope_id.unwrap(), node_id, backward, arena);
       if let Some(id) = next {
         return self.get(id).and_then(|n| n.wid);
+      } else if scope_id == trap {
+        // Cycle in the trap scope.
+        node_id?;
+        node_id = None;
       } else {
         node_id = scope_id;
         scope_id = self.scope_id(node_id.unwrap());
@@ -419,11 +679,26 @@ impl FocusManager {
 
 impl Dispatcher {
   pub fn next_focus_widget(&mut self, tree: &WidgetTree) {
-    self.focus_mgr.borrow_mut().next_focus(&tree.arena);
+    let mut mgr = self.focus_mgr.borrow_mut();
+    mgr.focus_visible = true;
+    mgr.next_focus(&tree.arena);
   }
 
   pub fn prev_focus_widget(&mut self, tree: &WidgetTree) {
-    self.focus_mgr.borrow_mut().prev_focus(&tree.arena);
+    let mut mgr = self.focus_mgr.borrow_mut();
+    mgr.focus_visible = true;
+    mgr.prev_focus(&tree.arena);
+  }
+
+  pub fn directional_focus_widget(&mut self, dir: FocusDirection, tree: &WidgetTree) {
+    let mut mgr = self.focus_mgr.borrow_mut();
+    mgr.focus_visible = true;
+    mgr.focus_in_direction(dir, &tree.arena, &tree.store);
+  }
+
+  /// Mark the focus is moved by the pointer or the keyboard.
+  pub(crate) fn set_focus_visible(&mut self, visible: bool) {
+    self.focus_mgr.borrow_mut().focus_visible = visible;
   }
 
   /// Removes keyboard focus from the current focusing widget and return its id.
@@ -434,6 +709,11 @@ impl Dispatcher {
   /// return the focusing widget.
   pub fn focusing(&self) -> Option<WidgetId> { self.focus_mgr.borrow_mut().focusing }
 
+  /// Return if the focus is requested to change but not applied yet.
+  pub(crate) fn is_focus_changing(&self) -> bool {
+    self.focus_widgets.first() != self.focus_mgr.borrow().focusing.as_ref()
+  }
+
   pub fn refresh_focus(&mut self, tree: &WidgetTree) {
     let focusing = self.focus_mgr.borrow().focusing.filter(|node_id| {
       self
@@ -509,11 +789,32 @@ impl Dispatcher {
     }
 
     self.focus_widgets = new_widgets;
-    focus_mgr.borrow_mut().focusing = node;
+    let (mut changed, visible) = {
+      let mut mgr = focus_mgr.borrow_mut();
+      mgr.focusing = node;
+      (mgr.focus_changed.clone(), mgr.focus_visible)
+    };
+    changed.next(FocusChange { old, new: node, visible });
     old
   }
 }
 
+impl FocusDirection {
+  /// Map the `rect` to a space that the direction is the positive x-axis.
+  fn oriented(self, rect: Rect) -> Rect {
+    let Rect { origin: Point { x, y, .. }, size } = rect;
+    match self {
+      FocusDirection::Right => rect,
+      FocusDirection::Left => Rect::new(Point::new(-x - size.width, y), size),
+      FocusDirection::Down => Rect::new(Point::new(y, x), Size::new(size.height, size.width)),
+      FocusDirection::Up => Rect::new(
+        Point::new(-y - size.height, x),
+        Size::new(size.height, size.width),
+      ),
+    }
+  }
+}
+
 fn common_ancestors(path: &[WidgetId], path2: &[WidgetId]) -> HashSet<WidgetId> {
   let it = path
     .iter()
@@ -687,4 +988,78 @@ mod tests {
     dispatcher.blur(tree);
     assert_eq!(&*log.borrow(), &["blur parent", "focusout parent",]);
   }
+
+  #[test]
+  fn focus_controller() {
+    use winit::event::{DeviceId, ElementState, KeyboardInput, MouseButton, WindowEvent};
+
+    let visible = Stateful::new(vec![]);
+    let size = Size::new(10., 10.);
+    let w = widget! {
+      states { visible: visible.clone() }
+      MockMulti {
+        MockBox { id: first, size, tab_index: 0 }
+        MockBox { id: second, size, tab_index: 0 }
+      }
+      finally {
+        let_watch!(first.focus_visible() || second.focus_visible())
+          .distinct_until_changed()
+          .subscribe(move |v| visible.push(v));
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+    let tree = &wnd.widget_tree;
+    let first = tree.root().first_child(&tree.arena).unwrap();
+    let controller = tree.wnd_ctx.focus_controller();
+    let changes = Rc::new(RefCell::new(vec![]));
+    let c_changes = changes.clone();
+    controller
+      .focus_changed_stream()
+      .subscribe(move |c: FocusChange| c_changes.borrow_mut().push((c.new, c.visible)));
+
+    controller.request_focus(first);
+    wnd.draw_frame();
+    assert_eq!(controller.focusing(), Some(first));
+
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::KeyboardInput {
+      device_id: unsafe { DeviceId::dummy() },
+      input: KeyboardInput {
+        scancode: 0,
+        virtual_keycode: Some(VirtualKeyCode::Tab),
+        state: ElementState::Pressed,
+        modifiers: ModifiersState::default(),
+      },
+      is_synthetic: false,
+    });
+    wnd.draw_frame();
+    let second = controller.focusing();
+    assert!(controller.focus_visible());
+
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::MouseInput {
+      device_id: unsafe { DeviceId::dummy() },
+      state: ElementState::Pressed,
+      button: MouseButton::Left,
+      modifiers: ModifiersState::default(),
+    });
+    wnd.draw_frame();
+    assert!(!controller.focus_visible());
+
+    controller.blur();
+    wnd.draw_frame();
+    assert_eq!(controller.focusing(), None);
+
+    assert_eq!(
+      &*changes.borrow(),
+      &[
+        (Some(first), false),
+        (second, true),
+        (Some(first), false),
+        (None, false)
+      ]
+    );
+    assert_eq!(&*visible.state_ref(), &[false, true, false]);
+  }
 }
@@ -0,0 +1,724 @@
+use std::{
+  convert::Infallible,
+  time::{Duration, Instant},
+};
+
+use super::{dispatcher::DispatchInfo, pointers::MULTI_TAP_DURATION};
+use crate::{
+  animation::AnimateTrack, data_widget::compose_child_as_data_widget,
+  impl_compose_child_for_listener, impl_listener, impl_query_self_only, prelude::*,
+  widget_tree::WidgetTree,
+};
+
+/// The distance a pressed pointer can move before it's treated as a pan.
+const PAN_SLOP: f32 = 8.;
+/// The duration a pointer keeps pressed to trigger the long press.
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// The minimum velocity, in pixels per second, of a pan end to trigger the
+/// swipe.
+const SWIPE_VELOCITY: f32 = 500.;
+/// The velocity of the pointer is treated as zero if it not moves in this
+/// duration.
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The event of the pan gesture, fired when a pressed pointer moves.
+#[derive(Debug, Clone)]
+pub struct PanEvent {
+  /// The id of the panning pointer.
+  pub pointer_id: PointerId,
+  /// The global position of the pointer.
+  pub pointer_pos: Point,
+  /// The movement of the pointer since the last pan event.
+  pub delta: Vector,
+  /// The movement of the pointer since it's pressed.
+  pub offset: Vector,
+  /// The velocity of the pointer, in pixels per second.
+  pub velocity: Vector,
+  pub common: EventCommon,
+}
+
+/// The event of the scale gesture, fired when two pressed pointers move.
+///
+/// There is no standalone rotate gesture, the rotation of the two pointers
+/// comes with the scale events in `rotation`, so listen to the scale events to
+/// rotate.
+#[derive(Debug, Clone)]
+pub struct ScaleEvent {
+  /// The global position of the center of the two pointers.
+  pub focal_pos: Point,
+  /// The distance between the two pointers relative to the distance when the
+  /// gesture starts.
+  pub scale: f32,
+  /// The clockwise rotation of the line between the two pointers since the
+  /// gesture starts.
+  pub rotation: Angle,
+  pub common: EventCommon,
+}
+
+/// Fired when a pressed pointer starts to move over the slop.
+#[derive(Declare)]
+pub struct PanStartListener {
+  #[declare(builtin, convert=custom)]
+  on_pan_start: MutRefItemSubject<'static, PanEvent, Infallible>,
+}
+
+/// Fired every time the panning pointer moves.
+#[derive(Declare)]
+pub struct PanUpdateListener {
+  #[declare(builtin, convert=custom)]
+  on_pan_update: MutRefItemSubject<'static, PanEvent, Infallible>,
+}
+
+/// Fired when the panning pointer is released.
+#[derive(Declare)]
+pub struct PanEndListener {
+  #[declare(builtin, convert=custom)]
+  on_pan_end: MutRefItemSubject<'static, PanEvent, Infallible>,
+}
+
+/// Fired when the panning pointer is released at a high velocity.
+#[derive(Declare)]
+pub struct SwipeListener {
+  #[declare(builtin, convert=custom)]
+  on_swipe: MutRefItemSubject<'static, PanEvent, Infallible>,
+}
+
+/// Fired when a pointer keeps pressed without moving for a while.
+#[derive(Declare)]
+pub struct LongPressListener {
+  #[declare(builtin, convert=custom)]
+  on_long_press: MutRefItemSubject<'static, PointerEvent, Infallible>,
+}
+
+/// Fired when a tap is not followed by a second tap in the multi tap duration,
+/// the tap is held until the duration expired.
+#[derive(Declare)]
+pub struct SingleTapListener {
+  #[declare(builtin, convert=custom)]
+  on_single_tap: MutRefItemSubject<'static, PointerEvent, Infallible>,
+}
+
+/// Fired when the second pointer is pressed.
+#[derive(Declare)]
+pub struct ScaleStartListener {
+  #[declare(builtin, convert=custom)]
+  on_scale_start: MutRefItemSubject<'static, ScaleEvent, Infallible>,
+}
+
+/// Fired every time one of the two scaling pointers moves.
+#[derive(Declare)]
+pub struct ScaleUpdateListener {
+  #[declare(builtin, convert=custom)]
+  on_scale_update: MutRefItemSubject<'static, ScaleEvent, Infallible>,
+}
+
+/// Fired when one of the two scaling pointers is released.
+#[derive(Declare)]
+pub struct ScaleEndListener {
+  #[declare(builtin, convert=custom)]
+  on_scale_end: MutRefItemSubject<'static, ScaleEvent, Infallible>,
+}
+
+impl_listener!(
+  PanStartListener,
+  PanStartListenerDeclarer,
+  on_pan_start,
+  PanEvent,
+  pan_start_stream
+);
+impl_compose_child_for_listener!(PanStartListener);
+
+impl_listener!(
+  PanUpdateListener,
+  PanUpdateListenerDeclarer,
+  on_pan_update,
+  PanEvent,
+  pan_update_stream
+);
+impl_compose_child_for_listener!(PanUpdateListener);
+
+impl_listener!(
+  PanEndListener,
+  PanEndListenerDeclarer,
+  on_pan_end,
+  PanEvent,
+  pan_end_stream
+);
+impl_compose_child_for_listener!(PanEndListener);
+
+impl_listener!(
+  SwipeListener,
+  SwipeListenerDeclarer,
+  on_swipe,
+  PanEvent,
+  swipe_stream
+);
+impl_compose_child_for_listener!(SwipeListener);
+
+impl_listener!(
+  LongPressListener,
+  LongPressListenerDeclarer,
+  on_long_press,
+  PointerEvent,
+  long_press_stream
+);
+impl_compose_child_for_listener!(LongPressListener);
+
+impl_listener!(
+  SingleTapListener,
+  SingleTapListenerDeclarer,
+  on_single_tap,
+  PointerEvent,
+  single_tap_stream
+);
+impl_compose_child_for_listener!(SingleTapListener);
+
+impl_listener!(
+  ScaleStartListener,
+  ScaleStartListenerDeclarer,
+  on_scale_start,
+  ScaleEvent,
+  scale_start_stream
+);
+impl_compose_child_for_listener!(ScaleStartListener);
+
+impl_listener!(
+  ScaleUpdateListener,
+  ScaleUpdateListenerDeclarer,
+  on_scale_update,
+  ScaleEvent,
+  scale_update_stream
+);
+impl_compose_child_for_listener!(ScaleUpdateListener);
+
+impl_listener!(
+  ScaleEndListener,
+  ScaleEndListenerDeclarer,
+  on_scale_end,
+  ScaleEvent,
+  scale_end_stream
+);
+impl_compose_child_for_listener!(ScaleEndListener);
+
+/// The arena resolves the competing gestures of a pointer sequence, from the
+/// first pointer pressed to all the pointers released.
+///
+/// The gestures are only recognized when the target or its ancestors listen to
+/// them. The first recognized gesture wins the arena, and the others are
+/// rejected until the sequence ends, except the scale gesture that takes over
+/// the pan gesture when the second pointer pressed. The tap is rejected if any
+/// gesture wins the arena.
+///
+/// The double tap gesture holds the tap of the widget that listens to the
+/// single tap, and releases it as a single tap if no second tap comes in the
+/// multi tap duration.
+#[derive(Default)]
+pub(crate) struct GestureArena {
+  pointers: Vec<PointerTrack>,
+  winner: Option<Gesture>,
+  /// Keep the frames running to check the long press when it's possible.
+  long_press: Option<AnimateTrack>,
+  double_tap: Option<DoubleTap>,
+}
+
+/// The first tap of a possible double tap.
+struct DoubleTap {
+  id: PointerId,
+  target: WidgetId,
+  pos: Point,
+  tapped_at: Instant,
+  /// Keep the frames running to release the single tap when it expired.
+  _track: AnimateTrack,
+}
+
+struct PointerTrack {
+  id: PointerId,
+  target: WidgetId,
+  down_pos: Point,
+  down_at: Instant,
+  pos: Point,
+  moved_at: Instant,
+  velocity: Vector,
+  over_slop: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Gesture {
+  Pan,
+  LongPress,
+  Scale {
+    distance: f32,
+    angle: Angle,
+  },
+  /// The gesture ended, but some pointers of the sequence are still pressed.
+  Ended,
+}
+
+impl GestureArena {
+  pub(crate) fn pointer_down(
+    &mut self,
+    id: PointerId,
+    target: WidgetId,
+    pos: Point,
+    tree: &mut WidgetTree,
+    info: &DispatchInfo,
+  ) {
+    let now = tree.wnd_ctx.now();
+    self.pointers.push(PointerTrack {
+      id,
+      target,
+      down_pos: pos,
+      down_at: now,
+      pos,
+      moved_at: now,
+      velocity: Vector::zero(),
+      over_slop: false,
+    });
+    let target = self.pointers[0].target;
+
+    if self.pointers.len() == 1 {
+      if listened::<LongPressListener>(target, tree) {
+        let mut track = tree.wnd_ctx.animate_track();
+        track.set_actived(true);
+        self.long_press = Some(track);
+      }
+    } else if self.pointers.len() == 2
+      && matches!(self.winner, None | Some(Gesture::Pan))
+      && (listened::<ScaleStartListener>(target, tree)
+        || listened::<ScaleUpdateListener>(target, tree)
+        || listened::<ScaleEndListener>(target, tree))
+    {
+      if matches!(self.winner, Some(Gesture::Pan)) {
+        let mut e = self.pan_event(0, target, tree, info);
+        tree.bubble_event::<PanEndListener>(&mut e);
+      }
+      self.long_press = None;
+      let (distance, angle) = self.pointers_line();
+      self.winner = Some(Gesture::Scale { distance, angle });
+      let mut e = self.scale_event(target, tree, info);
+      tree.bubble_event::<ScaleStartListener>(&mut e);
+    }
+  }
+
+  pub(crate) fn pointer_move(
+    &mut self,
+    id: PointerId,
+    pos: Point,
+    tree: &mut WidgetTree,
+    info: &DispatchInfo,
+  ) {
+    let Some(idx) = self.pointers.iter().position(|p| p.id == id) else {
+      return;
+    };
+    let now = tree.wnd_ctx.now();
+    let p = &mut self.pointers[idx];
+    let dur = now.saturating_duration_since(p.moved_at).as_secs_f32();
+    if dur > 0. {
+      p.velocity = (pos - p.pos) / dur;
+      p.moved_at = now;
+    }
+    let delta = pos - p.pos;
+    p.pos = pos;
+    if (pos - p.down_pos).length() > PAN_SLOP {
+      p.over_slop = true;
+    }
+    let over_slop = p.over_slop;
+    let target = self.pointers[0].target;
+
+    match self.winner {
+      None if over_slop && self.pointers.len() == 1 => {
+        self.long_press = None;
+        if listened::<PanStartListener>(target, tree)
+          || listened::<PanUpdateListener>(target, tree)
+          || listened::<PanEndListener>(target, tree)
+          || listened::<SwipeListener>(target, tree)
+        {
+          self.winner = Some(Gesture::Pan);
+          let mut e = self.pan_event(idx, target, tree, info);
+          e.delta = e.offset;
+          tree.bubble_event::<PanStartListener>(&mut e);
+        }
+      }
+      Some(Gesture::Pan) if idx == 0 => {
+        let mut e = self.pan_event(idx, target, tree, info);
+        e.delta = delta;
+        tree.bubble_event::<PanUpdateListener>(&mut e);
+      }
+      Some(Gesture::Scale { .. }) if idx < 2 => {
+        let mut e = self.scale_event(target, tree, info);
+        tree.bubble_event::<ScaleUpdateListener>(&mut e);
+      }
+      _ => {}
+    }
+  }
+
+  /// Release the pointer, return if the pointer sequence is claimed by a
+  /// gesture.
+  pub(crate) fn pointer_up(
+    &mut self,
+    id: PointerId,
+    tree: &mut WidgetTree,
+    info: &DispatchInfo,
+  ) -> bool {
+    let Some(idx) = self.pointers.iter().position(|p| p.id == id) else {
+      return false;
+    };
+    let target = self.pointers[0].target;
+    match self.winner {
+      Some(Gesture::Pan) if idx == 0 => {
+        let mut e = self.pan_event(idx, target, tree, info);
+        let now = tree.wnd_ctx.now();
+        if now.saturating_duration_since(self.pointers[idx].moved_at) > VELOCITY_TIMEOUT {
+          e.velocity = Vector::zero();
+        }
+        tree.bubble_event::<PanEndListener>(&mut e);
+        if e.velocity.length() >= SWIPE_VELOCITY {
+          let mut e = self.pan_event(idx, target, tree, info);
+          tree.bubble_event::<SwipeListener>(&mut e);
+        }
+      }
+      Some(Gesture::Scale { .. }) if idx < 2 => {
+        let mut e = self.scale_event(target, tree, info);
+        tree.bubble_event::<ScaleEndListener>(&mut e);
+      }
+      _ => {}
+    }
+
+    let claimed = self.winner.is_some();
+    if matches!(self.winner, Some(Gesture::Pan)) && idx == 0
+      || matches!(self.winner, Some(Gesture::Scale { .. })) && idx < 2
+    {
+      self.winner = Some(Gesture::Ended);
+    }
+    self.pointers.remove(idx);
+    self.long_press = None;
+    if self.pointers.is_empty() {
+      self.winner = None;
+    }
+    claimed
+  }
+
+  /// The pointer `id` taps on `target`. The tap is held if the target listens
+  /// to the single tap, and the second tap on the same target in time makes it
+  /// a double tap. Return the held single tap that's released by this tap.
+  pub(crate) fn tap(
+    &mut self,
+    id: PointerId,
+    target: WidgetId,
+    pos: Point,
+    tree: &WidgetTree,
+  ) -> Option<(PointerId, WidgetId, Point)> {
+    let now = tree.wnd_ctx.now();
+    let released = self.check_single_tap(now);
+    match self.double_tap.take() {
+      // The second tap, the held tap is not a single tap.
+      Some(first) if first.target == target => None,
+      first => {
+        if listened::<SingleTapListener>(target, tree) {
+          let mut track = tree.wnd_ctx.animate_track();
+          track.set_actived(true);
+          self.double_tap = Some(DoubleTap {
+            id,
+            target,
+            pos,
+            tapped_at: now,
+            _track: track,
+          });
+        }
+        released.or_else(|| first.map(|f| (f.id, f.target, f.pos)))
+      }
+    }
+  }
+
+  /// Release the held tap as a single tap if no second tap comes in time,
+  /// return the pointer id, the target and the position of the single tap.
+  pub(crate) fn check_single_tap(&mut self, now: Instant) -> Option<(PointerId, WidgetId, Point)> {
+    let first = self.double_tap.as_ref()?;
+    if now.saturating_duration_since(first.tapped_at) > MULTI_TAP_DURATION {
+      let first = self.double_tap.take()?;
+      Some((first.id, first.target, first.pos))
+    } else {
+      None
+    }
+  }
+
+  /// Check if the pressed pointer triggers the long press, return the pointer
+  /// id, the target and the position of the long press.
+  pub(crate) fn check_long_press(&mut self, now: Instant) -> Option<(PointerId, WidgetId, Point)> {
+    if self.long_press.is_none() || self.winner.is_some() {
+      return None;
+    }
+    let [p] = &self.pointers[..] else { return None };
+    if !p.over_slop && now.saturating_duration_since(p.down_at) >= LONG_PRESS_DURATION {
+      self.long_press = None;
+      self.winner = Some(Gesture::LongPress);
+      Some((p.id, p.target, p.pos))
+    } else {
+      None
+    }
+  }
+
+  fn pan_event(
+    &self,
+    idx: usize,
+    target: WidgetId,
+    tree: &WidgetTree,
+    info: &DispatchInfo,
+  ) -> PanEvent {
+    let p = &self.pointers[idx];
+    PanEvent {
+      pointer_id: p.id,
+      pointer_pos: p.pos,
+      delta: Vector::zero(),
+      offset: p.pos - p.down_pos,
+      velocity: p.velocity,
+      common: EventCommon::new(target, tree, info),
+    }
+  }
+
+  fn scale_event(&self, target: WidgetId, tree: &WidgetTree, info: &DispatchInfo) -> ScaleEvent {
+    let (distance, angle) = self.pointers_line();
+    let (scale, rotation) = match self.winner {
+      Some(Gesture::Scale { distance: d, angle: a }) if d > 0. => (distance / d, angle - a),
+      _ => (1., Angle::zero()),
+    };
+    ScaleEvent {
+      focal_pos: self.pointers[0].pos.lerp(self.pointers[1].pos, 0.5),
+      scale,
+      rotation,
+      common: EventCommon::new(target, tree, info),
+    }
+  }
+
+  /// The length and the angle of the line between the first two pointers.
+  fn pointers_line(&self) -> (f32, Angle) {
+    let line = self.pointers[1].pos - self.pointers[0].pos;
+    (line.length(), line.angle_from_x_axis())
+  }
+}
+
+fn listened<T: 'static>(target: WidgetId, tree: &WidgetTree) -> bool {
+  target
+    .ancestors(&tree.arena)
+    .any(|w| w.assert_get(&tree.arena).contain_type::<T>())
+}
+
+macro_rules! impl_common_event {
+  ($($event_ty: ident),*) => {
+    $(
+      impl std::borrow::Borrow<EventCommon> for $event_ty {
+        #[inline]
+        fn borrow(&self) -> &EventCommon { &self.common }
+      }
+
+      impl std::borrow::BorrowMut<EventCommon> for $event_ty {
+        #[inline]
+        fn borrow_mut(&mut self) -> &mut EventCommon { &mut self.common }
+      }
+
+      impl std::ops::Deref for $event_ty {
+        type Target = EventCommon;
+        #[inline]
+        fn deref(&self) -> &Self::Target { &self.common }
+      }
+
+      impl std::ops::DerefMut for $event_ty {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut Self::Target { &mut self.common }
+      }
+    )*
+  };
+}
+
+impl_common_event!(PanEvent, ScaleEvent);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{ElementState, TouchPhase};
+
+  #[test]
+  fn pan_and_swipe() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3, r4, r5) = (
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+      records.clone(),
+    );
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        on_pan_start: move |e| r1.borrow_mut().push(("start", e.offset)),
+        on_pan_update: move |e| r2.borrow_mut().push(("update", e.delta)),
+        on_pan_end: move |e| r3.borrow_mut().push(("end", e.offset)),
+        on_swipe: move |e| r4.borrow_mut().push(("swipe", e.velocity)),
+        on_tap: move |_| r5.borrow_mut().push(("tap", Vector::zero())),
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+
+    mouse_move(&mut wnd, 1., 1.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    clock.advance(Duration::from_millis(10));
+    // not over the slop.
+    mouse_move(&mut wnd, 5., 1.);
+    assert!(records.borrow().is_empty());
+
+    clock.advance(Duration::from_millis(10));
+    mouse_move(&mut wnd, 41., 1.);
+    clock.advance(Duration::from_millis(10));
+    mouse_move(&mut wnd, 61., 1.);
+    mouse_input(&mut wnd, ElementState::Released);
+
+    assert_eq!(
+      &*records.borrow(),
+      &[
+        ("start", Vector::new(40., 0.)),
+        ("update", Vector::new(20., 0.)),
+        ("end", Vector::new(60., 0.)),
+        ("swipe", Vector::new(2000., 0.)),
+      ]
+    );
+
+    // a slow pan not swipe.
+    records.borrow_mut().clear();
+    mouse_input(&mut wnd, ElementState::Pressed);
+    clock.advance(Duration::from_millis(10));
+    mouse_move(&mut wnd, 31., 1.);
+    clock.advance(Duration::from_millis(200));
+    mouse_input(&mut wnd, ElementState::Released);
+    assert_eq!(
+      &*records.borrow(),
+      &[
+        ("start", Vector::new(-30., 0.)),
+        ("end", Vector::new(-30., 0.)),
+      ]
+    );
+
+    // tap if not over the slop.
+    records.borrow_mut().clear();
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_input(&mut wnd, ElementState::Released);
+    assert_eq!(&*records.borrow(), &[("tap", Vector::zero())]);
+  }
+
+  #[test]
+  fn long_press() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2) = (records.clone(), records.clone());
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        on_long_press: move |_| r1.borrow_mut().push("long press"),
+        on_tap: move |_| r2.borrow_mut().push("tap"),
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+
+    mouse_move(&mut wnd, 1., 1.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    assert!(wnd.context.has_actived_animate());
+    clock.advance(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert!(records.borrow().is_empty());
+
+    clock.advance(Duration::from_millis(500));
+    wnd.draw_frame();
+    assert_eq!(&*records.borrow(), &["long press"]);
+    assert!(!wnd.context.has_actived_animate());
+
+    // the tap is rejected by the long press.
+    mouse_input(&mut wnd, ElementState::Released);
+    assert_eq!(&*records.borrow(), &["long press"]);
+  }
+
+  #[test]
+  fn single_and_double_tap() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3) = (records.clone(), records.clone(), records.clone());
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        on_tap: move |_| r1.borrow_mut().push("tap"),
+        on_single_tap: move |_| r2.borrow_mut().push("single tap"),
+        on_double_tap: move |_| r3.borrow_mut().push("double tap"),
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+
+    // the single tap is held until the double tap duration expired.
+    mouse_move(&mut wnd, 1., 1.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_input(&mut wnd, ElementState::Released);
+    assert_eq!(&*records.borrow(), &["tap"]);
+    assert!(wnd.context.has_actived_animate());
+    clock.advance(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert_eq!(&*records.borrow(), &["tap"]);
+
+    clock.advance(Duration::from_millis(200));
+    wnd.draw_frame();
+    assert_eq!(&*records.borrow(), &["tap", "single tap"]);
+    assert!(!wnd.context.has_actived_animate());
+
+    // the second tap in time makes a double tap, no single tap.
+    records.borrow_mut().clear();
+    clock.advance(Duration::from_secs(1));
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_input(&mut wnd, ElementState::Released);
+    clock.advance(Duration::from_millis(100));
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_input(&mut wnd, ElementState::Released);
+    clock.advance(Duration::from_millis(500));
+    wnd.draw_frame();
+    assert_eq!(&*records.borrow(), &["tap", "tap", "double tap"]);
+  }
+
+  #[test]
+  fn two_pointers_scale() {
+    let records = Rc::new(RefCell::new(vec![]));
+    let (r1, r2, r3) = (records.clone(), records.clone(), records.clone());
+    let w = widget! {
+      MockBox {
+        size: Size::new(200., 200.),
+        on_scale_start: move |e| r1.borrow_mut().push(("start", e.scale, e.rotation)),
+        on_scale_update: move |e| r2.borrow_mut().push(("update", e.scale, e.rotation)),
+        on_scale_end: move |e| r3.borrow_mut().push(("end", e.scale, e.rotation)),
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+
+    touch(&mut wnd, 1, TouchPhase::Started, 50., 100.);
+    touch(&mut wnd, 2, TouchPhase::Started, 150., 100.);
+    touch(&mut wnd, 2, TouchPhase::Moved, 250., 100.);
+    touch(&mut wnd, 2, TouchPhase::Moved, 50., 200.);
+    touch(&mut wnd, 1, TouchPhase::Ended, 50., 100.);
+    // the sequence ends until all pointers released.
+    touch(&mut wnd, 2, TouchPhase::Moved, 50., 150.);
+    touch(&mut wnd, 2, TouchPhase::Ended, 50., 150.);
+
+    assert_eq!(
+      &*records.borrow(),
+      &[
+        ("start", 1., Angle::zero()),
+        ("update", 2., Angle::zero()),
+        ("update", 1., Angle::frac_pi_2()),
+        ("end", 1., Angle::frac_pi_2()),
+      ]
+    );
+  }
+}
@@ -0,0 +1,94 @@
+use std::convert::Infallible;
+
+use crate::{
+  data_widget::compose_child_as_data_widget, impl_compose_child_with_focus_for_listener,
+  impl_listener, impl_query_self_only, prelude::*,
+};
+
+/// An attribute that receives the text composing by the input method, the
+/// committed text of the input method is received by the `on_char`.
+#[derive(Declare)]
+pub struct ImePreeditListener {
+  #[declare(builtin, convert=custom)]
+  on_ime_preedit: MutRefItemSubject<'static, ImePreeditEvent, Infallible>,
+}
+
+#[derive(Debug)]
+pub struct ImePreeditEvent {
+  /// The composing text, empty if the composing is ended or canceled.
+  pub preedit: String,
+  /// The byte range of the cursor in the `preedit`, `None` means the cursor
+  /// should be hidden.
+  pub cursor: Option<(usize, usize)>,
+  pub common: EventCommon,
+}
+
+impl_listener!(
+  ImePreeditListener,
+  ImePreeditListenerDeclarer,
+  on_ime_preedit,
+  ImePreeditEvent,
+  ime_preedit_stream
+);
+impl_compose_child_with_focus_for_listener!(ImePreeditListener);
+
+impl std::borrow::Borrow<EventCommon> for ImePreeditEvent {
+  #[inline]
+  fn borrow(&self) -> &EventCommon { &self.common }
+}
+
+impl std::borrow::BorrowMut<EventCommon> for ImePreeditEvent {
+  #[inline]
+  fn borrow_mut(&mut self) -> &mut EventCommon { &mut self.common }
+}
+
+impl std::ops::Deref for ImePreeditEvent {
+  type Target = EventCommon;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.common }
+}
+
+impl std::ops::DerefMut for ImePreeditEvent {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.common }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{Ime, WindowEvent};
+
+  #[test]
+  fn preedit_and_commit() {
+    let preedit = Rc::new(RefCell::new(vec![]));
+    let c_preedit = preedit.clone();
+    let receive = Rc::new(RefCell::new("".to_string()));
+    let c_receive = receive.clone();
+
+    let widget = widget! {
+      MockBox {
+        size: ZERO_SIZE,
+        auto_focus: true,
+        on_ime_preedit: move |e| c_preedit.borrow_mut().push((e.preedit.clone(), e.cursor)),
+        on_char: move |key| c_receive.borrow_mut().push(key.char)
+      }
+    };
+    let mut wnd = Window::default_mock(widget.into_widget(), None);
+    wnd.draw_frame();
+
+    wnd.processes_native_event(WindowEvent::Ime(Ime::Enabled));
+    wnd.processes_native_event(WindowEvent::Ime(Ime::Preedit("ni".into(), Some((2, 2)))));
+    wnd.processes_native_event(WindowEvent::Ime(Ime::Preedit("".into(), None)));
+    wnd.processes_native_event(WindowEvent::Ime(Ime::Commit("你".into())));
+
+    assert_eq!(
+      &*preedit.borrow(),
+      &[("ni".to_string(), Some((2, 2))), ("".to_string(), None)]
+    );
+    assert_eq!(&*receive.borrow(), "你");
+  }
+}
@@ -1,8 +1,8 @@
 use std::convert::Infallible;
 
 use crate::{
-  data_widget::compose_child_as_data_widget, impl_compose_child_with_focus_for_listener,
-  impl_listener, impl_query_self_only, prelude::*,
+  data_widget::compose_child_as_data_widget, impl_compose_child_for_listener,
+  impl_compose_child_with_focus_for_listener, impl_listener, impl_query_self_only, prelude::*,
 };
 
 #[derive(Debug)]
@@ -27,6 +27,21 @@ pub struct KeyUpListener {
   on_key_up: MutRefItemSubject<'static, KeyboardEvent, Infallible>,
 }
 
+/// Listen the key down event in the capture phase, it's dispatched from the
+/// root to the focused widget before the event bubbles.
+#[derive(Declare)]
+pub struct KeyDownCaptureListener {
+  #[declare(builtin, convert=custom)]
+  on_key_down_capture: MutRefItemSubject<'static, KeyboardEvent, Infallible>,
+}
+
+/// Listen the key up event in the capture phase.
+#[derive(Declare)]
+pub struct KeyUpCaptureListener {
+  #[declare(builtin, convert=custom)]
+  on_key_up_capture: MutRefItemSubject<'static, KeyboardEvent, Infallible>,
+}
+
 impl_listener!(
   KeyDownListener,
   KeyDownListenerDeclarer,
@@ -46,6 +61,24 @@ impl_listener!(
 
 impl_compose_child_with_focus_for_listener!(KeyUpListener);
 
+impl_listener!(
+  KeyDownCaptureListener,
+  KeyDownCaptureListenerDeclarer,
+  on_key_down_capture,
+  KeyboardEvent,
+  key_down_capture_stream
+);
+impl_compose_child_for_listener!(KeyDownCaptureListener);
+
+impl_listener!(
+  KeyUpCaptureListener,
+  KeyUpCaptureListenerDeclarer,
+  on_key_up_capture,
+  KeyboardEvent,
+  key_up_capture_stream
+);
+impl_compose_child_for_listener!(KeyUpCaptureListener);
+
 impl std::borrow::Borrow<EventCommon> for KeyboardEvent {
   #[inline]
   fn borrow(&self) -> &EventCommon { &self.common }
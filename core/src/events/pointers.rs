@@ -2,19 +2,33 @@ use rxrust::ops::filter_map::FilterMapOp;
 
 use super::EventCommon;
 use crate::{
-  data_widget::compose_child_as_data_widget, impl_compose_child_for_listener, impl_listener,
-  impl_query_self_only, prelude::*,
+  data_widget::compose_child_as_data_widget, impl_compose_child_for_listener,
+  impl_compose_child_with_focus_for_listener, impl_listener, impl_query_self_only, prelude::*,
 };
 use std::{
   convert::Infallible,
+  sync::atomic::{AtomicUsize, Ordering},
   time::{Duration, Instant},
 };
 
 mod from_mouse;
-const MULTI_TAP_DURATION: Duration = Duration::from_millis(250);
+mod from_touch;
+/// The max duration between the taps of a multi tap.
+pub(crate) const MULTI_TAP_DURATION: Duration = Duration::from_millis(250);
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PointerId(usize);
 
+impl PointerId {
+  /// The id of the mouse pointer.
+  pub(crate) const MOUSE: PointerId = PointerId(0);
+
+  /// Allocate a new id for the pointer pressed, other than the mouse.
+  pub(crate) fn new_unique() -> Self {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+    PointerId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+  }
+}
+
 /// The pointer is a hardware-agnostic device that can target a specific set of
 /// screen coordinates. Having a single event model for pointers can simplify
 /// creating Web sites and applications and provide a good user experience
@@ -124,6 +138,29 @@ pub struct PointerMoveListener {
   on_pointer_move: MutRefItemSubject<'static, PointerEvent, Infallible>,
 }
 
+/// Listen the pointer down event in the capture phase, it's dispatched from the
+/// root to the target before the event bubbles, so the ancestor can intercept
+/// the event before its descendants see it.
+#[derive(Declare)]
+pub struct PointerDownCaptureListener {
+  #[declare(builtin, convert=custom)]
+  on_pointer_down_capture: MutRefItemSubject<'static, PointerEvent, Infallible>,
+}
+
+/// Listen the pointer up event in the capture phase.
+#[derive(Declare)]
+pub struct PointerUpCaptureListener {
+  #[declare(builtin, convert=custom)]
+  on_pointer_up_capture: MutRefItemSubject<'static, PointerEvent, Infallible>,
+}
+
+/// Listen the pointer move event in the capture phase.
+#[derive(Declare)]
+pub struct PointerMoveCaptureListener {
+  #[declare(builtin, convert=custom)]
+  on_pointer_move_capture: MutRefItemSubject<'static, PointerEvent, Infallible>,
+}
+
 #[derive(Declare)]
 pub struct TapListener {
   #[declare(builtin, convert=custom)]
@@ -178,6 +215,30 @@ impl_pointer_listener!(
   pointer_move_stream
 );
 
+impl_pointer_listener!(
+  PointerDownCaptureListener,
+  PointerDownCaptureListenerDeclarer,
+  on_pointer_down_capture,
+  PointerEvent,
+  pointer_down_capture_stream
+);
+
+impl_pointer_listener!(
+  PointerUpCaptureListener,
+  PointerUpCaptureListenerDeclarer,
+  on_pointer_up_capture,
+  PointerEvent,
+  pointer_up_capture_stream
+);
+
+impl_pointer_listener!(
+  PointerMoveCaptureListener,
+  PointerMoveCaptureListenerDeclarer,
+  on_pointer_move_capture,
+  PointerEvent,
+  pointer_move_capture_stream
+);
+
 impl_pointer_listener!(
   PointerCancelListener,
   PointerCancelListenerDeclarer,
@@ -227,6 +288,11 @@ impl TapListenerDeclarer {
     self.on_x_times_tap((3, handler))
   }
 
+  #[deprecated(note = "use `on_triple_tap` instead")]
+  pub fn on_tripe_tap(self, handler: impl for<'r> FnMut(&'r mut PointerEvent) + 'static) -> Self {
+    self.on_triple_tap(handler)
+  }
+
   fn tap_subject(&mut self) -> MutRefItemSubject<'static, PointerEvent, Infallible> {
     self.on_tap.get_or_insert_with(Default::default).clone()
   }
@@ -254,7 +320,7 @@ impl TapListener {
     self.x_times_tap_stream(2, MULTI_TAP_DURATION)
   }
 
-  /// Return an observable stream of tripe tap event
+  /// Return an observable stream of triple tap event
   #[inline]
   pub fn triple_tap_stream(
     &self,
@@ -263,7 +329,7 @@ impl TapListener {
     impl FnMut(&mut PointerEvent) -> Option<&mut PointerEvent>,
     &mut PointerEvent,
   > {
-    self.x_times_tap_stream(2, MULTI_TAP_DURATION)
+    self.x_times_tap_stream(3, MULTI_TAP_DURATION)
   }
 
   /// Return an observable stream of x-tap event that user tapped 'x' times in
@@ -293,7 +359,7 @@ fn x_times_tap_map_filter(
 
   let mut type_info: Option<TapInfo> = None;
   move |e: &mut PointerEvent| {
-    let now = Instant::now();
+    let now = WidgetContext::wnd_ctx(&e.context()).now();
     match &mut type_info {
       Some(info) if info.pointer_id == e.id => {
         if info.stamps.len() + 1 == x {
@@ -325,24 +391,16 @@ impl EventListener for TapListener {
   fn dispatch(&self, event: &mut PointerEvent) { self.on_tap.clone().next(event) }
 }
 
-impl ComposeChild for TapListener {
-  type Child = Widget;
-  #[inline]
-  fn compose_child(this: State<Self>, child: Self::Child) -> Widget {
-    let widget = dynamic_compose_focus_node(child);
-    compose_child_as_data_widget(widget, this)
-  }
-}
+impl_compose_child_with_focus_for_listener!(TapListener);
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::test::MockBox;
-  use futures::executor::LocalPool;
   use std::{cell::RefCell, rc::Rc};
   use winit::event::{DeviceId, ElementState, ModifiersState, MouseButton, WindowEvent};
 
-  fn env(times: usize) -> (Window, Rc<RefCell<usize>>) {
+  fn env(times: usize) -> (Window, ManualClock, Rc<RefCell<usize>>) {
     let size = Size::new(400., 400.);
     let count = Rc::new(RefCell::new(0));
     let c_count = count.clone();
@@ -353,79 +411,92 @@ mod tests {
       }
     };
     let mut wnd = Window::default_mock(w, Some(size));
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
     wnd.draw_frame();
 
-    (wnd, count)
+    (wnd, clock, count)
   }
 
-  #[test]
-  fn double_tap() {
-    let (mut wnd, count) = env(2);
-
-    let mut local_pool = LocalPool::new();
+  fn tap_n(wnd: &mut Window, clock: &ManualClock, n: usize, interval: Duration) {
     let device_id = unsafe { DeviceId::dummy() };
-    observable::interval(Duration::from_millis(10), local_pool.spawner())
-      .take(8)
-      .subscribe(move |i| {
-        wnd.processes_native_event(WindowEvent::MouseInput {
-          device_id,
-          state: if i % 2 == 0 {
-            ElementState::Pressed
-          } else {
-            ElementState::Released
-          },
-          button: MouseButton::Left,
-          modifiers: ModifiersState::default(),
+    (0..n).for_each(|_| {
+      clock.advance(interval);
+      [ElementState::Pressed, ElementState::Released]
+        .into_iter()
+        .for_each(|state| {
+          wnd.processes_native_event(WindowEvent::MouseInput {
+            device_id,
+            state,
+            button: MouseButton::Left,
+            modifiers: ModifiersState::default(),
+          });
         });
-      });
-
-    local_pool.run();
+    });
+  }
 
+  #[test]
+  fn double_tap() {
+    let (mut wnd, clock, count) = env(2);
+    tap_n(&mut wnd, &clock, 4, Duration::from_millis(20));
     assert_eq!(*count.borrow(), 2);
 
-    let (mut wnd, count) = env(2);
-    observable::interval(Duration::from_millis(251), local_pool.spawner())
-      .take(8)
-      .subscribe(move |i| {
-        wnd.processes_native_event(WindowEvent::MouseInput {
-          device_id,
-          state: if i % 2 == 0 {
-            ElementState::Pressed
-          } else {
-            ElementState::Released
-          },
-          button: MouseButton::Left,
-          modifiers: ModifiersState::default(),
-        });
-      });
-
-    local_pool.run();
+    let (mut wnd, clock, count) = env(2);
+    let expired = MULTI_TAP_DURATION + Duration::from_millis(1);
+    tap_n(&mut wnd, &clock, 4, expired);
     assert_eq!(*count.borrow(), 0);
   }
 
   #[test]
   fn tripe_tap() {
-    let (mut wnd, count) = env(3);
+    let (mut wnd, clock, count) = env(3);
+    tap_n(&mut wnd, &clock, 6, Duration::from_millis(20));
+    assert_eq!(*count.borrow(), 2);
+  }
 
-    let mut local_pool = LocalPool::new();
-    let device_id = unsafe { DeviceId::dummy() };
-    observable::interval(Duration::from_millis(10), local_pool.spawner())
-      .take(12)
-      .subscribe(move |i| {
-        wnd.processes_native_event(WindowEvent::MouseInput {
-          device_id,
-          state: if i % 2 == 0 {
-            ElementState::Pressed
-          } else {
-            ElementState::Released
-          },
-          button: MouseButton::Left,
-          modifiers: ModifiersState::default(),
-        });
-      });
+  #[test]
+  #[allow(deprecated)]
+  fn tripe_tap_alias() {
+    let count = Rc::new(RefCell::new(0));
+    let c_count = count.clone();
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        on_tripe_tap: move |_| *c_count.borrow_mut() += 1
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    wnd.draw_frame();
+    tap_n(&mut wnd, &clock, 3, Duration::from_millis(20));
+    assert_eq!(*count.borrow(), 1);
+  }
+
+  #[test]
+  fn tap_widget_is_focus_node() {
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        on_tap: move |_| {}
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
 
-    local_pool.run();
+    wnd.dispatcher.next_focus_widget(&wnd.widget_tree);
+    assert!(wnd.dispatcher.focusing().is_some());
 
-    assert_eq!(*count.borrow(), 2);
+    // an explicit focus node should not be declared twice by the tap listener.
+    let w = widget! {
+      MockBox {
+        size: Size::new(100., 100.),
+        auto_focus: true,
+        on_double_tap: move |_| {}
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd.draw_frame();
+    assert!(wnd.dispatcher.focusing().is_some());
   }
 }
@@ -8,8 +8,7 @@ use winit::event::MouseButton;
 impl PointerEvent {
   pub(crate) fn from_mouse(target: WidgetId, tree: &WidgetTree, info: &DispatchInfo) -> Self {
     PointerEvent {
-      // todo: how to generate pointer id ?
-      id: PointerId(0),
+      id: PointerId::MOUSE,
       width: 1.0,
       height: 1.0,
       pressure: if info.mouse_buttons().is_empty() {
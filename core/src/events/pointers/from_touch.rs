@@ -0,0 +1,44 @@
+use super::PointerId;
+use crate::{
+  prelude::{dispatcher::DispatchInfo, *},
+  widget_tree::WidgetTree,
+};
+use winit::event::Force;
+
+impl PointerEvent {
+  /// Create a pointer event of a touch pointer. The touch with an altitude
+  /// angle is from a pen.
+  ///
+  /// The tilt of the pen is always zero, winit only reports the altitude angle,
+  /// the total tilt, without the azimuth, so the tilt in the X and Y planes can
+  /// not be known.
+  pub(crate) fn from_touch(
+    id: PointerId,
+    force: Option<Force>,
+    is_primary: bool,
+    target: WidgetId,
+    tree: &WidgetTree,
+    info: &DispatchInfo,
+  ) -> Self {
+    let point_type = match force {
+      Some(Force::Calibrated { altitude_angle: Some(_), .. }) => PointerType::Pen,
+      _ => PointerType::Touch,
+    };
+    PointerEvent {
+      id,
+      width: 1.0,
+      height: 1.0,
+      pressure: force.map_or(
+        // The platform not support pressure sensitivity.
+        0.5,
+        |f| f.normalized() as f32,
+      ),
+      tilt_x: 0.,
+      tilt_y: 0.,
+      twist: 0.,
+      point_type,
+      is_primary,
+      common: EventCommon::new(target, tree, info),
+    }
+  }
+}
@@ -0,0 +1,608 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use super::dispatcher::Dispatcher;
+use crate::{
+  data_widget::compose_child_as_data_widget, impl_compose_child_for_listener, impl_listener,
+  impl_query_self_only, prelude::*, widget_tree::WidgetTree,
+};
+use winit::event::ElementState;
+
+/// The action to move the focus to the next widget, bound to `Tab` by default.
+pub const NEXT_FOCUS_ACTION: &str = "next_focus";
+/// The action to move the focus to the previous widget, bound to `Shift+Tab` by
+/// default.
+pub const PREV_FOCUS_ACTION: &str = "prev_focus";
+/// The actions to move the focus in the direction, bound to the arrow keys by
+/// default, they only work in a [`FocusScope`] that opts in the directional
+/// navigation.
+pub const FOCUS_UP_ACTION: &str = "focus_up";
+pub const FOCUS_DOWN_ACTION: &str = "focus_down";
+pub const FOCUS_LEFT_ACTION: &str = "focus_left";
+pub const FOCUS_RIGHT_ACTION: &str = "focus_right";
+
+/// A key pressed with the modifiers, like `Ctrl+Shift+P`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+  pub modifiers: ModifiersState,
+  pub key: VirtualKeyCode,
+}
+
+/// A sequence of key chords pressed one by one, like `Ctrl+K Ctrl+C`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut(pub Vec<KeyChord>);
+
+/// The map from the shortcuts to the names of actions.
+///
+/// The app-wide bindings live in the [`AppContext`], and a [`ShortcutScope`]
+/// binds shortcuts only for its subtree. The bindings can be remapped from a
+/// config, see [`KeyBindings::load_config`].
+#[derive(Debug, Clone, Default)]
+pub struct KeyBindings {
+  bindings: Vec<(Shortcut, String)>,
+}
+
+/// The error of parsing a shortcut or a key bindings config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutParseError {
+  /// The shortcut has no key chord.
+  Empty,
+  /// The key chord has no key, or more than one key.
+  InvalidChord(String),
+  UnknownKey(String),
+  /// The line of the config is not `action = shortcut` or `[platform]`.
+  InvalidLine(usize),
+}
+
+/// The event of a shortcut bound to an action is pressed, dispatched to the
+/// focused widget, or the root if no widget is focused, and bubbles.
+///
+/// If no handler prevents its default, the framework performs the builtin
+/// actions, like [`NEXT_FOCUS_ACTION`].
+#[derive(Debug, Clone)]
+pub struct ActionEvent {
+  pub action: String,
+  pub common: EventCommon,
+}
+
+/// Fired when a shortcut bound to an action is pressed.
+#[derive(Declare)]
+pub struct ActionListener {
+  #[declare(builtin, convert=custom)]
+  on_action: MutRefItemSubject<'static, ActionEvent, Infallible>,
+}
+
+impl_listener!(
+  ActionListener,
+  ActionListenerDeclarer,
+  on_action,
+  ActionEvent,
+  action_stream
+);
+impl_compose_child_for_listener!(ActionListener);
+
+impl KeyChord {
+  #[inline]
+  pub fn new(modifiers: ModifiersState, key: VirtualKeyCode) -> Self { Self { modifiers, key } }
+}
+
+impl KeyBindings {
+  /// The bindings that the app uses by default.
+  pub fn app_default() -> Self {
+    let mut bindings = Self::default();
+    let key = |modifiers, key| Shortcut(vec![KeyChord::new(modifiers, key)]);
+    let none = ModifiersState::empty();
+    bindings
+      .bind(key(none, VirtualKeyCode::Tab), NEXT_FOCUS_ACTION)
+      .bind(
+        key(ModifiersState::SHIFT, VirtualKeyCode::Tab),
+        PREV_FOCUS_ACTION,
+      )
+      .bind(key(none, VirtualKeyCode::Up), FOCUS_UP_ACTION)
+      .bind(key(none, VirtualKeyCode::Down), FOCUS_DOWN_ACTION)
+      .bind(key(none, VirtualKeyCode::Left), FOCUS_LEFT_ACTION)
+      .bind(key(none, VirtualKeyCode::Right), FOCUS_RIGHT_ACTION);
+    bindings
+  }
+
+  /// Bind the `shortcut` to the `action`, replace the action it bound before.
+  pub fn bind(&mut self, shortcut: Shortcut, action: impl Into<String>) -> &mut Self {
+    let action = action.into();
+    match self.bindings.iter_mut().find(|(s, _)| s == &shortcut) {
+      Some(binding) => binding.1 = action,
+      None => self.bindings.push((shortcut, action)),
+    }
+    self
+  }
+
+  /// Remove all the shortcuts bound to the `action`.
+  pub fn unbind_action(&mut self, action: &str) -> &mut Self {
+    self.bindings.retain(|(_, a)| a != action);
+    self
+  }
+
+  /// The action bound to the `shortcut`.
+  pub fn action_of(&self, shortcut: &Shortcut) -> Option<&str> {
+    self
+      .bindings
+      .iter()
+      .find(|(s, _)| s == shortcut)
+      .map(|(_, a)| a.as_str())
+  }
+
+  /// The shortcuts bound to the `action`.
+  pub fn shortcuts_of<'a>(&'a self, action: &'a str) -> impl Iterator<Item = &'a Shortcut> {
+    self
+      .bindings
+      .iter()
+      .filter(move |(_, a)| a == action)
+      .map(|(s, _)| s)
+  }
+
+  /// Parse the bindings from a config, see [`KeyBindings::load_config`].
+  pub fn from_config(config: &str) -> Result<Self, ShortcutParseError> {
+    let mut bindings = Self::default();
+    bindings.load_config(config)?;
+    Ok(bindings)
+  }
+
+  /// Load the bindings from a config, the actions in the config are remapped
+  /// to the shortcuts of the config.
+  ///
+  /// Every line of the config binds a shortcut to an action as
+  /// `action = shortcut`, and an action can be bound to several shortcuts in
+  /// several lines. The lines after a `[platform]` line only apply on the
+  /// platform, the name of the platform is the same as
+  /// [`std::env::consts::OS`], and the lines before any `[platform]` line
+  /// apply on all platforms. The line starts with `#` is a comment.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use ribir_core::prelude::*;
+  /// let bindings = KeyBindings::from_config(
+  ///   r#"
+  ///   save = Mod+S
+  ///   comment = Ctrl+K Ctrl+C
+  ///   [macos]
+  ///   comment = Cmd+/
+  ///   "#,
+  /// )
+  /// .unwrap();
+  /// // `Mod` is `Cmd` on macOS and `Ctrl` on others.
+  /// let save: Shortcut = "Mod+S".parse().unwrap();
+  /// assert_eq!(bindings.action_of(&save), Some("save"));
+  /// ```
+  pub fn load_config(&mut self, config: &str) -> Result<(), ShortcutParseError> {
+    let mut platform = None;
+    let mut loaded: Vec<(Shortcut, String)> = vec![];
+    // The actions that the platform lines have overridden.
+    let mut overridden: Vec<&str> = vec![];
+    for (idx, line) in config.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        platform = Some(name.trim());
+        continue;
+      }
+      let (action, shortcut) = line
+        .split_once('=')
+        .ok_or(ShortcutParseError::InvalidLine(idx + 1))?;
+      let action = action.trim();
+      if action.is_empty() {
+        return Err(ShortcutParseError::InvalidLine(idx + 1));
+      }
+      let shortcut = shortcut.parse()?;
+      if platform.iter().all(|p| *p == std::env::consts::OS) {
+        // The platform lines override the common lines of the action.
+        if platform.is_some() && !overridden.contains(&action) {
+          loaded.retain(|(_, a)| a != action);
+          overridden.push(action);
+        }
+        loaded.push((shortcut, action.to_string()));
+      }
+    }
+
+    loaded.iter().for_each(|(_, action)| {
+      self.unbind_action(action);
+    });
+    loaded.into_iter().for_each(|(shortcut, action)| {
+      self.bind(shortcut, action);
+    });
+    Ok(())
+  }
+
+  fn lookup(&self, chords: &[KeyChord]) -> Lookup {
+    let mut lookup = Lookup::None;
+    for (shortcut, action) in self.bindings.iter() {
+      if shortcut.0 == chords {
+        return Lookup::Action(action.clone());
+      } else if shortcut.0.starts_with(chords) {
+        lookup = Lookup::Prefix;
+      }
+    }
+    lookup
+  }
+}
+
+enum Lookup {
+  Action(String),
+  /// The chords are the beginning of a shortcut, wait for the next chord.
+  Prefix,
+  None,
+}
+
+impl FromStr for KeyChord {
+  type Err = ShortcutParseError;
+
+  /// Parse a key chord like `Ctrl+Shift+P`, the modifiers are `Ctrl`, `Shift`,
+  /// `Alt` (or `Option`), `Cmd` (or `Super`, `Win`, `Logo`) and `Mod` that is
+  /// `Cmd` on macOS and `Ctrl` on others, case insensitive.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut modifiers = ModifiersState::empty();
+    let mut key = None;
+    // `+` itself is a key, like `Ctrl++`.
+    let parts = match s.strip_suffix("++") {
+      Some(rest) => rest
+        .split('+')
+        .chain(std::iter::once("+"))
+        .collect::<Vec<_>>(),
+      None => s.split('+').collect(),
+    };
+    for part in parts {
+      let part = part.trim();
+      let modifier = match part.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some(ModifiersState::CTRL),
+        "shift" => Some(ModifiersState::SHIFT),
+        "alt" | "option" => Some(ModifiersState::ALT),
+        "cmd" | "command" | "super" | "win" | "logo" | "meta" => Some(ModifiersState::LOGO),
+        "mod" if cfg!(target_os = "macos") => Some(ModifiersState::LOGO),
+        "mod" => Some(ModifiersState::CTRL),
+        _ => None,
+      };
+      match modifier {
+        Some(m) => modifiers |= m,
+        None if key.is_none() => key = Some(key_from_name(part)?),
+        None => return Err(ShortcutParseError::InvalidChord(s.to_string())),
+      }
+    }
+    let key = key.ok_or_else(|| ShortcutParseError::InvalidChord(s.to_string()))?;
+    Ok(KeyChord { modifiers, key })
+  }
+}
+
+impl FromStr for Shortcut {
+  type Err = ShortcutParseError;
+
+  /// Parse a shortcut like `Ctrl+K Ctrl+C`, the key chords are separated by
+  /// whitespace.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let chords = s
+      .split_whitespace()
+      .map(KeyChord::from_str)
+      .collect::<Result<Vec<_>, _>>()?;
+    if chords.is_empty() {
+      Err(ShortcutParseError::Empty)
+    } else {
+      Ok(Shortcut(chords))
+    }
+  }
+}
+
+fn key_from_name(name: &str) -> Result<VirtualKeyCode, ShortcutParseError> {
+  use VirtualKeyCode::*;
+  const LETTERS: [VirtualKeyCode; 26] = [
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+  ];
+  const DIGITS: [VirtualKeyCode; 10] = [Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9];
+  const FUNCTIONS: [VirtualKeyCode; 12] = [F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12];
+
+  let lower = name.to_ascii_lowercase();
+  let mut chars = lower.chars();
+  let key = match (chars.next(), chars.next()) {
+    (Some(c @ 'a'..='z'), None) => Some(LETTERS[(c as u8 - b'a') as usize]),
+    (Some(c @ '0'..='9'), None) => Some(DIGITS[(c as u8 - b'0') as usize]),
+    (Some('f'), Some(_)) => lower[1..]
+      .parse::<usize>()
+      .ok()
+      .and_then(|n| FUNCTIONS.get(n.wrapping_sub(1)).copied()),
+    _ => None,
+  };
+  let key = key.or(match lower.as_str() {
+    "esc" | "escape" => Some(Escape),
+    "tab" => Some(Tab),
+    "space" => Some(Space),
+    "enter" | "return" => Some(Return),
+    "backspace" | "back" => Some(Back),
+    "delete" | "del" => Some(Delete),
+    "insert" | "ins" => Some(Insert),
+    "home" => Some(Home),
+    "end" => Some(End),
+    "pageup" => Some(PageUp),
+    "pagedown" => Some(PageDown),
+    "left" => Some(Left),
+    "right" => Some(Right),
+    "up" => Some(Up),
+    "down" => Some(Down),
+    "+" | "plus" => Some(Plus),
+    "-" | "minus" => Some(Minus),
+    "=" | "equals" => Some(Equals),
+    "," | "comma" => Some(Comma),
+    "." | "period" => Some(Period),
+    "/" | "slash" => Some(Slash),
+    "\\" | "backslash" => Some(Backslash),
+    ";" | "semicolon" => Some(Semicolon),
+    "'" | "apostrophe" => Some(Apostrophe),
+    "[" | "lbracket" => Some(LBracket),
+    "]" | "rbracket" => Some(RBracket),
+    "`" | "grave" => Some(Grave),
+    _ => None,
+  });
+  key.ok_or_else(|| ShortcutParseError::UnknownKey(name.to_string()))
+}
+
+fn is_modifier_key(key: VirtualKeyCode) -> bool {
+  use VirtualKeyCode::*;
+  matches!(
+    key,
+    LShift | RShift | LControl | RControl | LAlt | RAlt | LWin | RWin
+  )
+}
+
+impl Dispatcher {
+  /// Match the pressed key chords with the key bindings of the shortcut scopes
+  /// of the focused widget from inner to outer, then the app. The inner scope
+  /// that binds the chords or their beginning shadows the outers.
+  pub fn shortcut_process(
+    &mut self,
+    key: VirtualKeyCode,
+    state: ElementState,
+    tree: &mut WidgetTree,
+  ) {
+    if state == ElementState::Released || is_modifier_key(key) {
+      return;
+    }
+    let target = self.focusing().unwrap_or_else(|| tree.root());
+    let chord = KeyChord::new(self.info.modifiers(), key);
+    self.pending_chords.push(chord);
+    loop {
+      match scopes_lookup(target, &self.pending_chords, tree) {
+        Lookup::Action(action) => {
+          self.pending_chords.clear();
+          self.dispatch_action(action, target, tree);
+          return;
+        }
+        Lookup::Prefix => return,
+        // The chord may start a new shortcut.
+        Lookup::None if self.pending_chords.len() > 1 => self.pending_chords = vec![chord],
+        Lookup::None => {
+          self.pending_chords.clear();
+          return;
+        }
+      }
+    }
+  }
+
+  fn dispatch_action(&mut self, action: String, target: WidgetId, tree: &mut WidgetTree) {
+    let mut e = ActionEvent {
+      action,
+      common: EventCommon::new(target, tree, &self.info),
+    };
+    tree.bubble_event::<ActionListener>(&mut e);
+    if !e.common.prevent_default {
+      match e.action.as_str() {
+        NEXT_FOCUS_ACTION => self.next_focus_widget(tree),
+        PREV_FOCUS_ACTION => self.prev_focus_widget(tree),
+        FOCUS_UP_ACTION => self.directional_focus_widget(FocusDirection::Up, tree),
+        FOCUS_DOWN_ACTION => self.directional_focus_widget(FocusDirection::Down, tree),
+        FOCUS_LEFT_ACTION => self.directional_focus_widget(FocusDirection::Left, tree),
+        FOCUS_RIGHT_ACTION => self.directional_focus_widget(FocusDirection::Right, tree),
+        _ => {}
+      }
+    }
+  }
+}
+
+/// Look up the chords from the nearest `ShortcutScope` of the `target` to the
+/// application key bindings.
+fn scopes_lookup(target: WidgetId, chords: &[KeyChord], tree: &WidgetTree) -> Lookup {
+  let arena = &tree.arena;
+  for w in target.ancestors(arena) {
+    let mut lookup = Lookup::None;
+    w.assert_get(arena)
+      .query_on_first_type(QueryOrder::InnerFirst, |s: &ShortcutScope| {
+        lookup = s.bindings.lookup(chords)
+      });
+    if !matches!(lookup, Lookup::None) {
+      return lookup;
+    }
+  }
+  tree.wnd_ctx.app_ctx.key_bindings.borrow().lookup(chords)
+}
+
+impl fmt::Display for ShortcutParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ShortcutParseError::Empty => write!(f, "the shortcut is empty"),
+      ShortcutParseError::InvalidChord(chord) => write!(f, "invalid key chord `{chord}`"),
+      ShortcutParseError::UnknownKey(key) => write!(f, "unknown key `{key}`"),
+      ShortcutParseError::InvalidLine(line) => {
+        write!(f, "line {line} is not `action = shortcut` or `[platform]`")
+      }
+    }
+  }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+impl std::borrow::Borrow<EventCommon> for ActionEvent {
+  #[inline]
+  fn borrow(&self) -> &EventCommon { &self.common }
+}
+
+impl std::borrow::BorrowMut<EventCommon> for ActionEvent {
+  #[inline]
+  fn borrow_mut(&mut self) -> &mut EventCommon { &mut self.common }
+}
+
+impl std::ops::Deref for ActionEvent {
+  type Target = EventCommon;
+  #[inline]
+  fn deref(&self) -> &Self::Target { &self.common }
+}
+
+impl std::ops::DerefMut for ActionEvent {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.common }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::*;
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{DeviceId, KeyboardInput, WindowEvent};
+
+  fn press(wnd: &mut Window, modifiers: ModifiersState, key: VirtualKeyCode) {
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(modifiers));
+    [ElementState::Pressed, ElementState::Released]
+      .into_iter()
+      .for_each(|state| {
+        #[allow(deprecated)]
+        wnd.processes_native_event(WindowEvent::KeyboardInput {
+          device_id: unsafe { DeviceId::dummy() },
+          input: KeyboardInput {
+            scancode: 0,
+            virtual_keycode: Some(key),
+            state,
+            modifiers,
+          },
+          is_synthetic: false,
+        });
+      });
+  }
+
+  #[test]
+  fn parse_shortcut() {
+    let chord = |s: &str| s.parse::<KeyChord>();
+    assert_eq!(
+      chord("Ctrl+Shift+P"),
+      Ok(KeyChord::new(
+        ModifiersState::CTRL | ModifiersState::SHIFT,
+        VirtualKeyCode::P
+      ))
+    );
+    assert_eq!(
+      chord("ctrl++"),
+      Ok(KeyChord::new(ModifiersState::CTRL, VirtualKeyCode::Plus))
+    );
+    assert_eq!(
+      chord("F12"),
+      Ok(KeyChord::new(ModifiersState::empty(), VirtualKeyCode::F12))
+    );
+    assert_eq!(
+      chord("Ctrl+Shift"),
+      Err(ShortcutParseError::InvalidChord("Ctrl+Shift".into()))
+    );
+    assert_eq!(
+      chord("Ctrl+Foo"),
+      Err(ShortcutParseError::UnknownKey("Foo".into()))
+    );
+
+    let shortcut: Shortcut = "Ctrl+K Ctrl+C".parse().unwrap();
+    assert_eq!(shortcut.0.len(), 2);
+    assert_eq!("  ".parse::<Shortcut>(), Err(ShortcutParseError::Empty));
+  }
+
+  #[test]
+  fn remap_from_config() {
+    let mut bindings = KeyBindings::app_default();
+    bindings
+      .load_config(
+        r#"
+        # move focus by the arrow keys
+        next_focus = Down
+        prev_focus = Up
+        [not_exist_os]
+        next_focus = Right
+        "#,
+      )
+      .unwrap();
+    let tab: Shortcut = "Tab".parse().unwrap();
+    assert_eq!(bindings.action_of(&tab), None);
+    let down: Shortcut = "Down".parse().unwrap();
+    assert_eq!(bindings.action_of(&down), Some(NEXT_FOCUS_ACTION));
+    assert_eq!(bindings.shortcuts_of(NEXT_FOCUS_ACTION).count(), 1);
+
+    assert_eq!(
+      KeyBindings::from_config("save Ctrl+S").unwrap_err(),
+      ShortcutParseError::InvalidLine(1)
+    );
+  }
+
+  #[test]
+  fn platform_lines_override_common_lines() {
+    let config = format!(
+      "save = Ctrl+S\n[{}]\nsave = Ctrl+Shift+S\nsave = F12",
+      std::env::consts::OS
+    );
+    let bindings = KeyBindings::from_config(&config).unwrap();
+    let ctrl_s: Shortcut = "Ctrl+S".parse().unwrap();
+    assert_eq!(bindings.action_of(&ctrl_s), None);
+    let ctrl_shift_s: Shortcut = "Ctrl+Shift+S".parse().unwrap();
+    assert_eq!(bindings.action_of(&ctrl_shift_s), Some("save"));
+    let f12: Shortcut = "F12".parse().unwrap();
+    assert_eq!(bindings.action_of(&f12), Some("save"));
+    assert_eq!(bindings.shortcuts_of("save").count(), 2);
+  }
+
+  #[test]
+  fn scoped_multi_stroke_shortcut() {
+    let actions = Rc::new(RefCell::new(vec![]));
+    let c_actions = actions.clone();
+    let mut scope_bindings = KeyBindings::default();
+    scope_bindings
+      .bind("Ctrl+K Ctrl+C".parse().unwrap(), "comment")
+      .bind("Ctrl+S".parse().unwrap(), "scope_save");
+    let w = widget! {
+      MockMulti {
+        on_action: move |e| c_actions.borrow_mut().push(e.action.clone()),
+        ShortcutScope {
+          bindings: scope_bindings,
+          MockBox {
+            size: Size::zero(),
+            auto_focus: true,
+            on_key_down: move |e| if e.key == VirtualKeyCode::Q {
+              e.prevent_default();
+            },
+          }
+        }
+      }
+    };
+    let mut wnd = Window::default_mock(w, None);
+    wnd
+      .widget_tree
+      .wnd_ctx
+      .app_ctx
+      .key_bindings
+      .borrow_mut()
+      .load_config("save = Ctrl+S\nquit = Ctrl+Q")
+      .unwrap();
+    wnd.draw_frame();
+
+    let ctrl = ModifiersState::CTRL;
+    press(&mut wnd, ctrl, VirtualKeyCode::K);
+    assert!(actions.borrow().is_empty());
+    press(&mut wnd, ctrl, VirtualKeyCode::C);
+    // The scope shadows the app binding.
+    press(&mut wnd, ctrl, VirtualKeyCode::S);
+    // The focused widget handles the key first.
+    press(&mut wnd, ctrl, VirtualKeyCode::Q);
+    // A chord not follow the prefix starts a new shortcut.
+    press(&mut wnd, ctrl, VirtualKeyCode::K);
+    press(&mut wnd, ctrl, VirtualKeyCode::S);
+
+    assert_eq!(&*actions.borrow(), &["comment", "scope_save", "scope_save"]);
+  }
+}
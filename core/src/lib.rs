@@ -13,6 +13,8 @@ mod state;
 pub(crate) mod widget_tree;
 
 pub mod assign_observable;
+pub mod clipboard;
+pub mod clock;
 pub mod declare;
 mod decorator;
 pub mod dynamic_widget;
@@ -28,6 +30,10 @@ pub mod prelude {
   pub use crate::application::Application;
   pub use crate::assign_observable::AssignObservable;
   #[doc(no_inline)]
+  pub use crate::clipboard::{Clipboard, MockClipboard, SystemClipboard};
+  #[doc(no_inline)]
+  pub use crate::clock::{Clock, ManualClock, SystemClock};
+  #[doc(no_inline)]
   pub use crate::builtin_widgets::*;
   #[doc(no_inline)]
   pub use crate::context::*;
@@ -1,4 +1,8 @@
 use crate::{impl_query_self_only, prelude::*};
+use std::{cell::RefCell, time::Duration};
+use winit::event::{
+  DeviceId, ElementState, ModifiersState, MouseButton, Touch, TouchPhase, WindowEvent,
+};
 
 #[derive(Default, Clone, Copy)]
 pub struct ExpectRect {
@@ -139,6 +143,76 @@ impl Window {
   pub fn widget_count(&self) -> usize { self.widget_tree.count() }
 }
 
+/// Move the mouse cursor of the window to `(x, y)`.
+pub fn mouse_move(wnd: &mut Window, x: f32, y: f32) {
+  wnd.processes_native_event(WindowEvent::CursorMoved {
+    device_id: unsafe { DeviceId::dummy() },
+    position: (x, y).into(),
+    modifiers: ModifiersState::default(),
+  });
+}
+
+/// Press or release the left mouse button.
+pub fn mouse_input(wnd: &mut Window, state: ElementState) {
+  wnd.processes_native_event(WindowEvent::MouseInput {
+    device_id: unsafe { DeviceId::dummy() },
+    state,
+    button: MouseButton::Left,
+    modifiers: ModifiersState::default(),
+  });
+}
+
+/// Dispatch a touch event of the finger `id` at `(x, y)`.
+pub fn touch(wnd: &mut Window, id: u64, phase: TouchPhase, x: f64, y: f64) {
+  wnd.processes_native_event(WindowEvent::Touch(Touch {
+    device_id: unsafe { DeviceId::dummy() },
+    phase,
+    location: (x, y).into(),
+    force: None,
+    id,
+  }));
+}
+
+/// A window context that draws the animation frames by hand, the time of the
+/// frames comes from a [`ManualClock`].
+pub struct ManualFrames {
+  pub wnd_ctx: WindowCtx,
+  pub clock: ManualClock,
+  pub pool: FuturesLocalSchedulerPool,
+}
+
+impl Default for ManualFrames {
+  fn default() -> Self {
+    let pool = FuturesLocalSchedulerPool::default();
+    let wnd_ctx = WindowCtx::new(<_>::default(), pool.spawner());
+    let clock = ManualClock::default();
+    wnd_ctx.set_clock(clock.clone());
+    Self { wnd_ctx, clock, pool }
+  }
+}
+
+impl ManualFrames {
+  /// Call `f` with a build context of the window.
+  pub fn build<R>(&self, f: impl FnOnce(&BuildCtx) -> R) -> R {
+    let themes = RefCell::new(vec![]);
+    f(&BuildCtx::new(&themes, &self.wnd_ctx))
+  }
+
+  /// Advance the clock `ms` milliseconds and draw a frame. `read` is called
+  /// after the animations updated the frame, before they restore the values
+  /// at the end of the frame.
+  pub fn step<R>(&mut self, ms: u64, read: impl FnOnce() -> R) -> R {
+    self.clock.advance(Duration::from_millis(ms));
+    self.wnd_ctx.layout_ready();
+    let res = read();
+    self.wnd_ctx.end_frame();
+    self.pool.run();
+    res
+  }
+
+  pub fn has_actived_animate(&self) -> bool { self.wnd_ctx.has_actived_animate() }
+}
+
 #[allow(unused)]
 macro count {
   () => (0usize),
@@ -5,7 +5,11 @@ pub mod widget_id;
 pub(crate) use widget_id::TreeArena;
 pub use widget_id::WidgetId;
 mod layout_info;
-use crate::{prelude::*, widget::widget_id::new_node};
+use crate::{
+  animation::paint_hero_flights, builtin_widgets::end_layout_transitions,
+  events::paint_drag_feedback, prelude::*,
+  widget::widget_id::new_node,
+};
 pub use layout_info::*;
 
 pub(crate) type DirtySet = Rc<RefCell<HashSet<WidgetId, ahash::RandomState>>>;
@@ -41,17 +45,7 @@ impl WidgetTree {
 
   /// Draw current tree by painter.
   pub(crate) fn draw(&self, painter: &mut Painter) {
-    fn paint_rect_intersect(painter: &mut Painter, rc: &Rect) -> bool {
-      let paint_rect = painter.get_transform().outer_transformed_rect(rc);
-      painter
-        .visual_rect()
-        .and_then(|rc| rc.intersection(&paint_rect))
-        .is_some()
-    }
-
     let Self { root, arena, store, wnd_ctx, .. } = self;
-    let mut w = Some(*root);
-
     let mut paint_ctx = PaintingCtx {
       id: *root,
       arena,
@@ -59,40 +53,27 @@ impl WidgetTree {
       wnd_ctx,
       painter,
     };
-    while let Some(id) = w {
-      paint_ctx.id = id;
-      paint_ctx.painter.save();
-
-      let mut need_paint = false;
-      if paint_ctx.painter.alpha() != 0. {
-        if let Some(layout_box) = paint_ctx.box_rect() {
-          let render = id.assert_get(arena);
-          if paint_rect_intersect(paint_ctx.painter, &layout_box) || render.can_overflow() {
-            paint_ctx
-              .painter
-              .translate(layout_box.min_x(), layout_box.min_y());
-            render.paint(&mut paint_ctx);
-            need_paint = true;
-          }
+    paint_subtree(*root, &mut paint_ctx);
+
+    // The widgets delay to drop are painted over the tree at where its parent is.
+    wnd_ctx
+      .delay_drop_widgets
+      .borrow()
+      .iter()
+      .for_each(|(parent, id)| {
+        let pos = match parent {
+          Some(p) if in_tree(*p, *root, arena) => store.map_to_global(Point::zero(), *p, arena),
+          Some(_) => return,
+          // The root is replaced, the widget is painted at the window origin.
+          None => Point::zero(),
         };
-      }
-
-      w = id.first_child(arena).filter(|_| need_paint).or_else(|| {
-        let mut node = w;
-        while let Some(p) = node {
-          // self node sub-tree paint finished, goto sibling
-          paint_ctx.painter.restore();
-          node = p.next_sibling(arena);
-          if node.is_some() {
-            break;
-          } else {
-            // if there is no more sibling, back to parent to find sibling.
-            node = p.parent(arena);
-          }
-        }
-        node
+        paint_ctx.painter.save();
+        paint_ctx.painter.translate(pos.x, pos.y);
+        paint_subtree(*id, &mut paint_ctx);
+        paint_ctx.painter.restore();
       });
-    }
+    paint_hero_flights(&mut paint_ctx);
+    paint_drag_feedback(&mut paint_ctx);
   }
 
   /// Do the work of computing the layout for all node which need, Return if any
@@ -132,6 +113,35 @@ impl WidgetTree {
         });
       }
     }
+    end_layout_transitions(&self.arena, &self.store, &self.wnd_ctx);
+    self.drop_delay_widgets();
+    self.wnd_ctx.hero_track.borrow_mut().end_layout();
+  }
+
+  /// Drop the widgets that delay to drop if they are allowed to drop or their
+  /// parent is not in the tree anymore.
+  fn drop_delay_widgets(&mut self) {
+    let Self { root, arena, store, wnd_ctx, .. } = self;
+    let mut widgets = wnd_ctx.delay_drop_widgets.borrow_mut();
+    let remove = widgets
+      .iter()
+      .filter(|(parent, id)| {
+        !id.need_delay_drop(arena) || matches!(parent, Some(p) if !in_tree(*p, *root, arena))
+      })
+      .map(|(_, id)| *id)
+      .collect::<Vec<_>>();
+    if remove.is_empty() {
+      return;
+    }
+    widgets.retain(|(_, id)| !remove.contains(id));
+    drop(widgets);
+
+    remove.into_iter().for_each(|id| {
+      id.descendants(arena).for_each(|w| {
+        store.remove(w);
+      });
+      id.remove_subtree(arena);
+    });
   }
 
   pub(crate) fn mark_dirty(&self, id: WidgetId) { self.dirty_set.borrow_mut().insert(id); }
@@ -169,6 +179,61 @@ impl WidgetTree {
   }
 }
 
+fn paint_rect_intersect(painter: &mut Painter, rc: &Rect) -> bool {
+  let paint_rect = painter.get_transform().outer_transformed_rect(rc);
+  painter
+    .visual_rect()
+    .and_then(|rc| rc.intersection(&paint_rect))
+    .is_some()
+}
+
+/// Paint the subtree of `root`, the painter should be at the parent of `root`.
+pub(crate) fn paint_subtree(root: WidgetId, paint_ctx: &mut PaintingCtx) {
+  let arena = paint_ctx.arena;
+  let mut w = Some(root);
+  while let Some(id) = w {
+    paint_ctx.id = id;
+    paint_ctx.painter.save();
+
+    let mut need_paint = false;
+    if paint_ctx.painter.alpha() != 0. {
+      if let Some(layout_box) = paint_ctx.box_rect() {
+        let render = id.assert_get(arena);
+        if paint_rect_intersect(paint_ctx.painter, &layout_box) || render.can_overflow() {
+          paint_ctx
+            .painter
+            .translate(layout_box.min_x(), layout_box.min_y());
+          render.paint(paint_ctx);
+          need_paint = true;
+        }
+      };
+    }
+
+    w = id.first_child(arena).filter(|_| need_paint).or_else(|| {
+      let mut node = w;
+      while let Some(p) = node {
+        // self node sub-tree paint finished, goto sibling
+        paint_ctx.painter.restore();
+        if p == root {
+          return None;
+        }
+        node = p.next_sibling(arena);
+        if node.is_some() {
+          break;
+        } else {
+          // if there is no more sibling, back to parent to find sibling.
+          node = p.parent(arena);
+        }
+      }
+      node
+    });
+  }
+}
+
+fn in_tree(id: WidgetId, root: WidgetId, arena: &TreeArena) -> bool {
+  !id.is_dropped(arena) && id.ancestors(arena).last() == Some(root)
+}
+
 impl Widget {
   pub(crate) fn into_subtree(
     self,
@@ -290,7 +355,7 @@ mod tests {
   extern crate test;
   use crate::{
     test::{layout_size_by_path, MockBox, MockMulti},
-    widget::widget_id::dispose_nodes,
+    widget::widget_id::{dispatch_disposed, drop_subtree},
   };
 
   use super::*;
@@ -482,8 +547,10 @@ mod tests {
     tree.mark_dirty(tree.root());
     let WidgetTree { root, arena, store, wnd_ctx, .. } = &mut tree;
 
-    dispose_nodes(root.descendants(arena), arena, store, wnd_ctx);
-    root.remove_subtree(arena);
+    root
+      .descendants(arena)
+      .for_each(|w| dispatch_disposed(w, arena, store, wnd_ctx));
+    drop_subtree(*root, arena, store, wnd_ctx);
 
     assert_eq!(tree.layout_list(), None);
     assert!(!tree.is_dirty());
@@ -2,7 +2,7 @@ use indextree::{Arena, Node, NodeId};
 use rxrust::prelude::*;
 
 use crate::{
-  builtin_widgets::{DisposedListener, MountedListener, Void},
+  builtin_widgets::{DelayDrop, DisposedListener, MountedListener, Void},
   context::{LifeCycleCtx, WindowCtx},
   state::{ModifyScope, StateChangeNotifier},
   widget::{QueryOrder, Render},
@@ -131,6 +131,17 @@ impl WidgetId {
 
   pub(crate) fn detach(self, tree: &mut TreeArena) { self.0.detach(tree) }
 
+  /// Detect if the widget is a [`DelayDrop`] that not allowed to drop yet.
+  pub(crate) fn need_delay_drop(self, tree: &TreeArena) -> bool {
+    let mut delay = false;
+    self
+      .assert_get(tree)
+      .query_on_first_type(QueryOrder::OutsideFirst, |d: &DelayDrop| {
+        delay = !d.delay_drop_until
+      });
+    delay
+  }
+
   pub(crate) fn remove_subtree(self, arena: &mut TreeArena) { self.0.remove_subtree(arena) }
 
   pub(crate) fn on_mounted_subtree(
@@ -229,21 +240,39 @@ pub(crate) fn new_node(arena: &mut TreeArena, node: Box<dyn Render>) -> WidgetId
 
 pub(crate) fn empty_node(arena: &mut TreeArena) -> WidgetId { new_node(arena, Box::new(Void)) }
 
-pub(crate) fn dispose_nodes<T: Iterator<Item = WidgetId>>(
-  it: T,
-  arena: &TreeArena,
+/// Remove the subtree of `id` from the tree, its disposed events should have
+/// been dispatched. But if `id` is a [`DelayDrop`] not allowed to drop yet, the
+/// subtree is detached and keeps its layout information to paint until it's
+/// allowed to drop.
+pub(crate) fn drop_subtree(
+  id: WidgetId,
+  arena: &mut TreeArena,
   store: &mut LayoutStore,
   wnd_ctx: &WindowCtx,
 ) {
-  it.for_each(|id| {
-    store.remove(id);
+  if id.need_delay_drop(arena) {
+    let parent = id.parent(arena);
+    id.detach(arena);
+    wnd_ctx.delay_drop_widgets.borrow_mut().push((parent, id));
+  } else {
+    id.descendants(arena).for_each(|w| {
+      store.remove(w);
+    });
+    id.remove_subtree(arena);
+  }
+}
 
-    id.assert_get(arena).query_all_type(
-      |d: &DisposedListener| {
-        d.dispatch(LifeCycleCtx { id, arena, store, wnd_ctx });
-        true
-      },
-      QueryOrder::OutsideFirst,
-    )
-  });
+pub(crate) fn dispatch_disposed(
+  id: WidgetId,
+  arena: &TreeArena,
+  store: &LayoutStore,
+  wnd_ctx: &WindowCtx,
+) {
+  id.assert_get(arena).query_all_type(
+    |d: &DisposedListener| {
+      d.dispatch(LifeCycleCtx { id, arena, store, wnd_ctx });
+      true
+    },
+    QueryOrder::OutsideFirst,
+  )
 }
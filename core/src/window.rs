@@ -1,7 +1,10 @@
-use std::error::Error;
+use std::{cell::RefCell, error::Error, rc::Rc};
 
 use crate::{
-  context::AppContext, events::dispatcher::Dispatcher, prelude::*, widget_tree::WidgetTree,
+  context::{AppContext, ImeRequest},
+  events::dispatcher::Dispatcher,
+  prelude::*,
+  widget_tree::WidgetTree,
 };
 
 pub use winit::window::CursorIcon;
@@ -19,6 +22,11 @@ pub trait RawWindow {
   /// Modify the native window if cursor modified.
   fn set_cursor(&mut self, cursor: CursorIcon);
   fn scale_factor(&self) -> f64;
+  /// Allow the input method or not.
+  fn set_ime_allowed(&mut self, allowed: bool);
+  /// Set the position of the candidate window of the input method, relative to
+  /// the window.
+  fn set_ime_pos(&mut self, pos: Point);
 }
 
 impl RawWindow for winit::window::Window {
@@ -63,6 +71,16 @@ impl RawWindow for winit::window::Window {
 
   #[inline]
   fn scale_factor(&self) -> f64 { winit::window::Window::scale_factor(self) }
+
+  #[inline]
+  fn set_ime_allowed(&mut self, allowed: bool) {
+    winit::window::Window::set_ime_allowed(self, allowed)
+  }
+
+  fn set_ime_pos(&mut self, pos: Point) {
+    let pos = winit::dpi::LogicalPosition::new(pos.x, pos.y);
+    winit::window::Window::set_ime_position(self, pos)
+  }
 }
 
 pub struct WindowBuilder {
@@ -214,12 +232,16 @@ impl Window {
     if let Some(icon) = self.dispatcher.take_cursor_icon() {
       self.raw_window.set_cursor(icon);
     }
+    self.update_ime();
   }
 
   /// Draw an image what current render tree represent.
   pub fn draw_frame(&mut self) {
     if self.need_draw() {
       self.context.begin_frame();
+      self.dispatcher.check_long_press(&mut self.widget_tree);
+      self.dispatcher.check_single_tap(&mut self.widget_tree);
+      self.dispatcher.drag_dispatch(None, &mut self.widget_tree);
 
       loop {
         self.layout();
@@ -239,6 +261,7 @@ impl Window {
       self.p_backend.submit(commands);
 
       self.context.end_frame();
+      self.update_ime();
     }
   }
 
@@ -247,8 +270,14 @@ impl Window {
     self.context.layout_ready();
   }
 
+  /// Replace the clock of the window, see [`WindowCtx::set_clock`].
+  #[inline]
+  pub fn set_clock(&self, clock: impl Clock + 'static) { self.context.set_clock(clock) }
+
   pub(crate) fn need_draw(&self) -> bool {
-    self.widget_tree.is_dirty() || self.context.has_actived_animate()
+    self.widget_tree.is_dirty()
+      || self.context.has_actived_animate()
+      || self.dispatcher.is_focus_changing()
   }
 
   pub fn new<W, P>(wnd: W, p_backend: P, root: Widget, context: AppContext) -> Self
@@ -273,6 +302,16 @@ impl Window {
     }
   }
 
+  fn update_ime(&mut self) {
+    let ImeRequest { allowed, pos } = self.context.take_ime_request();
+    if let Some(allowed) = allowed {
+      self.raw_window.set_ime_allowed(allowed);
+    }
+    if let Some(pos) = pos {
+      self.raw_window.set_ime_pos(pos);
+    }
+  }
+
   fn on_resize(&mut self, size: DeviceSize) {
     self.painter.finish();
     self.widget_tree.mark_dirty(self.widget_tree.root());
@@ -384,12 +423,19 @@ impl RawWindow for MockRawWindow {
   fn set_cursor(&mut self, cursor: CursorIcon) { self.cursor = Some(cursor); }
   fn request_redraw(&self) {}
   fn scale_factor(&self) -> f64 { 1. }
+  fn set_ime_allowed(&mut self, _: bool) {}
+  fn set_ime_pos(&mut self, _: Point) {}
 }
 
 impl Window {
   pub fn default_mock(root: Widget, size: Option<Size>) -> Self {
     let size = size.unwrap_or_else(|| Size::new(1024., 1024.));
-    Self::mock_window(root, size, <_>::default())
+    // mock window not share the clipboard with the system.
+    let ctx = AppContext {
+      clipboard: Rc::new(RefCell::new(MockClipboard::default())),
+      ..<_>::default()
+    };
+    Self::mock_window(root, size, ctx)
   }
 
   pub fn mock_window(root: Widget, size: Size, ctx: AppContext) -> Self {
@@ -7,7 +7,8 @@ use rayon::iter::ParallelIterator;
 use rayon::prelude::ParallelSliceMut;
 use ribir_algo::{FrameCache, Resource, ShareResource};
 use ribir_painter::{
-  Brush, ClipInstruct, PaintCommand, PaintInstruct, PaintPath, Path, PathStyle, TileMode, Transform,
+  Brush, ClipInstruct, Color, PaintCommand, PaintInstruct, PaintPath, Path, PathStyle, TileMode,
+  Transform,
 };
 use ribir_text::{
   font_db::ID,
@@ -173,15 +174,16 @@ impl Tessellator {
     cmd: &PaintInstruct,
     render: &mut R,
   ) -> (Primitive, PrimitiveType) {
+    let color_prim = |color: &Color| {
+      let c = ColorPrimitive::new(
+        color.into_f32_components(),
+        cmd.transform.clone().to_arrays(),
+        cmd.opacity,
+      );
+      (c.into(), PrimitiveType::Color)
+    };
     match &cmd.brush {
-      Brush::Color(color) => {
-        let c = ColorPrimitive::new(
-          color.into_f32_components(),
-          cmd.transform.clone().to_arrays(),
-          cmd.opacity,
-        );
-        (c.into(), PrimitiveType::Color)
-      }
+      Brush::Color(color) => color_prim(color),
       Brush::Image { img, tile_mode } => {
         let mut id = ATLAS_ID;
         let rect = self.atlas.store_image(img).unwrap_or_else(|_| {
@@ -215,7 +217,13 @@ impl Tessellator {
         let t = TexturePrimitive::new([x, y, w, h], factor, cmd.transform.to_arrays(), cmd.opacity);
         (t.into(), PrimitiveType::Texture { id })
       }
-      Brush::Gradient => todo!(),
+      // todo: paint the geometry of the gradient, fill with its first stop before
+      // that.
+      Brush::Gradient(gradient) => {
+        let first = gradient.stops.first();
+        color_prim(first.map_or(&Color::TRANSPARENT, |s| &s.color))
+      }
+      Brush::CrossFade { .. } => unreachable!("the painter splits a cross-fade brush into layers."),
     }
   }
 
@@ -588,7 +596,9 @@ mod tests {
   };
 
   use crate::TriangleLists;
-  use ribir_painter::{Color, DeviceSize, Painter, Point, Radius, Rect, Size};
+  use ribir_painter::{
+    Color, DeviceSize, Gradient, GradientStop, Painter, Point, Radius, Rect, Size,
+  };
 
   use ribir_text::{font_db::FontDB, shaper::TextShaper, TypographyStore};
   extern crate test;
@@ -699,6 +709,45 @@ mod tests {
     assert_eq!(&render_data, &[true, false, true, false]);
   }
 
+  #[test]
+  fn gradient_fill_with_first_stop() {
+    let mut tess = tessellator();
+    let mut painter = default_painter();
+    let stop = |offset, color| GradientStop { offset, color };
+    let gradient = Gradient {
+      stops: vec![stop(0., Color::RED), stop(1., Color::BLUE)],
+    };
+    painter
+      .set_brush(gradient)
+      .circle(Point::new(10., 10.), 5.);
+    painter.fill();
+    painter
+      .set_brush(Gradient::default())
+      .rect(&Rect::new(Point::new(0., 0.), Size::new(10., 10.)));
+    painter.fill();
+
+    let mut colors = vec![];
+    tess.tessellate(&painter.finish(), &mut |data: TriangleLists| {
+      assert!(
+        data
+          .commands
+          .iter()
+          .all(|cmd| matches!(cmd, DrawTriangles::Color(_)))
+      );
+      colors.extend(
+        data
+          .primitives
+          .iter()
+          .map(|p| unsafe { p.color_primitive.color }),
+      );
+    });
+
+    assert_eq!(
+      colors,
+      [Color::RED, Color::TRANSPARENT].map(|c| c.into_f32_components())
+    );
+  }
+
   #[test]
   fn large_image_cannot_batch() {
     let mut tess = tessellator();
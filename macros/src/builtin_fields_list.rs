@@ -27,12 +27,203 @@ builtin! {
     fn pointer_move_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
   }
 
+  PointerDownCaptureListener {
+    #[doc="specify the event handler for the pointer down event in the capture phase, it's \
+    dispatched from the root to the target before the event bubbles."]
+    on_pointer_down_capture: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer down event in the capture phase"]
+    fn pointer_down_capture_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  PointerUpCaptureListener {
+    #[doc="specify the event handler for the pointer up event in the capture phase."]
+    on_pointer_up_capture: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer up event in the capture phase"]
+    fn pointer_up_capture_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  PointerMoveCaptureListener {
+    #[doc="specify the event handler for the pointer move event in the capture phase."]
+    on_pointer_move_capture: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer move event in the capture phase"]
+    fn pointer_move_capture_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  PointerCancelListener {
+    #[doc="specify the event handler to process pointer cancel event."]
+    on_pointer_cancel: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer cancel event"]
+    fn pointer_cancel_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  PanStartListener {
+    #[doc="specify the event handler for the pan start event, \
+    a pressed pointer starts to move over the slop."]
+    on_pan_start: impl FnMut(&mut PanEvent),
+    #[doc= "return an observable stream of the pan start event"]
+    fn pan_start_stream(&self) -> MutRefItemSubject<'static, PanEvent, ()>,
+  }
+
+  PanUpdateListener {
+    #[doc="specify the event handler for the pan update event, the panning pointer moves."]
+    on_pan_update: impl FnMut(&mut PanEvent),
+    #[doc= "return an observable stream of the pan update event"]
+    fn pan_update_stream(&self) -> MutRefItemSubject<'static, PanEvent, ()>,
+  }
+
+  PanEndListener {
+    #[doc="specify the event handler for the pan end event, the panning pointer is released."]
+    on_pan_end: impl FnMut(&mut PanEvent),
+    #[doc= "return an observable stream of the pan end event"]
+    fn pan_end_stream(&self) -> MutRefItemSubject<'static, PanEvent, ()>,
+  }
+
+  SwipeListener {
+    #[doc="specify the event handler for the swipe event, \
+    the panning pointer is released at a high velocity."]
+    on_swipe: impl FnMut(&mut PanEvent),
+    #[doc= "return an observable stream of the swipe event"]
+    fn swipe_stream(&self) -> MutRefItemSubject<'static, PanEvent, ()>,
+  }
+
+  LongPressListener {
+    #[doc="specify the event handler for the long press event, \
+    a pointer keeps pressed without moving for a while."]
+    on_long_press: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the long press event"]
+    fn long_press_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  SingleTapListener {
+    #[doc="specify the event handler for the single tap event, \
+    a tap that not followed by a second tap, it's fired after the double tap duration expired."]
+    on_single_tap: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the single tap event"]
+    fn single_tap_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  ScaleStartListener {
+    #[doc="specify the event handler for the scale start event, the second pointer is pressed."]
+    on_scale_start: impl FnMut(&mut ScaleEvent),
+    #[doc= "return an observable stream of the scale start event"]
+    fn scale_start_stream(&self) -> MutRefItemSubject<'static, ScaleEvent, ()>,
+  }
+
+  ScaleUpdateListener {
+    #[doc="specify the event handler for the scale update event, \
+    one of the two scaling pointers moves, the rotation of the pointers is in the event too."]
+    on_scale_update: impl FnMut(&mut ScaleEvent),
+    #[doc= "return an observable stream of the scale update event"]
+    fn scale_update_stream(&self) -> MutRefItemSubject<'static, ScaleEvent, ()>,
+  }
+
+  ScaleEndListener {
+    #[doc="specify the event handler for the scale end event, \
+    one of the two scaling pointers is released."]
+    on_scale_end: impl FnMut(&mut ScaleEvent),
+    #[doc= "return an observable stream of the scale end event"]
+    fn scale_end_stream(&self) -> MutRefItemSubject<'static, ScaleEvent, ()>,
+  }
+
+  DragEnterListener {
+    #[doc="specify the event handler for the drag enter event, \
+    the dragging pointer enters the drop target."]
+    on_drag_enter: impl FnMut(&mut DragEvent),
+    #[doc= "return an observable stream of the drag enter event"]
+    fn drag_enter_stream(&self) -> MutRefItemSubject<'static, DragEvent, ()>,
+  }
+
+  DragOverListener {
+    #[doc="specify the event handler for the drag over event, \
+    the dragging pointer is over the drop target."]
+    on_drag_over: impl FnMut(&mut DragEvent),
+    #[doc= "return an observable stream of the drag over event"]
+    fn drag_over_stream(&self) -> MutRefItemSubject<'static, DragEvent, ()>,
+  }
+
+  DragLeaveListener {
+    #[doc="specify the event handler for the drag leave event, \
+    the dragging pointer leaves the drop target."]
+    on_drag_leave: impl FnMut(&mut DragEvent),
+    #[doc= "return an observable stream of the drag leave event"]
+    fn drag_leave_stream(&self) -> MutRefItemSubject<'static, DragEvent, ()>,
+  }
+
+  DropListener {
+    #[doc="specify the event handler for the drop event, the dragging pointer releases over the \
+    drop target that accepts the drag."]
+    on_drop: impl FnMut(&mut DragEvent),
+    #[doc= "return an observable stream of the drop event"]
+    fn drop_stream(&self) -> MutRefItemSubject<'static, DragEvent, ()>,
+  }
+
+  FileHoverListener {
+    #[doc="specify the event handler for the file hover event, a file is dragged over the window."]
+    on_file_hover: impl FnMut(&mut FileDragEvent),
+    #[doc= "return an observable stream of the file hover event"]
+    fn file_hover_stream(&self) -> MutRefItemSubject<'static, FileDragEvent, ()>,
+  }
+
+  FileHoverCancelListener {
+    #[doc="specify the event handler for the file hover cancel event, the hovered files leave the \
+    window without dropping."]
+    on_file_hover_cancel: impl FnMut(&mut FileDragEvent),
+    #[doc= "return an observable stream of the file hover cancel event"]
+    fn file_hover_cancel_stream(&self) -> MutRefItemSubject<'static, FileDragEvent, ()>,
+  }
+
+  FileDropListener {
+    #[doc="specify the event handler for the file drop event, a file is dropped on the window."]
+    on_file_drop: impl FnMut(&mut FileDragEvent),
+    #[doc= "return an observable stream of the file drop event"]
+    fn file_drop_stream(&self) -> MutRefItemSubject<'static, FileDragEvent, ()>,
+  }
+
+  ActionListener {
+    #[doc="specify the event handler for the action event, a shortcut bound to an action is \
+    pressed."]
+    on_action: impl FnMut(&mut ActionEvent),
+    #[doc= "return an observable stream of the action event"]
+    fn action_stream(&self) -> MutRefItemSubject<'static, ActionEvent, ()>,
+  }
+
+  PointerEnterListener {
+    #[doc="specify the event handler when pointer enter this widget."]
+    on_pointer_enter: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer enter event"]
+    fn pointer_enter_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  PointerLeaveListener {
+    #[doc="specify the event handler when pointer leave this widget."]
+    on_pointer_leave: impl FnMut(&mut PointerEvent),
+    #[doc= "return an observable stream of the pointer leave event"]
+    fn pointer_leave_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
+  }
+
+  FocusNode {
+    #[doc="Indicates whether the widget should automatically get focus when the window loads."]
+    auto_focus: bool,
+    #[doc="indicates that widget can be focused, and where it participates in \
+          sequential keyboard navigation (usually with the Tab key, hence the name."]
+    tab_index: i16,
+  }
+
+  RequestFocus{
+    #[doc="request the this node to be focused."]
+    fn request_focus(&self),
+    #[doc="removes the focus from this node."]
+    fn unfocus(&self),
+  }
+
   TapListener {
     #[doc="specify the event handler for the pointer tap event."]
     on_tap: impl FnMut(&mut PointerEvent),
     #[doc="specify the event handler for the pointer double tap event."]
     on_double_tap: Box<dyn for<'r> FnMut(&'r mut PointerEvent)>,
     #[doc="specify the event handler for the pointer triple tap event."]
+    on_triple_tap: Box<dyn for<'r> FnMut(&'r mut PointerEvent)>,
+    #[doc="deprecated alias of `on_triple_tap`, will be removed in a future version."]
     on_tripe_tap: Box<dyn for<'r> FnMut(&'r mut PointerEvent)>,
     #[doc="specify the event handler for the pointer `x` times tap event."]
     on_x_times_tap: (usize, Box<dyn for<'r> FnMut(&'r mut PointerEvent)>),
@@ -49,7 +240,7 @@ builtin! {
       &mut PointerEvent,
     >,
 
-    #[doc="Return an observable stream of tripe tap event"]
+    #[doc="Return an observable stream of triple tap event"]
     fn triple_tap_stream(
       &self,
     ) -> FilterMapOp<
@@ -70,42 +261,6 @@ builtin! {
     >,
   }
 
-  PointerCancelListener {
-    #[doc="specify the event handler to process pointer cancel event."]
-    on_pointer_cancel: impl FnMut(&mut PointerEvent),
-    #[doc= "return an observable stream of the pointer cancel event"]
-    fn pointer_cancel_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
-  }
-
-  PointerEnterListener {
-    #[doc="specify the event handler when pointer enter this widget."]
-    on_pointer_enter: impl FnMut(&mut PointerEvent),
-    #[doc= "return an observable stream of the pointer enter event"]
-    fn pointer_enter_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
-  }
-
-  PointerLeaveListener {
-    #[doc="specify the event handler when pointer leave this widget."]
-    on_pointer_leave: impl FnMut(&mut PointerEvent),
-    #[doc= "return an observable stream of the pointer leave event"]
-    fn pointer_leave_stream(&self) -> MutRefItemSubject<'static, PointerEvent, ()>,
-  }
-
-  FocusNode {
-    #[doc="Indicates whether the widget should automatically get focus when the window loads."]
-    auto_focus: bool,
-    #[doc="indicates that widget can be focused, and where it participates in \
-          sequential keyboard navigation (usually with the Tab key, hence the name."]
-    tab_index: i16,
-  }
-
-  RequestFocus{
-    #[doc="request the this node to be focused."]
-    fn request_focus(&self),
-    #[doc="removes the focus from this node."]
-    fn unfocus(&self),
-  }
-
   FocusListener {
     #[doc="specify the event handler to process focus event."]
     on_focus: impl FnMut(&mut FocusEvent),
@@ -137,6 +292,9 @@ builtin! {
   HasFocus {
     #[doc="return if the widget has focus."]
     fn has_focus(&self) -> bool,
+    #[doc="return if the widget has focus and the focus is moved by the keyboard, the focus ring \
+    should be drawn only if it's true."]
+    fn focus_visible(&self) -> bool,
   }
 
   KeyDownListener {
@@ -153,6 +311,21 @@ builtin! {
     fn key_up_stream(&self) -> MutRefItemSubject<'static, KeyboardEvent, ()>,
   }
 
+  KeyDownCaptureListener {
+    #[doc="specify the event handler when keyboard press down in the capture phase, it's \
+    dispatched from the root to the focused widget before the event bubbles."]
+    on_key_down_capture: impl FnMut(&mut KeyboardEvent),
+    #[doc= "return an observable stream of the key down event in the capture phase"]
+    fn key_down_capture_stream(&self) -> MutRefItemSubject<'static, KeyboardEvent, ()>,
+  }
+
+  KeyUpCaptureListener {
+    #[doc="specify the event handler when a key is released in the capture phase."]
+    on_key_up_capture: impl FnMut(&mut KeyboardEvent),
+    #[doc= "return an observable stream of the key up event in the capture phase"]
+    fn key_up_capture_stream(&self) -> MutRefItemSubject<'static, KeyboardEvent, ()>,
+  }
+
   CharListener {
     #[doc="specify the event handler when received a unicode character."]
     on_char: impl FnMut(&mut CharEvent),
@@ -160,6 +333,13 @@ builtin! {
     fn char_stream(&self) -> MutRefItemSubject<'static, CharEvent, ()>,
   }
 
+  ImePreeditListener {
+    #[doc="specify the event handler when the composing text of the input method changed."]
+    on_ime_preedit: impl FnMut(&mut ImePreeditEvent),
+    #[doc= "return an observable stream of the input method preedit event"]
+    fn ime_preedit_stream(&self) -> MutRefItemSubject<'static, ImePreeditEvent, ()>,
+  }
+
   WheelListener {
     #[doc="specify the event handler when user moving a mouse wheel or similar input device."]
     on_wheel: impl FnMut(&mut WheelEvent),
@@ -217,6 +397,14 @@ builtin! {
     cursor: CursorIcon
   }
 
+  Draggable {
+    #[doc="make the widget draggable, every drag carries a payload created from the data."]
+    drag_data: DragData,
+    #[doc="the widget painted following the pointer during the drag, \
+    instead of the dragged widget."]
+    drag_feedback: DragFeedback,
+  }
+
   Margin {
     #[doc="expand space around widget wrapped."]
     margin: impl EdgeInsets,
@@ -293,4 +481,16 @@ builtin! {
     #[doc= "return an observable stream of the widget disposed event"]
     disposed_stream: LifecycleSubject,
   }
+
+  LayoutTransition {
+    #[doc="animate the painted position and size of the widget from its old layout rect to the \
+    new one when its parent places it at a different rect."]
+    auto_layout_transition: Rc<Box<dyn Roc>>,
+  }
+
+  DelayDrop {
+    #[doc="delay the drop of the widget until the field is `true` after it's disposed, the \
+    widget keeps painted but not hit-testable, use it to run an exit animation."]
+    delay_drop_until: bool,
+  }
 }
@@ -316,7 +316,10 @@ impl Item {
           ));
         });
       }
-      Item::Transition(d) | Item::Animate(d) => {
+      Item::Transition(d)
+      | Item::Animate(d)
+      | Item::KeyframeAnimate(d)
+      | Item::AnimateGroup(d) => {
         if let DesugaredObj::Obj(obj) = d.desugar(desugared) {
           let warning = DeclareWarning::DefObjWithoutId(obj.span().unwrap());
           desugared.warnings.push(warning)
@@ -22,6 +22,8 @@ pub mod kw {
   syn::custom_keyword!(DynWidget);
   syn::custom_keyword!(id);
   syn::custom_keyword!(Animate);
+  syn::custom_keyword!(KeyframeAnimate);
+  syn::custom_keyword!(AnimateGroup);
   syn::custom_keyword!(Transition);
   syn::custom_keyword!(transition);
   syn::custom_punctuation!(AssignColon, :=);
@@ -113,6 +115,8 @@ pub enum Item {
   TransProps(TransProps),
   Transition(DeclareSingle),
   Animate(DeclareSingle),
+  KeyframeAnimate(DeclareSingle),
+  AnimateGroup(DeclareSingle),
 }
 
 pub struct TransProps {
@@ -171,6 +175,10 @@ impl Parse for MacroSyntax {
       let lk = input.lookahead1();
       if lk.peek(kw::Animate) {
         items.push(Item::Animate(input.parse()?));
+      } else if lk.peek(kw::KeyframeAnimate) {
+        items.push(Item::KeyframeAnimate(input.parse()?));
+      } else if lk.peek(kw::AnimateGroup) {
+        items.push(Item::AnimateGroup(input.parse()?));
       } else if lk.peek(kw::Transition) {
         items.push(Item::Transition(input.parse()?));
       } else if lk.peek(kw::transition) {
@@ -15,7 +15,7 @@ pub use style::*;
 mod svg_parser;
 pub use ribir_text;
 pub use ribir_text::{typography::Overflow, *};
-pub use svg_parser::SvgPaths;
+pub use svg_parser::{SvgPaths, SvgRenderPath};
 
 /// The tag for device unit system to prevent mixing values from different
 /// system.
@@ -307,15 +307,37 @@ impl Painter {
     let transform = self.current_state().transform;
     let alpha = self.alpha();
     let brush = self.current_state().brush.clone();
-    self.commands.push(PaintCommand::Paint(PaintInstruct {
+    self.push_paint(PaintInstruct {
       opacity: alpha,
       path: PaintPath::Path(path.into()),
       transform,
       brush,
-    }));
+    });
     self
   }
 
+  /// Push a paint command, a cross-fade brush is painted as two layers with
+  /// the opacity of their proportion.
+  fn push_paint(&mut self, instruct: PaintInstruct) {
+    if let Brush::CrossFade { from, to, factor } = instruct.brush {
+      let PaintInstruct { opacity, path, transform, .. } = instruct;
+      self.push_paint(PaintInstruct {
+        opacity: opacity * (1. - factor),
+        path: path.clone(),
+        transform,
+        brush: *from,
+      });
+      self.push_paint(PaintInstruct {
+        opacity: opacity * factor,
+        path,
+        transform,
+        brush: *to,
+      });
+    } else {
+      self.commands.push(PaintCommand::Paint(instruct));
+    }
+  }
+
   /// Strokes (outlines) the current path with the current brush and line width.
   pub fn stroke(&mut self) -> &mut Self {
     let builder = std::mem::take(&mut self.path_builder);
@@ -340,9 +362,19 @@ impl Painter {
     foreground: Brush,
     bounds: Option<Size>,
   ) -> &mut Self {
-    let transform = self.current_state().transform;
     let visual_glyphs = typography_with_text_style(&self.typography_store, text, style, bounds);
-    self.commands.push(PaintCommand::Paint(PaintInstruct {
+    self.paint_glyphs(&visual_glyphs, style, foreground)
+  }
+
+  /// Paint the glyphs which already typography with the `style`.
+  pub fn paint_glyphs(
+    &mut self,
+    visual_glyphs: &VisualGlyphs,
+    style: &TextStyle,
+    foreground: Brush,
+  ) -> &mut Self {
+    let transform = self.current_state().transform;
+    self.push_paint(PaintInstruct {
       path: PaintPath::Text {
         font_size: style.font_size,
         glyphs: visual_glyphs.pixel_glyphs().collect(),
@@ -351,7 +383,7 @@ impl Painter {
       opacity: self.alpha(),
       brush: foreground,
       transform,
-    }));
+    });
 
     self
   }
@@ -388,7 +420,7 @@ impl Painter {
       bounds,
     );
 
-    let cmd = PaintCommand::Paint(PaintInstruct {
+    let instruct = PaintInstruct {
       opacity: self.alpha(),
       path: PaintPath::Text {
         font_size,
@@ -397,8 +429,8 @@ impl Painter {
       },
       transform,
       brush: brush.clone(),
-    });
-    self.commands.push(cmd);
+    };
+    self.push_paint(instruct);
     self
   }
 
@@ -688,4 +720,39 @@ mod test {
       layer.get_transform()
     );
   }
+
+  #[test]
+  fn cross_fade_paint_two_layers() {
+    let mut painter = Painter::new(
+      1.,
+      TypographyStore::new(
+        <_>::default(),
+        <_>::default(),
+        TextShaper::new(<_>::default()),
+      ),
+      Size::new(512., 512.),
+    );
+    painter
+      .apply_alpha(0.5)
+      .set_brush(Brush::CrossFade {
+        from: Box::new(Color::RED.into()),
+        to: Box::new(Color::BLUE.into()),
+        factor: 0.2,
+      })
+      .rect(&Rect::from_size(Size::new(10., 10.)))
+      .fill();
+
+    let layers: Vec<_> = painter
+      .finish()
+      .into_iter()
+      .map(|cmd| match cmd {
+        PaintCommand::Paint(PaintInstruct { opacity, brush, .. }) => (opacity, brush),
+        _ => unreachable!(),
+      })
+      .collect();
+    assert_eq!(
+      layers,
+      vec![(0.4, Color::RED.into()), (0.1, Color::BLUE.into())]
+    );
+  }
 }
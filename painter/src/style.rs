@@ -68,7 +68,30 @@ pub enum Brush {
     img: ShallowImage,
     tile_mode: TileMode,
   },
-  Gradient, // todo,
+  Gradient(Gradient),
+  /// Cross-fade from one brush to another, the painter paints the path twice,
+  /// with the `from` brush in the opacity of `1 - factor` and the `to` brush in
+  /// the opacity of `factor`.
+  CrossFade {
+    from: Box<Brush>,
+    to: Box<Brush>,
+    factor: f32,
+  },
+}
+
+/// The color stops of a gradient brush.
+// todo: the geometry of the gradient and paint it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Gradient {
+  /// The stops sorted by their offset.
+  pub stops: Vec<GradientStop>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+  /// The offset of the stop, in the range of `[0, 1]`.
+  pub offset: f32,
+  pub color: Color,
 }
 
 impl Brush {
@@ -106,6 +129,11 @@ impl From<Color> for Brush {
   fn from(c: Color) -> Self { Brush::Color(c) }
 }
 
+impl From<Gradient> for Brush {
+  #[inline]
+  fn from(g: Gradient) -> Self { Brush::Gradient(g) }
+}
+
 impl Default for Brush {
   #[inline]
   fn default() -> Self { Color::BLACK.into() }
@@ -30,7 +30,7 @@ fn main() {
         easing: easing::LINEAR,
         repeat: Some(f32::MAX),
       },
-      prop: prop!(path_widget.paths, PathPaintKit::paths_lerp_fn(prop!(path_widget.paths))),
+      prop: prop!(path_widget.paths, PathPaintKit::paths_lerp_fn()),
       from: vec![
         PathPaintKit {
           path: Path::rect(&Rect::zero(), style),
@@ -107,6 +107,9 @@ impl TodoMVP {
     let mount_task_cnt = mount_task_cnt.clone_stateful();
     widget! {
       states { this, mount_task_cnt, mount_idx: Stateful::new(0) }
+      init ctx => {
+        let ease_in_out = transitions::EASE_IN_OUT.of(ctx);
+      }
       KeyWidget {
         id: key,
         key: Key::from(task.id),
@@ -114,6 +117,7 @@ impl TodoMVP {
         ListItem {
           id: item,
           transform: Transform::default(),
+          auto_layout_transition: ease_in_out,
           on_mounted: move |_| {
             if key.is_enter() {
               *mount_idx = *mount_task_cnt;
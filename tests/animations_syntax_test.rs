@@ -86,3 +86,34 @@ fn listener_trigger() {
 
   assert!(*animate_state.state_ref());
 }
+
+#[test]
+fn keyframes_trigger() {
+  let animate_state = Stateful::new(false);
+
+  let w = widget! {
+    states { animate_state:  animate_state.clone() }
+    init ctx => {
+      let linear_transition = transitions::LINEAR.of(ctx);
+    }
+    SizedBox {
+      id: sized_box,
+      size: Size::new(100., 100.),
+      on_wheel: move |_| pulse.run()
+    }
+    KeyframeAnimate {
+      id: pulse,
+      transition: linear_transition,
+      prop: prop!(sized_box.size),
+      keyframes: vec![KeyFrame::new(0.5, Size::new(120., 120.))],
+    }
+    finally {
+      watch!(pulse.is_running())
+        .subscribe(move |v| *animate_state = v);
+    }
+  };
+
+  wheel_widget(w);
+
+  assert!(*animate_state.state_ref());
+}
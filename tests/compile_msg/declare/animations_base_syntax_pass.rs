@@ -26,6 +26,26 @@ fn main() {
     }
   };
 
+  let _keyframes = widget! {
+    init ctx => {
+      let linear = transitions::LINEAR.of(ctx);
+    }
+    SizedBox {
+      id: sized_box,
+      size: Size::zero(),
+      on_tap: move |_| pulse.run(),
+    }
+    KeyframeAnimate {
+      id: pulse,
+      transition: linear,
+      prop: prop!(sized_box.size),
+      keyframes: vec![
+        KeyFrame::new(0.5, Size::new(10., 10.)).with_easing(easing::EASE_IN),
+        KeyFrame::new(1., Size::zero()),
+      ],
+    }
+  };
+
   let _implicit_from_state = widget! {
     SizedBox {
       id: sized_box,
@@ -60,4 +80,36 @@ fn main() {
       duration: std::time::Duration::from_millis(200),
     }
   };
+
+  let _group = widget! {
+    init ctx => {
+      let linear = transitions::LINEAR.of(ctx);
+    }
+    SizedBox {
+      id: sized_box,
+      size: Size::zero(),
+      opacity: 1.,
+      on_tap: move |_| group.run(),
+    }
+    Animate {
+      id: grow,
+      transition: linear.clone(),
+      prop: prop!(sized_box.size),
+      from: Size::new(10., 10.),
+    }
+    Animate {
+      id: fade,
+      transition: linear,
+      prop: prop!(sized_box.opacity),
+      from: 0.,
+    }
+    AnimateGroup {
+      id: group,
+      mode: GroupMode::Stagger(std::time::Duration::from_millis(50)),
+      animations: Vec::from([
+        Box::new(grow.clone_stateful()) as Box<dyn AnimateCtrl>,
+        Box::new(fade.clone_stateful()),
+      ]),
+    }
+  };
 }
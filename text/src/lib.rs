@@ -24,6 +24,9 @@ pub use text_writer::{CharacterCursor, ControlChar, TextWriter};
 mod grapheme_cursor;
 pub use grapheme_cursor::GraphemeCursor;
 
+mod word_boundary;
+pub use word_boundary::{next_word_boundary, prev_word_boundary, word_range};
+
 pub mod unicode_help;
 
 /// Unit for convert between pixel and em.
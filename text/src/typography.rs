@@ -7,9 +7,13 @@ use unicode_script::{Script, UnicodeScript};
 
 use crate::{Em, FontSize, Glyph, Pixel, TextAlign};
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Overflow {
+  /// Glyphs over the bounds of the line are clipped.
   Clip,
+  /// Break the paragraph into multi lines when it over the bounds of the line,
+  /// prefer to break after a white space.
+  AutoWrap,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -52,6 +56,9 @@ pub trait InlineCursor {
 
 #[derive(Default)]
 pub struct VisualLine {
+  /// The index of the paragraph this line belong to, a paragraph may be
+  /// placed in multi lines if it's wrapped.
+  pub para: usize,
   pub x: Em,
   pub y: Em,
   pub height: Em,
@@ -81,6 +88,9 @@ pub struct TypographyMan<Inputs> {
   inline_cursor: Em,
   visual_lines: Vec<VisualLine>,
   over_bounds: bool,
+  para_cnt: usize,
+  /// The glyph index in the current line that can break line before it.
+  break_at: Option<usize>,
 }
 
 impl<Inputs, Runs> TypographyMan<Inputs>
@@ -96,6 +106,8 @@ where
       inline_cursor: Em::zero(),
       visual_lines: vec![],
       over_bounds: false,
+      para_cnt: 0,
+      break_at: None,
     }
   }
 
@@ -201,7 +213,9 @@ where
 
   /// consume paragraph and return if early break because over boundary.
   fn consume_paragraph(&mut self, p: InputParagraph<Runs>) -> bool {
-    self.visual_lines.push(<_>::default());
+    self.visual_lines.push(VisualLine { para: self.para_cnt, ..<_>::default() });
+    self.para_cnt += 1;
+    self.break_at = None;
 
     if self.cfg.line_dir.is_horizontal() {
       let mut cursor = VInlineCursor { pos: self.inline_cursor };
@@ -239,7 +253,7 @@ where
     run: &Runs::Item,
     inner_cursor: &mut impl InlineCursor,
   ) {
-    if self.cfg.text_align != Some(TextAlign::Center) {
+    if self.cfg.text_align != Some(TextAlign::Center) && self.cfg.overflow != Overflow::AutoWrap {
       let bounds = if self.cfg.line_dir.is_horizontal() {
         self.cfg.bounds.height
       } else {
@@ -275,14 +289,81 @@ where
       let over_boundary = cursor.advance_glyph(&mut at, line_offset, text);
       at.cluster = g.cluster + base;
       self.push_glyph(at);
+      let is_whitespace = matches!(
+        text[g.cluster as usize..].chars().next(),
+        Some(c) if c.is_whitespace()
+      );
       if over_boundary {
         self.over_bounds = true;
         break;
       }
+      if is_whitespace {
+        // white space can hang at the end of line, and we prefer to break after it.
+        self.break_at = Some(self.visual_lines.last().unwrap().glyphs.len());
+      } else if self.cfg.overflow == Overflow::AutoWrap && self.is_inline_over(cursor.position())
+      {
+        self.wrap_line(cursor);
+      }
     }
     self.inline_cursor = cursor.position();
   }
 
+  fn is_inline_over(&self, pos: Em) -> bool {
+    if self.cfg.line_dir.is_horizontal() {
+      self.cfg.bounds.height < pos
+    } else {
+      self.cfg.bounds.width < pos
+    }
+  }
+
+  /// Move the glyphs after the last break position of current line to a new
+  /// line, if no break position, break before the last glyph.
+  fn wrap_line(&mut self, cursor: &mut impl InlineCursor) {
+    let is_horizontal = self.cfg.line_dir.is_horizontal();
+    let line = self.visual_lines.last_mut().unwrap();
+    let at = self
+      .break_at
+      .filter(|at| *at < line.glyphs.len())
+      .unwrap_or(line.glyphs.len() - 1);
+    // A glyph wider than the line, can't break it.
+    if at == 0 {
+      return;
+    }
+
+    let mut glyphs = line.glyphs.split_off(at);
+    let start = if is_horizontal {
+      glyphs[0].y_offset
+    } else {
+      glyphs[0].x_offset
+    };
+    glyphs.iter_mut().for_each(|g| {
+      if is_horizontal {
+        g.y_offset -= start;
+      } else {
+        g.x_offset -= start;
+      }
+    });
+    let mut new_line = VisualLine { para: line.para, glyphs, ..<_>::default() };
+    if is_horizontal {
+      line.height = start;
+      new_line.width = line.width;
+    } else {
+      line.width = start;
+      new_line.height = line.height;
+    }
+    if let Some(line_height) = self.cfg.line_height {
+      if is_horizontal {
+        line.width = line_height;
+      } else {
+        line.height = line_height;
+      }
+    }
+
+    self.visual_lines.push(new_line);
+    self.break_at = None;
+    cursor.advance(Em::zero() - start);
+  }
+
   fn push_glyph(&mut self, g: Glyph<Em>) {
     let line = self.visual_lines.last_mut();
     line.unwrap().glyphs.push(g)
@@ -177,13 +177,14 @@ impl TypographyStore {
       // line width is not so important in clip mode, the cache can be use even with difference line
       // width. The wider one can use for the narrower one. S
       Overflow::Clip => Em::absolute(f32::MAX),
-      // For word wrap
-      //
-      // if line_dir.is_horizontal() {
-      //   bounds.width()
-      // } else {
-      //   bounds.height()
-      // };
+      Overflow::AutoWrap => {
+        let width = if line_dir.is_horizontal() {
+          cfg.bounds.height
+        } else {
+          cfg.bounds.width
+        };
+        width / font_size.into_em()
+      }
     };
 
     TypographyKey {
@@ -232,44 +233,43 @@ impl VisualGlyphs {
     )
   }
 
+  /// Return the nearest glyph position `(line, offset)` of the point, the
+  /// point is relative to the visual rect.
   pub fn nearest_glyph(&self, offset_x: f32, offset_y: f32) -> (usize, usize) {
-    let rc = self.visual_rect();
-
-    let mut bottom: Em = Pixel(rc.height().into()).into();
-
-    let x: Em = Pixel(offset_x.into()).into();
-    let y: Em = Pixel(offset_y.into()).into();
+    let lines = &self.visual_info.visual_lines;
+    let is_horizontal = self.visual_info.line_dir.is_horizontal();
+    let x = self.to_em_value(offset_x);
+    let y = self.to_em_value(offset_y);
+    let (inline, block) = if is_horizontal { (y, x) } else { (x, y) };
 
-    let mut iter = self
-      .visual_info
-      .visual_lines
+    let row = lines
       .iter()
-      .enumerate()
-      .rev()
-      .skip_while(move |(_, line)| {
-        bottom = bottom.max(line.height) - line.height;
-        y < bottom
-      });
-
-    if let Some((row, line)) = iter.next() {
+      .rposition(|l| if is_horizontal { l.x <= block } else { l.y <= block })
+      .unwrap_or(0);
+
+    lines.get(row).map_or((0, 0), |line| {
+      type GlyphValue = fn(&Glyph<Em>) -> Em;
+      let (inline, advance, offset): (Em, GlyphValue, GlyphValue) = if is_horizontal {
+        (inline - line.y, |g: &Glyph<Em>| g.y_advance, |g: &Glyph<Em>| g.y_offset)
+      } else {
+        (inline - line.x, |g: &Glyph<Em>| g.x_advance, |g: &Glyph<Em>| g.x_offset)
+      };
       let idx = line
         .glyphs
         .iter()
         .enumerate()
         .rev()
-        .find(|(_, g)| Em::zero() < g.x_advance && g.x_offset <= x)
+        .find(|(_, g)| Em::zero() < advance(g) && offset(g) <= inline)
         .map(|(i, g)| {
-          if x - g.x_offset >= g.x_offset + g.x_advance - x {
+          if inline - offset(g) >= offset(g) + advance(g) - inline {
             i + 1
           } else {
             i
           }
         })
         .unwrap_or(0);
-      return (row, idx);
-    }
-
-    (0, 0)
+      (row, idx)
+    })
   }
 
   pub fn position_by_cluster(&self, cluster: u32) -> (usize, usize) {
@@ -304,17 +304,21 @@ impl VisualGlyphs {
     }
 
     let visual_lines = &self.visual_info.visual_lines;
+    if visual_lines.is_empty() {
+      return (0, 0);
+    }
     let para = self
       .order_info
       .paras
       .partition_point(|p| p.range.end <= cluster as usize)
       .min(self.order_info.paras.len() - 1);
     let order_info = &self.order_info.paras[para];
-    let line = &visual_lines[para];
+    let lines = self.para_lines(para);
+    let last_line = lines.last().copied().unwrap_or(0);
     let locator = RangeLocator::from_unorder_ranges(order_info.runs.iter());
     if let Some(dst_run) = locator.range_index(cluster as usize) {
       let is_ltr = order_info.levels[order_info.runs[dst_run].start].is_ltr();
-      let offset = line.glyphs.partition_point(|glyph| {
+      let before_cluster = |glyph: &Glyph<Em>| {
         let glyph_run = locator.range_index(glyph.cluster as usize);
         let glyph_run = glyph_run.unwrap();
         if dst_run == glyph_run {
@@ -325,24 +329,92 @@ impl VisualGlyphs {
           }
         }
         glyph_run < dst_run
-      });
-      (para, offset)
+      };
+      // A paragraph may be wrapped to multi lines, the glyphs of these lines are continuous.
+      lines
+        .iter()
+        .find_map(|&idx| {
+          let glyphs = &visual_lines[idx].glyphs;
+          let offset = glyphs.partition_point(before_cluster);
+          (offset < glyphs.len()).then_some((idx, offset))
+        })
+        .unwrap_or_else(|| (last_line, visual_lines[last_line].glyphs.len()))
     } else {
-      (para, order_info.range.end)
+      (last_line, visual_lines[last_line].glyphs.len())
     }
   }
 
-  pub fn position_to_cluster(&self, para: usize, offset: usize) -> u32 {
+  pub fn position_to_cluster(&self, line: usize, offset: usize) -> u32 {
     let lines = &self.visual_info.visual_lines;
     let paras = &self.order_info.paras;
-    if para >= lines.len() {
-      return paras.last().map_or(0, |p| p.range.end as u32);
+    if line >= lines.len() {
+      paras.last().map_or(0, |p| p.range.end as u32)
     } else {
-      return lines[para]
-        .glyphs
-        .get(offset)
-        .map_or(paras[para].range.end as u32, |g| g.cluster);
+      lines[line].glyphs.get(offset).map_or_else(
+        || {
+          // the end of a wrapped line is the begin of its next line.
+          let para = lines[line].para;
+          self
+            .para_lines(para)
+            .into_iter()
+            .skip_while(|idx| *idx != line)
+            .nth(1)
+            .and_then(|next| lines[next].glyphs.first())
+            .map_or(paras[para].range.end as u32, |g| g.cluster)
+        },
+        |g| g.cluster,
+      )
+    }
+  }
+
+  /// Return the cluster where the visual line that contains `cluster` begin.
+  pub fn line_begin_cluster(&self, cluster: u32) -> u32 {
+    let (line, _) = self.position_by_cluster(cluster);
+    self.position_to_cluster(line, 0)
+  }
+
+  /// Return the cluster where the visual line that contains `cluster` end,
+  /// the line break or the white space that wrap the line is not include.
+  pub fn line_end_cluster(&self, cluster: u32) -> u32 {
+    let (line, _) = self.position_by_cluster(cluster);
+    let glyphs = &self.visual_info.visual_lines[line].glyphs;
+    match glyphs.last() {
+      Some(g) if self.is_break_glyph(g) => g.cluster,
+      _ => self.position_to_cluster(line, glyphs.len()),
+    }
+  }
+
+  /// The count of the visual lines.
+  #[inline]
+  pub fn line_count(&self) -> usize { self.visual_info.visual_lines.len() }
+
+  fn is_break_glyph(&self, g: &Glyph<Em>) -> bool {
+    use unicode_bidi::BidiClass;
+    matches!(
+      self.order_info.original_classes.get(g.cluster as usize),
+      Some(BidiClass::B | BidiClass::S | BidiClass::WS)
+    )
+  }
+
+  /// Return the index of lines that the paragraph placed, in the logic order.
+  fn para_lines(&self, para: usize) -> Vec<usize> {
+    let mut lines: Vec<_> = self
+      .visual_info
+      .visual_lines
+      .iter()
+      .enumerate()
+      .filter(|(_, l)| l.para == para)
+      .map(|(idx, _)| idx)
+      .collect();
+    if self.visual_info.line_dir.is_reverse() {
+      lines.reverse();
     }
+    lines
+  }
+
+  fn to_em_value(&self, v: f32) -> Em {
+    let em: Em = Pixel(v.into()).into();
+    Em::absolute(em.value() / self.scale)
   }
 
   pub fn glyph_rect(&self, mut para: usize, mut offset: usize) -> Rect<f32> {
@@ -375,8 +447,8 @@ impl VisualGlyphs {
       move |glyph| {
         let mut rc = Rect::new(
           Point::new(
-            self.to_pixel_value(glyph.x_offset),
-            self.to_pixel_value(glyph.y_offset),
+            self.to_pixel_value(glyph.x_offset + line.x),
+            self.to_pixel_value(glyph.y_offset + line.y),
           ),
           Size::new(
             self.to_pixel_value(glyph.x_advance),
@@ -400,11 +472,11 @@ impl VisualGlyphs {
     )
   }
 
-  pub fn line_height(&self, para: usize) -> f32 {
+  pub fn line_height(&self, line: usize) -> f32 {
     self
       .visual_info
       .visual_lines
-      .get(para)
+      .get(line)
       .map_or(0., |line| self.to_pixel_value(line.height))
   }
 
@@ -442,7 +514,10 @@ impl VisualGlyphs {
       let height = (line.height * self.scale).into();
       for glyph in &line.glyphs {
         if rg.contains(&(glyph.cluster as usize)) {
-          let glyph = self.scale_to_pixel_glyph(glyph);
+          let mut glyph = glyph.clone();
+          glyph.x_offset += line.x;
+          glyph.y_offset += line.y;
+          let glyph = self.scale_to_pixel_glyph(&glyph);
           let rc = Rect::new(
             Point::new(glyph.x_offset, glyph.y_offset),
             Size::new(glyph.x_advance, height),
@@ -795,4 +870,40 @@ mod tests {
     assert!((0, 27) == graphys.position_by_cluster(31));
     assert!((0, 8) == graphys.position_by_cluster(53));
   }
+
+  #[test]
+  fn auto_wrap() {
+    let cfg = TypographyCfg {
+      line_height: None,
+      letter_space: None,
+      text_align: None,
+      bounds: (Pixel::from(60.).into(), Em::MAX).into(),
+      line_dir: PlaceLineDirection::TopToBottom,
+      overflow: Overflow::AutoWrap,
+    };
+    let text = "Hello world!\nabc".into();
+    let visual = typography_text(text, FontSize::Pixel(14.0.into()), cfg);
+
+    assert_eq!(visual.line_count(), 3);
+    assert!(visual.visual_rect().width() <= 60.);
+    assert_eq!(visual.visual_rect().height(), 42.);
+
+    // wrap after the white space.
+    assert_eq!(visual.position_by_cluster(5), (0, 5));
+    assert_eq!(visual.position_by_cluster(6), (1, 0));
+    assert_eq!(visual.position_to_cluster(0, 6), 6);
+    assert_eq!(visual.glyph_rect(1, 0).min_y(), 14.);
+    assert_eq!(visual.glyph_rect(2, 0).min_y(), 28.);
+
+    assert_eq!(visual.line_begin_cluster(8), 6);
+    assert_eq!(visual.line_end_cluster(8), 12);
+    assert_eq!(visual.line_begin_cluster(2), 0);
+    assert_eq!(visual.line_end_cluster(2), 5);
+    assert_eq!(visual.line_end_cluster(14), 16);
+
+    let (line, offset) = visual.nearest_glyph(0., 20.);
+    assert_eq!(visual.position_to_cluster(line, offset), 6);
+    let (line, offset) = visual.nearest_glyph(100., 30.);
+    assert_eq!(visual.position_to_cluster(line, offset), 16);
+  }
 }
@@ -0,0 +1,66 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Return the byte offset of the end of the next word after `offset`, the
+/// white spaces between are skipped. The words are split by the Unicode word
+/// boundaries (UAX #29).
+pub fn next_word_boundary(text: &str, offset: usize) -> usize {
+  text
+    .split_word_bound_indices()
+    .map(|(start, word)| (start + word.len(), word))
+    .find(|(end, word)| offset < *end && !is_whitespace(word))
+    .map_or(text.len(), |(end, _)| end)
+}
+
+/// Return the byte offset of the begin of the previous word before `offset`,
+/// the white spaces between are skipped. The words are split by the Unicode
+/// word boundaries (UAX #29).
+pub fn prev_word_boundary(text: &str, offset: usize) -> usize {
+  text
+    .split_word_bound_indices()
+    .rev()
+    .find(|(start, word)| *start < offset && !is_whitespace(word))
+    .map_or(0, |(start, _)| start)
+}
+
+/// Return the byte range of the word that contains the `offset`, the word
+/// before it is selected if `offset` is the end of the text.
+pub fn word_range(text: &str, offset: usize) -> Range<usize> {
+  text
+    .split_word_bound_indices()
+    .map(|(start, word)| start..start + word.len())
+    .find(|rg| rg.contains(&offset) || rg.end == text.len())
+    .unwrap_or(offset..offset)
+}
+
+fn is_whitespace(word: &str) -> bool { word.chars().all(char::is_whitespace) }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn word_move() {
+    let text = "Hello, world!  你好";
+    assert_eq!(next_word_boundary(text, 0), 5);
+    assert_eq!(next_word_boundary(text, 5), 6);
+    assert_eq!(next_word_boundary(text, 6), 12);
+    assert_eq!(next_word_boundary(text, 13), 18);
+    assert_eq!(next_word_boundary(text, text.len()), text.len());
+
+    assert_eq!(prev_word_boundary(text, text.len()), 18);
+    assert_eq!(prev_word_boundary(text, 15), 12);
+    assert_eq!(prev_word_boundary(text, 8), 7);
+    assert_eq!(prev_word_boundary(text, 0), 0);
+  }
+
+  #[test]
+  fn word_at() {
+    let text = "Hello, world";
+    assert_eq!(word_range(text, 2), 0..5);
+    assert_eq!(word_range(text, 5), 5..6);
+    assert_eq!(word_range(text, 6), 6..7);
+    assert_eq!(word_range(text, text.len()), 7..12);
+    assert_eq!(word_range("", 0), 0..0);
+  }
+}
@@ -1,11 +1,23 @@
 mod caret;
 mod caret_state;
+mod edit_history;
 mod glyphs_helper;
 mod handle;
+mod preedit;
+mod selectable_text;
 mod selected_text;
+mod text_area;
 pub use caret_state::CaretState;
+pub use edit_history::{EditHistory, EditKind, EditRecord};
+pub use selectable_text::SelectableText;
+pub use text_area::TextArea;
 
-use self::{caret::Caret, glyphs_helper::GlyphsHelper, selected_text::SelectedText};
+use self::{
+  caret::Caret,
+  glyphs_helper::GlyphsHelper,
+  preedit::{Preedit, PreeditUnderline},
+  selected_text::SelectedText,
+};
 use crate::layout::{ConstrainedBox, Stack};
 use crate::prelude::Text;
 use ribir_core::{prelude::*, ticker::FrameMsg};
@@ -17,9 +29,43 @@ pub struct InputTheme {
   pub min_length: f32,
   pub select_background: Brush,
   pub caret_color: Brush,
+  pub keymap: EditKeymap,
 }
 impl CustomTheme for InputTheme {}
 
+/// The modifiers of the editing shortcuts of the text inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EditKeymap {
+  /// The modifier of the command shortcuts like copy, paste, undo and select
+  /// all. With the arrow keys, it moves the caret to the begin or end of the
+  /// line or the text.
+  pub shortcut: ModifiersState,
+  /// The modifier to move the caret or delete by word.
+  pub word: ModifiersState,
+}
+
+impl EditKeymap {
+  /// The conventional keymap of the current platform, `Command` for shortcuts
+  /// and `Option` for words on macOS, and `Ctrl` for both on others.
+  pub fn platform() -> Self {
+    if cfg!(target_os = "macos") {
+      Self {
+        shortcut: ModifiersState::LOGO,
+        word: ModifiersState::ALT,
+      }
+    } else {
+      Self {
+        shortcut: ModifiersState::CTRL,
+        word: ModifiersState::CTRL,
+      }
+    }
+  }
+}
+
+impl Default for EditKeymap {
+  fn default() -> Self { Self::platform() }
+}
+
 #[derive(Declare)]
 pub struct Input {
   #[declare(default = TypographyTheme::of(ctx).body_large.text.clone())]
@@ -28,6 +74,10 @@ pub struct Input {
   text: CowArc<str>,
   #[declare(skip)]
   caret: CaretState,
+  #[declare(skip)]
+  history: EditHistory,
+  #[declare(skip)]
+  preedit: Option<Preedit>,
   #[declare(default = InputTheme::of(ctx).min_length)]
   min_length: f32,
 }
@@ -46,6 +96,22 @@ impl Input {
     self.caret = caret;
     self.caret.valid(self.text.len());
   }
+
+  /// The edit history of the text, it can be used to add custom merge or clear
+  /// the history.
+  pub fn history(&self) -> &EditHistory { &self.history }
+
+  pub fn history_mut(&mut self) -> &mut EditHistory { &mut self.history }
+
+  /// Undo the last edit transaction, return if anything undone.
+  pub fn undo(&mut self) -> bool { handle::undo(self) }
+
+  /// Redo the last undone edit transaction, return if anything redone.
+  pub fn redo(&mut self) -> bool { handle::redo(self) }
+
+  fn display_text(&self) -> CowArc<str> {
+    preedit::display_text(&self.text, self.caret, self.preedit.as_ref())
+  }
 }
 
 impl ComposeChild for Input {
@@ -60,6 +126,7 @@ impl ComposeChild for Input {
         let tick_of_layout_ready = ctx.wnd_ctx()
           .frame_tick_stream()
           .filter(|msg| matches!(msg, FrameMsg::LayoutReady(_)));
+        let keymap = InputTheme::of(ctx).keymap;
       }
 
       ConstrainedBox {
@@ -70,7 +137,13 @@ impl ComposeChild for Input {
         },
         auto_focus: true,
         on_char: move |char_event| this.edit_handle(char_event),
-        on_key_down: move |key| this.key_handle(key),
+        on_ime_preedit: move |e| this.preedit = Preedit::from_event(e),
+        on_focus: move |e| e.context().wnd_ctx().set_ime_allowed(true),
+        on_blur: move |e| {
+          this.preedit = None;
+          e.context().wnd_ctx().set_ime_allowed(false);
+        },
+        on_key_down: move |key| this.key_handle(key, &keymap, &helper),
         on_pointer_move: move |e| {
           if let CaretState::Selecting(begin, _) = this.caret {
             if e.point_type == PointerType::Mouse
@@ -82,9 +155,20 @@ impl ComposeChild for Input {
           }
         },
         on_pointer_down: move |e| {
+          let position = to_content_pos(&container, &e.position());
+          let cluster = helper.cluster_from_pos(position.x, position.y) as usize;
+          let begin = if e.modifiers().shift() { this.caret.anchor() } else { cluster };
+          this.caret = CaretState::Selecting(begin, cluster);
+        },
+        on_double_tap: move |e| {
           let position = to_content_pos(&container, &e.position());
           let cluster = helper.cluster_from_pos(position.x, position.y);
-          this.caret = CaretState::Selecting(cluster as usize, cluster as usize);
+          this.select_word(cluster as usize);
+        },
+        on_triple_tap: move |e| {
+          let position = to_content_pos(&container, &e.position());
+          let cluster = helper.cluster_from_pos(position.x, position.y);
+          this.select_line(cluster as usize);
         },
         on_pointer_up: move |_| {
           if let CaretState::Selecting(begin, end) = this.caret {
@@ -107,7 +191,7 @@ impl ComposeChild for Input {
             }
             Text {
               id: text,
-              text: this.text.clone(),
+              text: this.display_text(),
               style: this.style.clone(),
 
               on_performed_layout: move |ctx| {
@@ -117,15 +201,21 @@ impl ComposeChild for Input {
                   &text.style,
                   ctx.wnd_ctx().typography_store(),
                   bound,
+                  Overflow::Clip,
                 ));
               }
             }
 
+            PreeditUnderline {
+              id: underline,
+              rects: vec![],
+            }
+
             DynWidget {
               dyns: placeholder.map(|holder| {
                 widget! {
                   Text {
-                    visible: this.text.is_empty(),
+                    visible: this.text.is_empty() && this.preedit.is_none(),
                     text: holder.0,
                   }
                 }
@@ -138,17 +228,33 @@ impl ComposeChild for Input {
               left_anchor: 0.,
               focused: outbox.has_focus(),
               height: 0.,
+              on_performed_layout: move |ctx| {
+                // place the candidate window of the input method below the caret.
+                if outbox.has_focus() {
+                  let size = ctx.box_size().unwrap_or_default();
+                  let pos = ctx.map_to_global(Point::new(size.width / 2., size.height));
+                  ctx.wnd_ctx().set_ime_pos(pos);
+                }
+              }
             }
           }
         }
       }
       finally {
-        let_watch!(this.caret)
+        let_watch!((this.caret, this.preedit.clone()))
           .distinct_until_changed()
           .sample(tick_of_layout_ready)
-          .subscribe(move |cursor| {
-            selected.rects = helper.selection(&cursor.select_range());
-            let (offset, height) = helper.cursor(cursor.offset());
+          .subscribe(move |(cursor, preedit)| {
+            let offset = if let Some(preedit) = preedit {
+              selected.rects = vec![];
+              underline.rects = helper.selection(&preedit.range(cursor));
+              preedit.cursor(cursor)
+            } else {
+              selected.rects = helper.selection(&cursor.select_range());
+              underline.rects = vec![];
+              cursor.offset()
+            };
+            let (offset, height) = helper.cursor(offset);
             caret.top_anchor = PositionUnit::Pixel(offset.y);
             caret.left_anchor = PositionUnit::Pixel(offset.x);
             caret.height = height;
@@ -174,7 +280,7 @@ impl Placeholder {
   pub fn new(str: impl Into<CowArc<str>>) -> Self { Self(str.into()) }
 }
 
-fn input_width(font_size: FontSize, length: f32) -> f32 {
+pub(crate) fn input_width(font_size: FontSize, length: f32) -> f32 {
   FontSize::Em(Em::relative_to(length, font_size))
     .into_pixel()
     .value()
@@ -200,6 +306,177 @@ fn auto_x_scroll_pos(container: &ScrollableWidget, before: f32, after: f32) -> f
   pos - after
 }
 
-fn to_content_pos(container: &ScrollableWidget, view_position: &Point) -> Point {
+pub(crate) fn to_content_pos(container: &ScrollableWidget, view_position: &Point) -> Point {
   *view_position - Size::from(container.scroll_pos.to_vector())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::prelude::material;
+  use std::{cell::RefCell, rc::Rc};
+  use winit::event::{DeviceId, ElementState, Ime, KeyboardInput, ModifiersState, WindowEvent};
+
+  /// Record the text and the caret of the edit widget in a test window, and
+  /// the clipboard of the window.
+  #[derive(Clone, Default)]
+  pub(super) struct EditProbe {
+    text: Rc<RefCell<String>>,
+    caret: Rc<RefCell<CaretState>>,
+    pub(super) clipboard: Rc<RefCell<MockClipboard>>,
+  }
+
+  impl EditProbe {
+    pub(super) fn text(&self) -> String { self.text.borrow().clone() }
+
+    pub(super) fn caret(&self) -> CaretState { *self.caret.borrow() }
+
+    pub(super) fn set_text(&self, text: &str) { *self.text.borrow_mut() = text.to_string(); }
+
+    pub(super) fn set_caret(&self, caret: CaretState) { *self.caret.borrow_mut() = caret; }
+  }
+
+  /// Mock a window with the material theme for the widget created by `w`, the
+  /// widget should record its text and caret by the probe.
+  pub(super) fn edit_window(w: impl FnOnce(EditProbe) -> Widget) -> (Window, EditProbe) {
+    let probe = EditProbe::default();
+    let ctx = AppContext {
+      app_theme: Rc::new(material::purple::light()),
+      clipboard: probe.clipboard.clone(),
+      ..<_>::default()
+    };
+    let mut wnd = Window::mock_window(w(probe.clone()), Size::new(400., 400.), ctx);
+    wnd.draw_frame();
+    (wnd, probe)
+  }
+
+  /// Send the events to the window and draw a frame.
+  pub(super) fn send(wnd: &mut Window, events: Vec<WindowEvent<'static>>) {
+    events
+      .into_iter()
+      .for_each(|e| wnd.processes_native_event(e));
+    wnd.draw_frame();
+  }
+
+  pub(super) fn key_with_ctrl(key: VirtualKeyCode) -> Vec<WindowEvent<'static>> {
+    key_with(key, EditKeymap::platform().shortcut)
+  }
+
+  pub(super) fn key_with(
+    key: VirtualKeyCode,
+    modifiers: ModifiersState,
+  ) -> Vec<WindowEvent<'static>> {
+    #[allow(deprecated)]
+    let input = |state| WindowEvent::KeyboardInput {
+      device_id: unsafe { DeviceId::dummy() },
+      input: KeyboardInput {
+        scancode: 0,
+        virtual_keycode: Some(key),
+        state,
+        modifiers,
+      },
+      is_synthetic: false,
+    };
+    vec![
+      WindowEvent::ModifiersChanged(modifiers),
+      input(ElementState::Pressed),
+      input(ElementState::Released),
+      WindowEvent::ModifiersChanged(ModifiersState::empty()),
+    ]
+  }
+
+  /// An `Input` with `text` and `caret` that records its text and caret.
+  fn input(text: &'static str, caret: CaretState) -> (Window, EditProbe) {
+    edit_window(|probe| {
+      widget! {
+        Input { id: input }
+        finally {
+          input.set_text(text);
+          input.set_caret(caret);
+          let_watch!((input.text(), *input.caret()))
+            .subscribe(move |(text, caret)| {
+              probe.set_text(&text);
+              probe.set_caret(caret);
+            });
+        }
+      }
+    })
+  }
+
+  #[test]
+  fn clipboard_shortcuts() {
+    let (mut wnd, probe) = input("hello", CaretState::Select(0, 5));
+
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::C));
+    assert_eq!(probe.clipboard.borrow_mut().read_text().unwrap(), "hello");
+
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::X));
+    assert_eq!(probe.text(), "");
+
+    probe.clipboard.borrow_mut().write_text("a\nb").unwrap();
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::V));
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::V));
+    assert_eq!(probe.text(), "a ba b");
+  }
+
+  #[test]
+  fn undo_redo() {
+    let (mut wnd, probe) = input("", CaretState::default());
+    "hello world".chars().for_each(|c| {
+      wnd.processes_native_event(WindowEvent::ReceivedCharacter(c));
+    });
+    wnd.draw_frame();
+    assert_eq!(probe.text(), "hello world");
+
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::Z));
+    assert_eq!(probe.text(), "hello ");
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::Z));
+    assert_eq!(probe.text(), "");
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::Y));
+    assert_eq!(probe.text(), "hello ");
+    send(&mut wnd, key_with_ctrl(VirtualKeyCode::Y));
+    assert_eq!(probe.text(), "hello world");
+  }
+
+  #[test]
+  fn ime_preedit() {
+    let (mut wnd, probe) = edit_window(|probe| {
+      widget! {
+        Input { id: input }
+        finally {
+          let_watch!(input.display_text())
+            .subscribe(move |v| probe.set_text(&v));
+        }
+      }
+    });
+    let mut ime = |ime| send(&mut wnd, vec![WindowEvent::Ime(ime)]);
+
+    ime(Ime::Preedit("ni".into(), Some((2, 2))));
+    assert_eq!(probe.text(), "ni");
+    ime(Ime::Preedit("".into(), None));
+    assert_eq!(probe.text(), "");
+    ime(Ime::Commit("你".into()));
+    assert_eq!(probe.text(), "你");
+  }
+
+  #[test]
+  fn word_navigation() {
+    let (mut wnd, probe) = input("hello world foo", CaretState::Caret(0));
+    let mut key = |key, modifiers| send(&mut wnd, key_with(key, modifiers));
+    let word = EditKeymap::platform().word;
+
+    key(VirtualKeyCode::Right, word);
+    assert_eq!(probe.caret(), CaretState::Caret(5));
+    key(VirtualKeyCode::Right, word | ModifiersState::SHIFT);
+    assert_eq!(probe.caret().select_range(), 5..11);
+    key(VirtualKeyCode::Left, ModifiersState::empty());
+    assert_eq!(probe.caret(), CaretState::Caret(5));
+
+    key(VirtualKeyCode::End, ModifiersState::empty());
+    key(VirtualKeyCode::Back, word);
+    assert_eq!(probe.text(), "hello world ");
+
+    key(VirtualKeyCode::Home, ModifiersState::SHIFT);
+    assert_eq!(probe.caret().select_range(), 0..12);
+  }
+}
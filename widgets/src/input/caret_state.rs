@@ -42,6 +42,16 @@ impl CaretState {
     }
   }
 
+  /// The position where the selection begin, it's the caret position if there
+  /// is no selection.
+  pub fn anchor(&self) -> usize {
+    match *self {
+      CaretState::Caret(cursor) => cursor,
+      CaretState::Select(begin, _) => begin,
+      CaretState::Selecting(begin, _) => begin,
+    }
+  }
+
   pub fn valid(&mut self, len: usize) {
     *self = match *self {
       CaretState::Caret(cursor) => CaretState::Caret(cursor.min(len)),
@@ -0,0 +1,192 @@
+use ribir_core::prelude::CowArc;
+
+use super::CaretState;
+
+/// The state of the editable text, before or after an edit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditRecord {
+  pub text: CowArc<str>,
+  pub caret: CaretState,
+}
+
+/// Describe what an edit did, the continuous edits of the same kind will be
+/// merged into one transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKind {
+  /// Type a char that is a part of a word.
+  TypeWord,
+  /// Type a white space, it's merged into the word before it.
+  TypeSpace,
+  /// Delete chars one by one, by backspace or delete key.
+  Delete,
+  /// Other edits like paste, cut or delete a selection, never merged.
+  Other,
+}
+
+/// The transaction log of the edits of an editable text, support undo and
+/// redo.
+///
+/// The history only keep the states before every transaction, a transaction
+/// contains the continuous edits that can be merged, for example, the chars
+/// typed of a word. A transaction ends if the caret is moved by others, or
+/// call [`EditHistory::break_merge`] manually.
+#[derive(Debug)]
+pub struct EditHistory {
+  undo_stack: Vec<EditRecord>,
+  redo_stack: Vec<EditRecord>,
+  /// The kind and the caret after the last edit, use to detect if the next
+  /// edit can be merged.
+  last_edit: Option<(EditKind, CaretState)>,
+  capacity: usize,
+}
+
+impl EditHistory {
+  /// Create a history that keep `capacity` transactions at most, the oldest
+  /// one will be dropped if over the capacity.
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      undo_stack: vec![],
+      redo_stack: vec![],
+      last_edit: None,
+      capacity,
+    }
+  }
+
+  /// Record an edit, `before` is the state before the edit and `after_caret`
+  /// is the caret after it. The redo history is cleared by a new edit.
+  pub fn record(&mut self, before: EditRecord, after_caret: CaretState, kind: EditKind) {
+    let merge = matches!(
+      self.last_edit,
+      Some((last, caret)) if caret == before.caret && can_merge(last, kind)
+    );
+    if !merge {
+      if self.undo_stack.len() >= self.capacity {
+        self.undo_stack.remove(0);
+      }
+      self.undo_stack.push(before);
+    }
+    self.redo_stack.clear();
+    self.last_edit = Some((kind, after_caret));
+  }
+
+  /// Pop the last transaction, return the state before it. `current` is the
+  /// state now, it's kept to redo.
+  pub fn undo(&mut self, current: EditRecord) -> Option<EditRecord> {
+    let record = self.undo_stack.pop()?;
+    self.redo_stack.push(current);
+    self.last_edit = None;
+    Some(record)
+  }
+
+  /// Redo the last undo transaction, return the state after it. `current` is
+  /// the state now, it's kept to undo.
+  pub fn redo(&mut self, current: EditRecord) -> Option<EditRecord> {
+    let record = self.redo_stack.pop()?;
+    self.undo_stack.push(current);
+    self.last_edit = None;
+    Some(record)
+  }
+
+  #[inline]
+  pub fn can_undo(&self) -> bool { !self.undo_stack.is_empty() }
+
+  #[inline]
+  pub fn can_redo(&self) -> bool { !self.redo_stack.is_empty() }
+
+  /// End the current transaction, the next edit will not be merged into it.
+  #[inline]
+  pub fn break_merge(&mut self) { self.last_edit = None; }
+
+  /// Clear all the history, for example, after the text saved.
+  pub fn clear(&mut self) {
+    self.undo_stack.clear();
+    self.redo_stack.clear();
+    self.last_edit = None;
+  }
+}
+
+impl Default for EditHistory {
+  fn default() -> Self { Self::new(100) }
+}
+
+fn can_merge(last: EditKind, next: EditKind) -> bool {
+  matches!(
+    (last, next),
+    (EditKind::TypeWord, EditKind::TypeWord)
+      | (EditKind::TypeWord | EditKind::TypeSpace, EditKind::TypeSpace)
+      | (EditKind::Delete, EditKind::Delete)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn type_str(history: &mut EditHistory, text: &mut String, s: &str) {
+    s.chars().for_each(|c| {
+      let before = state_of(text);
+      text.push(c);
+      let kind = if c.is_whitespace() {
+        EditKind::TypeSpace
+      } else {
+        EditKind::TypeWord
+      };
+      history.record(before, CaretState::Caret(text.len()), kind);
+    });
+  }
+
+  fn state_of(text: &str) -> EditRecord {
+    EditRecord {
+      text: text.to_string().into(),
+      caret: CaretState::Caret(text.len()),
+    }
+  }
+
+  #[test]
+  fn merge_words() {
+    let mut history = EditHistory::default();
+    let mut text = String::new();
+    type_str(&mut history, &mut text, "hello world");
+
+    let record = history.undo(state_of(&text)).unwrap();
+    assert_eq!(record, state_of("hello "));
+    let record = history.undo(record).unwrap();
+    assert_eq!(record, state_of(""));
+    assert!(!history.can_undo());
+
+    let record = history.redo(record).unwrap();
+    assert_eq!(record, state_of("hello "));
+    let record = history.redo(record).unwrap();
+    assert_eq!(record, state_of("hello world"));
+    assert!(!history.can_redo());
+  }
+
+  #[test]
+  fn caret_moved_break_merge() {
+    let mut history = EditHistory::default();
+    let mut text = String::new();
+    type_str(&mut history, &mut text, "ab");
+    history.record(
+      EditRecord {
+        text: "ab".into(),
+        caret: CaretState::Caret(1),
+      },
+      CaretState::Caret(2),
+      EditKind::TypeWord,
+    );
+    assert_eq!(history.undo_stack.len(), 2);
+
+    history.break_merge();
+    type_str(&mut history, &mut text, "c");
+    assert_eq!(history.undo_stack.len(), 3);
+  }
+
+  #[test]
+  fn capacity() {
+    let mut history = EditHistory::new(2);
+    let mut text = String::new();
+    type_str(&mut history, &mut text, "a b c");
+    assert_eq!(history.undo_stack.len(), 2);
+    assert_eq!(history.undo_stack[0], state_of("a "));
+  }
+}
@@ -22,6 +22,23 @@ impl GlyphsHelper {
     (Point::new(glphy.min_x(), glphy.max_y()), line_height)
   }
 
+  /// Return the cluster that the caret should be moved to if it moves `delta`
+  /// pixels in the block direction.
+  pub(crate) fn cluster_by_line_move(&self, cursor: usize, delta: f32) -> usize {
+    let (pos, line_height) = self.cursor(cursor);
+    self.cluster_from_pos(pos.x, pos.y + line_height / 2. + delta) as usize
+  }
+
+  pub(crate) fn line_begin(&self, cursor: usize) -> usize {
+    let glyphs = self.glyphs.as_ref().unwrap();
+    glyphs.line_begin_cluster(cursor as u32) as usize
+  }
+
+  pub(crate) fn line_end(&self, cursor: usize) -> usize {
+    let glyphs = self.glyphs.as_ref().unwrap();
+    glyphs.line_end_cluster(cursor as u32) as usize
+  }
+
   pub(crate) fn selection(&self, rg: &Range<usize>) -> Vec<Rect> {
     if rg.is_empty() {
       return vec![];
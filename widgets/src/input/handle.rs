@@ -1,76 +1,346 @@
 use std::ops::{Deref, DerefMut};
 
-use ribir_core::prelude::{CharEvent, GraphemeCursor, KeyboardEvent, TextWriter, VirtualKeyCode};
+use ribir_core::prelude::{
+  log, next_word_boundary, prev_word_boundary, word_range, CharEvent, CharacterCursor, CowArc,
+  GraphemeCursor, KeyboardEvent, ModifiersState, TextWriter, VirtualKeyCode, WidgetContext,
+};
 
-struct InputWriter<'a> {
-  input: &'a mut Input,
+use super::{
+  glyphs_helper::GlyphsHelper, CaretState, EditHistory, EditKeymap, EditKind, EditRecord,
+};
+
+/// The state of a widget that its text can be edited by user, the edit logic
+/// is shared by the widgets implemented it.
+pub(crate) trait EditableText {
+  fn edit_text(&self) -> CowArc<str>;
+
+  fn edit_caret(&self) -> CaretState;
+
+  fn update_edit(&mut self, text: CowArc<str>, caret: CaretState);
+
+  fn edit_history(&mut self) -> &mut EditHistory;
+
+  /// If the text can contain line breaks, the line breaks of the pasted text
+  /// will be replaced by white spaces if not.
+  fn multiline(&self) -> bool { false }
+}
+
+pub(crate) struct InputWriter<'a, E: EditableText> {
+  input: &'a mut E,
   writer: TextWriter<GraphemeCursor>,
+  /// The state before write and the kind of the edit, if the edit should be
+  /// recorded to the history.
+  record: Option<(EditRecord, EditKind)>,
 }
 
-impl<'a> InputWriter<'a> {
-  fn new(input: &'a mut Input) -> Self {
-    let cursor = GraphemeCursor(input.caret.offset());
-    let string = input.text().to_string();
+impl<'a, E: EditableText> InputWriter<'a, E> {
+  pub(crate) fn new(input: &'a mut E) -> Self {
+    let cursor = GraphemeCursor(input.edit_caret().offset());
+    let string = input.edit_text().to_string();
     Self {
       input,
       writer: TextWriter::new(string, cursor),
+      record: None,
     }
   }
+
+  /// Create a writer that record its modify to the edit history as `kind`.
+  pub(crate) fn edit(input: &'a mut E, kind: EditKind) -> Self {
+    let before = EditRecord {
+      text: input.edit_text(),
+      caret: input.edit_caret(),
+    };
+    let mut writer = Self::new(input);
+    writer.record = Some((before, kind));
+    writer
+  }
 }
 
-impl<'a> Drop for InputWriter<'a> {
+impl<'a, E: EditableText> Drop for InputWriter<'a, E> {
   fn drop(&mut self) {
-    let Self { input, writer } = self;
-    input.caret = writer.byte_offset().into();
-    input.text = writer.text().clone().into();
+    let Self { input, writer, record } = self;
+    let caret: CaretState = writer.byte_offset().into();
+    if let Some((before, kind)) = record.take() {
+      if *before.text != **writer.text() {
+        input.edit_history().record(before, caret, kind);
+      }
+    }
+    input.update_edit(writer.text().clone().into(), caret);
   }
 }
 
-impl<'a> Deref for InputWriter<'a> {
+impl<'a, E: EditableText> Deref for InputWriter<'a, E> {
   type Target = TextWriter<GraphemeCursor>;
   fn deref(&self) -> &Self::Target { &self.writer }
 }
 
-impl<'a> DerefMut for InputWriter<'a> {
+impl<'a, E: EditableText> DerefMut for InputWriter<'a, E> {
   fn deref_mut(&mut self) -> &mut Self::Target { &mut self.writer }
 }
 
-use super::Input;
-impl Input {
-  pub(crate) fn edit_handle(&mut self, event: &mut CharEvent) {
-    if !event.char.is_ascii_control() {
-      let rg = self.caret.select_range();
-      let mut writer = InputWriter::new(self);
-      writer.delete_byte_range(&rg);
-      writer.insert_char(event.char);
-    }
+pub(crate) fn edit_handle(this: &mut impl EditableText, event: &mut CharEvent) {
+  if !event.char.is_ascii_control() {
+    insert_char(this, event.char);
   }
+}
 
-  pub(crate) fn key_handle(&mut self, key: &mut KeyboardEvent) {
-    match key.key {
-      VirtualKeyCode::Left => {
-        InputWriter::new(self).move_to_prev();
-      }
-      VirtualKeyCode::Right => {
-        InputWriter::new(self).move_to_next();
+/// Insert a char at the caret, replace the selected text if any.
+pub(crate) fn insert_char(this: &mut impl EditableText, c: char) {
+  let rg = this.edit_caret().select_range();
+  let kind = if !rg.is_empty() {
+    EditKind::Other
+  } else if c.is_whitespace() {
+    EditKind::TypeSpace
+  } else {
+    EditKind::TypeWord
+  };
+  let mut writer = InputWriter::edit(this, kind);
+  writer.delete_byte_range(&rg);
+  writer.insert_char(c);
+}
+
+/// Insert a string at the caret, replace the selected text if any.
+pub(crate) fn insert_str(this: &mut impl EditableText, text: &str) {
+  let rg = this.edit_caret().select_range();
+  let mut writer = InputWriter::edit(this, EditKind::Other);
+  writer.delete_byte_range(&rg);
+  writer.insert_str(text);
+}
+
+/// Copy the selected text to the clipboard, return if anything copied.
+pub(crate) fn copy_selection(text: &str, caret: CaretState, key: &mut KeyboardEvent) -> bool {
+  let rg = caret.select_range();
+  if rg.is_empty() {
+    return false;
+  }
+  let clipboard = key.context().wnd_ctx().clipboard();
+  let res = clipboard.borrow_mut().write_text(&text[rg]);
+  if let Err(err) = res {
+    log::warn!("Copy to clipboard failed: {err}");
+  }
+  true
+}
+
+fn shortcut_handle(this: &mut impl EditableText, key: &mut KeyboardEvent) -> bool {
+  match key.key {
+    VirtualKeyCode::C => {
+      copy_selection(&this.edit_text(), this.edit_caret(), key);
+    }
+    VirtualKeyCode::X => {
+      if copy_selection(&this.edit_text(), this.edit_caret(), key) {
+        let rg = this.edit_caret().select_range();
+        InputWriter::edit(this, EditKind::Other).delete_byte_range(&rg);
       }
-      VirtualKeyCode::Back => {
-        let rg = self.caret.select_range();
-        if rg.is_empty() {
-          InputWriter::new(self).back_space();
-        } else {
-          InputWriter::new(self).delete_byte_range(&rg);
-        }
+    }
+    VirtualKeyCode::Z if key.modifiers().shift() => {
+      redo(this);
+    }
+    VirtualKeyCode::Z => {
+      undo(this);
+    }
+    VirtualKeyCode::Y => {
+      redo(this);
+    }
+    VirtualKeyCode::A => {
+      let text = this.edit_text();
+      let len = text.len();
+      this.update_edit(text, CaretState::Select(0, len));
+    }
+    VirtualKeyCode::V => {
+      let clipboard = key.context().wnd_ctx().clipboard();
+      let text = clipboard.borrow_mut().read_text();
+      match text {
+        Ok(text) if this.multiline() => insert_str(this, &text),
+        Ok(text) => insert_str(this, &text.replace("\r\n", " ").replace(['\r', '\n'], " ")),
+        Err(err) => log::warn!("Paste from clipboard failed: {err}"),
       }
-      VirtualKeyCode::Delete => {
-        let rg = self.caret.select_range();
-        if rg.is_empty() {
-          InputWriter::new(self).del_char();
-        } else {
-          InputWriter::new(self).delete_byte_range(&rg);
-        }
+    }
+    _ => return false,
+  };
+  true
+}
+
+/// Restore the state before the last edit transaction, return if anything
+/// undone.
+pub(crate) fn undo(this: &mut impl EditableText) -> bool {
+  let current = EditRecord {
+    text: this.edit_text(),
+    caret: this.edit_caret(),
+  };
+  if let Some(EditRecord { text, caret }) = this.edit_history().undo(current) {
+    this.update_edit(text, caret);
+    true
+  } else {
+    false
+  }
+}
+
+/// Redo the last undone edit transaction, return if anything redone.
+pub(crate) fn redo(this: &mut impl EditableText) -> bool {
+  let current = EditRecord {
+    text: this.edit_text(),
+    caret: this.edit_caret(),
+  };
+  if let Some(EditRecord { text, caret }) = this.edit_history().redo(current) {
+    this.update_edit(text, caret);
+    true
+  } else {
+    false
+  }
+}
+
+/// Process the editing and caret navigation keys, return if the key is
+/// processed. The line navigation depends on the layout of the glyphs, and
+/// `page_height` is the height the caret moved by the page up and down keys.
+pub(crate) fn key_handle(
+  this: &mut impl EditableText,
+  key: &mut KeyboardEvent,
+  keymap: &EditKeymap,
+  helper: &GlyphsHelper,
+  page_height: f32,
+) -> bool {
+  let modifiers = key.modifiers();
+  if modifiers.contains(keymap.shortcut) && shortcut_handle(this, key) {
+    return true;
+  }
+
+  let text = this.edit_text();
+  let caret = this.edit_caret();
+  if let Some(cursor) = navigate(this, key.key, modifiers, keymap, helper, page_height) {
+    let caret = if modifiers.shift() {
+      let anchor = caret.anchor();
+      if anchor == cursor {
+        CaretState::Caret(cursor)
+      } else {
+        CaretState::Select(anchor, cursor)
       }
-      _ => (),
+    } else {
+      CaretState::Caret(cursor)
     };
+    this.update_edit(text, caret);
+    // The navigation keys move the caret, not the focus.
+    key.prevent_default();
+    return true;
   }
+
+  let rg = caret.select_range();
+  let cursor = caret.offset();
+  let by_word = modifiers.contains(keymap.word);
+  match key.key {
+    VirtualKeyCode::Back | VirtualKeyCode::Delete if !rg.is_empty() => {
+      InputWriter::edit(this, EditKind::Other).delete_byte_range(&rg);
+    }
+    VirtualKeyCode::Back if by_word => {
+      let rg = prev_word_boundary(&text, cursor)..cursor;
+      InputWriter::edit(this, EditKind::Other).delete_byte_range(&rg);
+    }
+    VirtualKeyCode::Delete if by_word => {
+      let rg = cursor..next_word_boundary(&text, cursor);
+      InputWriter::edit(this, EditKind::Other).delete_byte_range(&rg);
+    }
+    VirtualKeyCode::Back => InputWriter::edit(this, EditKind::Delete).back_space(),
+    VirtualKeyCode::Delete => InputWriter::edit(this, EditKind::Delete).del_char(),
+    _ => return false,
+  };
+  true
+}
+
+/// Return the new position of the caret if the key is a navigation key.
+fn navigate(
+  this: &impl EditableText,
+  key: VirtualKeyCode,
+  modifiers: ModifiersState,
+  keymap: &EditKeymap,
+  helper: &GlyphsHelper,
+  page_height: f32,
+) -> Option<usize> {
+  let text = this.edit_text();
+  let caret = this.edit_caret();
+  let cursor = caret.offset();
+  let rg = caret.select_range();
+  let by_word = modifiers.contains(keymap.word);
+  let by_doc = modifiers.contains(keymap.shortcut);
+  // Move the caret to the side of the selection instead of move it from the
+  // caret if the selection is not extended.
+  let collapse = !modifiers.shift() && !rg.is_empty();
+  let line_height = || helper.cursor(cursor).1;
+
+  let pos = match key {
+    VirtualKeyCode::Left if by_word => prev_word_boundary(&text, cursor),
+    VirtualKeyCode::Right if by_word => next_word_boundary(&text, cursor),
+    VirtualKeyCode::Left if by_doc => helper.line_begin(cursor),
+    VirtualKeyCode::Right if by_doc => helper.line_end(cursor),
+    VirtualKeyCode::Left if collapse => rg.start,
+    VirtualKeyCode::Right if collapse => rg.end,
+    VirtualKeyCode::Left => {
+      let mut cursor = GraphemeCursor(cursor);
+      cursor.prev(&text);
+      cursor.byte_offset()
+    }
+    VirtualKeyCode::Right => {
+      let mut cursor = GraphemeCursor(cursor);
+      cursor.next(&text);
+      cursor.byte_offset()
+    }
+    VirtualKeyCode::Home | VirtualKeyCode::Up if by_doc => 0,
+    VirtualKeyCode::End | VirtualKeyCode::Down if by_doc => text.len(),
+    VirtualKeyCode::Home => helper.line_begin(cursor),
+    VirtualKeyCode::End => helper.line_end(cursor),
+    VirtualKeyCode::Up if this.multiline() => helper.cluster_by_line_move(cursor, -line_height()),
+    VirtualKeyCode::Down if this.multiline() => helper.cluster_by_line_move(cursor, line_height()),
+    VirtualKeyCode::PageUp if this.multiline() => helper.cluster_by_line_move(cursor, -page_height),
+    VirtualKeyCode::PageDown if this.multiline() => {
+      helper.cluster_by_line_move(cursor, page_height)
+    }
+    _ => return None,
+  };
+  Some(pos)
+}
+
+/// Select the word at the `cursor`.
+pub(crate) fn select_word(this: &mut impl EditableText, cursor: usize) {
+  let text = this.edit_text();
+  let rg = word_range(&text, cursor);
+  this.update_edit(text, CaretState::Select(rg.start, rg.end));
+}
+
+/// Select the paragraph at the `cursor`, the line break is not included.
+pub(crate) fn select_line(this: &mut impl EditableText, cursor: usize) {
+  let text = this.edit_text();
+  let start = text[..cursor].rfind('\n').map_or(0, |idx| idx + 1);
+  let end = text[cursor..]
+    .find('\n')
+    .map_or(text.len(), |idx| cursor + idx);
+  this.update_edit(text, CaretState::Select(start, end));
+}
+
+use super::Input;
+impl EditableText for Input {
+  fn edit_text(&self) -> CowArc<str> { self.text.clone() }
+
+  fn edit_caret(&self) -> CaretState { self.caret }
+
+  fn update_edit(&mut self, text: CowArc<str>, caret: CaretState) {
+    self.text = text;
+    self.caret = caret;
+  }
+
+  fn edit_history(&mut self) -> &mut EditHistory { &mut self.history }
+}
+
+impl Input {
+  pub(crate) fn edit_handle(&mut self, event: &mut CharEvent) { edit_handle(self, event); }
+
+  pub(crate) fn key_handle(
+    &mut self,
+    key: &mut KeyboardEvent,
+    keymap: &EditKeymap,
+    helper: &GlyphsHelper,
+  ) {
+    key_handle(self, key, keymap, helper, 0.);
+  }
+
+  pub(crate) fn select_word(&mut self, cursor: usize) { select_word(self, cursor) }
+
+  pub(crate) fn select_line(&mut self, cursor: usize) { select_line(self, cursor) }
 }
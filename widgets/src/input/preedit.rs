@@ -0,0 +1,88 @@
+use std::ops::Range;
+
+use ribir_core::prelude::*;
+
+use super::{CaretState, InputTheme};
+use crate::layout::{Container, Stack};
+
+/// The text composing by the input method, it's displayed at the caret but not
+/// a part of the text before committed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Preedit {
+  pub(crate) text: String,
+  /// The byte range of the cursor in the `text`.
+  pub(crate) cursor: Option<(usize, usize)>,
+}
+
+impl Preedit {
+  /// Return the preedit of the event, `None` if the composing is ended.
+  pub(crate) fn from_event(e: &ImePreeditEvent) -> Option<Self> {
+    (!e.preedit.is_empty()).then(|| Preedit {
+      text: e.preedit.clone(),
+      cursor: e.cursor,
+    })
+  }
+
+  /// The byte range of the preedit in the display text.
+  pub(crate) fn range(&self, caret: CaretState) -> Range<usize> {
+    let start = caret.select_range().start;
+    start..start + self.text.len()
+  }
+
+  /// The cursor of the input method in the display text.
+  pub(crate) fn cursor(&self, caret: CaretState) -> usize {
+    let start = caret.select_range().start;
+    start + self.cursor.map_or(self.text.len(), |(_, end)| end)
+  }
+}
+
+/// Return the text to display, the composing text replaces the selection.
+pub(crate) fn display_text(
+  text: &CowArc<str>,
+  caret: CaretState,
+  preedit: Option<&Preedit>,
+) -> CowArc<str> {
+  match preedit {
+    Some(preedit) => {
+      let rg = caret.select_range();
+      let display = format!("{}{}{}", &text[..rg.start], preedit.text, &text[rg.end..]);
+      display.into()
+    }
+    None => text.clone(),
+  }
+}
+
+/// The underline of the composing text.
+#[derive(Declare)]
+pub(crate) struct PreeditUnderline {
+  pub(crate) rects: Vec<Rect>,
+}
+
+impl Compose for PreeditUnderline {
+  fn compose(this: State<Self>) -> Widget {
+    widget! {
+      states { this: this.into_readonly() }
+      init ctx => {
+        let color = InputTheme::of(ctx).caret_color.clone();
+      }
+      Stack {
+        DynWidget {
+          dyns: {
+            this.rects.iter().copied()
+            .map(|rc| {
+              let color = color.clone();
+              widget! {
+                Container {
+                  background: color,
+                  top_anchor: rc.max_y() - 1.,
+                  left_anchor: rc.origin.x,
+                  size: Size::new(rc.width(), 1.),
+                }
+              }
+            }).collect::<Vec<_>>()
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,164 @@
+use super::{
+  glyphs_helper::GlyphsHelper,
+  handle::copy_selection,
+  selected_text::SelectedText,
+  CaretState, InputTheme,
+};
+use crate::layout::Stack;
+use crate::prelude::Text;
+use ribir_core::{prelude::*, ticker::FrameMsg};
+
+/// A text that can be selected by the pointer and copy the selected text to
+/// the clipboard.
+///
+/// `Text` is a plain render widget that keeps no state, so use this widget
+/// instead of `Text` when the text should be selectable.
+#[derive(Declare)]
+pub struct SelectableText {
+  #[declare(convert=into)]
+  pub text: CowArc<str>,
+  #[declare(default = Brush::Color(Palette::of(ctx).on_surface_variant()))]
+  pub foreground: Brush,
+  #[declare(default = TypographyTheme::of(ctx).body_medium.text.clone())]
+  pub style: CowArc<TextStyle>,
+  #[declare(skip)]
+  caret: CaretState,
+}
+
+impl SelectableText {
+  pub fn caret(&self) -> &CaretState { &self.caret }
+
+  pub fn set_caret(&mut self, caret: CaretState) {
+    self.caret = caret;
+    self.caret.valid(self.text.len());
+  }
+}
+
+impl Compose for SelectableText {
+  fn compose(this: State<Self>) -> Widget {
+    widget! {
+      states {
+        this: this.into_writable(),
+        helper: Stateful::new(GlyphsHelper::default()),
+      }
+      init ctx => {
+        let tick_of_layout_ready = ctx.wnd_ctx()
+          .frame_tick_stream()
+          .filter(|msg| matches!(msg, FrameMsg::LayoutReady(_)));
+        let keymap = InputTheme::of(ctx).keymap;
+      }
+
+      Stack {
+        tab_index: -1,
+        on_key_down: move |key| {
+          if key.modifiers().contains(keymap.shortcut) {
+            match key.key {
+              VirtualKeyCode::C => { copy_selection(&this.text, this.caret, key); }
+              VirtualKeyCode::A => this.caret = CaretState::Select(0, this.text.len()),
+              _ => {}
+            }
+          }
+        },
+        on_blur: move |_| this.caret = CaretState::default(),
+        on_pointer_move: move |e| {
+          if let CaretState::Selecting(begin, _) = this.caret {
+            if e.point_type == PointerType::Mouse
+              && e.mouse_buttons() == MouseButtons::PRIMARY {
+              let position = e.position();
+              let cluster = helper.cluster_from_pos(position.x, position.y);
+              this.caret = CaretState::Selecting(begin, cluster as usize);
+            }
+          }
+        },
+        on_pointer_down: move |e| {
+          let position = e.position();
+          let cluster = helper.cluster_from_pos(position.x, position.y) as usize;
+          let begin = if e.modifiers().shift() { this.caret.anchor() } else { cluster };
+          this.caret = CaretState::Selecting(begin, cluster);
+        },
+        on_double_tap: move |e| {
+          let position = e.position();
+          let cluster = helper.cluster_from_pos(position.x, position.y);
+          let rg = word_range(&this.text, cluster as usize);
+          this.caret = CaretState::Select(rg.start, rg.end);
+        },
+        on_triple_tap: move |_| this.caret = CaretState::Select(0, this.text.len()),
+        on_pointer_up: move |_| {
+          if let CaretState::Selecting(begin, end) = this.caret {
+            this.caret = if begin == end {
+             CaretState::Caret(begin)
+            } else {
+              CaretState::Select(begin, end)
+            };
+          }
+        },
+        SelectedText {
+          id: selected,
+          rects: vec![],
+        }
+        Text {
+          id: text,
+          text: this.text.clone(),
+          foreground: this.foreground.clone(),
+          style: this.style.clone(),
+          on_performed_layout: move |ctx| {
+            let bound = ctx.layout_info().expect("layout info must exit in performed_layout").clamp;
+            helper.glyphs = Some(Text::text_layout(
+              &text.text,
+              &text.style,
+              ctx.wnd_ctx().typography_store(),
+              bound,
+              Overflow::Clip,
+            ));
+          }
+        }
+      }
+      finally {
+        let_watch!(this.caret)
+          .distinct_until_changed()
+          .sample(tick_of_layout_ready)
+          .subscribe(move |cursor| {
+            selected.rects = helper.selection(&cursor.select_range());
+          });
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::input::tests::{edit_window, key_with_ctrl, send};
+  use ribir_core::test::{mouse_input, mouse_move};
+  use std::time::Duration;
+  use winit::event::ElementState;
+
+  #[test]
+  fn select_and_copy() {
+    let (mut wnd, probe) = edit_window(|_| widget! { SelectableText { text: "hello world" } });
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    let copy = |wnd: &mut Window| {
+      send(wnd, key_with_ctrl(VirtualKeyCode::C));
+      probe.clipboard.borrow_mut().read_text().unwrap()
+    };
+
+    mouse_move(&mut wnd, 0., 2.);
+    mouse_input(&mut wnd, ElementState::Pressed);
+    mouse_move(&mut wnd, 20., 2.);
+    mouse_input(&mut wnd, ElementState::Released);
+    wnd.draw_frame();
+    let copied = copy(&mut wnd);
+    assert!(!copied.is_empty() && "hello world".starts_with(&copied));
+
+    // double tap to select a word.
+    clock.advance(Duration::from_secs(1));
+    mouse_move(&mut wnd, 2., 2.);
+    (0..2).for_each(|_| {
+      mouse_input(&mut wnd, ElementState::Pressed);
+      mouse_input(&mut wnd, ElementState::Released);
+    });
+    wnd.draw_frame();
+    assert_eq!(copy(&mut wnd), "hello");
+  }
+}
@@ -0,0 +1,468 @@
+use super::{
+  caret::Caret,
+  glyphs_helper::GlyphsHelper,
+  handle::{self, EditableText},
+  input_width,
+  preedit::{self, Preedit, PreeditUnderline},
+  selected_text::SelectedText,
+  to_content_pos, CaretState, EditHistory, EditKeymap, Placeholder,
+};
+use crate::layout::{ConstrainedBox, Stack};
+use crate::prelude::{InputTheme, Text};
+use ribir_core::{prelude::*, ticker::FrameMsg};
+
+/// A multi-line text input, the text auto wrap by the width of the widget and
+/// scroll vertically when the lines over the rows it can display.
+#[derive(Declare)]
+pub struct TextArea {
+  #[declare(default = TypographyTheme::of(ctx).body_large.text.clone())]
+  pub style: CowArc<TextStyle>,
+  #[declare(skip)]
+  text: CowArc<str>,
+  #[declare(skip)]
+  caret: CaretState,
+  #[declare(skip)]
+  history: EditHistory,
+  #[declare(skip)]
+  preedit: Option<Preedit>,
+  #[declare(default = InputTheme::of(ctx).min_length)]
+  min_length: f32,
+  /// The count of the lines the text area display, default is 2.
+  #[declare(default = 2.)]
+  pub rows: f32,
+  /// Grow the height of the text area with its content if true, the `rows`
+  /// is the minimum lines it display.
+  #[declare(default)]
+  pub auto_grow: bool,
+}
+
+impl TextArea {
+  pub fn text(&self) -> CowArc<str> { self.text.clone() }
+
+  pub fn caret(&self) -> &CaretState { &self.caret }
+
+  pub fn set_text(&mut self, text: impl Into<CowArc<str>>) {
+    self.text = text.into();
+    self.caret.valid(self.text.len());
+  }
+
+  pub fn set_caret(&mut self, caret: CaretState) {
+    self.caret = caret;
+    self.caret.valid(self.text.len());
+  }
+
+  /// The edit history of the text, it can be used to add custom merge or clear
+  /// the history.
+  pub fn history(&self) -> &EditHistory { &self.history }
+
+  pub fn history_mut(&mut self) -> &mut EditHistory { &mut self.history }
+
+  /// Undo the last edit transaction, return if anything undone.
+  pub fn undo(&mut self) -> bool { handle::undo(self) }
+
+  /// Redo the last undone edit transaction, return if anything redone.
+  pub fn redo(&mut self) -> bool { handle::redo(self) }
+
+  fn display_text(&self) -> CowArc<str> {
+    preedit::display_text(&self.text, self.caret, self.preedit.as_ref())
+  }
+
+  pub(crate) fn edit_handle(&mut self, event: &mut CharEvent) { handle::edit_handle(self, event); }
+
+  pub(crate) fn key_handle(
+    &mut self,
+    key: &mut KeyboardEvent,
+    keymap: &EditKeymap,
+    helper: &GlyphsHelper,
+    page_height: f32,
+  ) {
+    if matches!(key.key, VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter) {
+      handle::insert_char(self, '\n');
+    } else {
+      handle::key_handle(self, key, keymap, helper, page_height);
+    }
+  }
+
+  pub(crate) fn select_word(&mut self, cursor: usize) { handle::select_word(self, cursor) }
+
+  pub(crate) fn select_line(&mut self, cursor: usize) { handle::select_line(self, cursor) }
+
+  fn rows_height(&self) -> f32 {
+    let line_height = self.style.line_height.map_or_else(
+      || self.style.font_size.into_pixel().value(),
+      |h| Pixel::from(h).value(),
+    );
+    line_height * self.rows
+  }
+}
+
+impl EditableText for TextArea {
+  fn edit_text(&self) -> CowArc<str> { self.text.clone() }
+
+  fn edit_caret(&self) -> CaretState { self.caret }
+
+  fn update_edit(&mut self, text: CowArc<str>, caret: CaretState) {
+    self.text = text;
+    self.caret = caret;
+  }
+
+  fn edit_history(&mut self) -> &mut EditHistory { &mut self.history }
+
+  fn multiline(&self) -> bool { true }
+}
+
+impl ComposeChild for TextArea {
+  type Child = Option<Placeholder>;
+  fn compose_child(this: State<Self>, placeholder: Self::Child) -> Widget {
+    widget! {
+      states {
+        this: this.into_writable(),
+        helper: Stateful::new(GlyphsHelper::default()),
+      }
+      init ctx => {
+        let tick_of_layout_ready = ctx.wnd_ctx()
+          .frame_tick_stream()
+          .filter(|msg| matches!(msg, FrameMsg::LayoutReady(_)));
+        let keymap = InputTheme::of(ctx).keymap;
+      }
+
+      ConstrainedBox {
+        id: outbox,
+        clamp: BoxClamp {
+          min: Size::new(input_width(this.style.font_size, this.min_length), this.rows_height()),
+          max: Size::new(
+            input_width(this.style.font_size, this.min_length),
+            if this.auto_grow { f32::INFINITY } else { this.rows_height() }
+          ),
+        },
+        auto_focus: true,
+        on_char: move |char_event| this.edit_handle(char_event),
+        on_ime_preedit: move |e| this.preedit = Preedit::from_event(e),
+        on_focus: move |e| e.context().wnd_ctx().set_ime_allowed(true),
+        on_blur: move |e| {
+          this.preedit = None;
+          e.context().wnd_ctx().set_ime_allowed(false);
+        },
+        on_key_down: move |key| {
+          let page_height = container.scroll_view_size().height;
+          this.key_handle(key, &keymap, &helper, page_height);
+        },
+        on_pointer_move: move |e| {
+          if let CaretState::Selecting(begin, _) = this.caret {
+            if e.point_type == PointerType::Mouse
+              && e.mouse_buttons() == MouseButtons::PRIMARY {
+              let position = to_content_pos(&container, &e.position());
+              let cluster = helper.cluster_from_pos(position.x, position.y);
+              this.caret = CaretState::Selecting(begin, cluster as usize);
+            }
+          }
+        },
+        on_pointer_down: move |e| {
+          let position = to_content_pos(&container, &e.position());
+          let cluster = helper.cluster_from_pos(position.x, position.y) as usize;
+          let begin = if e.modifiers().shift() { this.caret.anchor() } else { cluster };
+          this.caret = CaretState::Selecting(begin, cluster);
+        },
+        on_double_tap: move |e| {
+          let position = to_content_pos(&container, &e.position());
+          let cluster = helper.cluster_from_pos(position.x, position.y);
+          this.select_word(cluster as usize);
+        },
+        on_triple_tap: move |e| {
+          let position = to_content_pos(&container, &e.position());
+          let cluster = helper.cluster_from_pos(position.x, position.y);
+          this.select_line(cluster as usize);
+        },
+        on_pointer_up: move |_| {
+          if let CaretState::Selecting(begin, end) = this.caret {
+            this.caret = if begin == end {
+             CaretState::Caret(begin)
+            } else {
+              CaretState::Select(begin, end)
+            };
+          }
+        },
+
+        ScrollableWidget {
+          id: container,
+          scrollable: Scrollable::Y,
+          padding: EdgeInsets::horizontal(1.),
+          Stack {
+            SelectedText {
+              id: selected,
+              rects: vec![],
+            }
+            Text {
+              id: text,
+              text: this.display_text(),
+              style: this.style.clone(),
+              overflow: Overflow::AutoWrap,
+
+              on_performed_layout: move |ctx| {
+                let bound = ctx
+                  .layout_info()
+                  .expect("layout info must exit in performed_layout")
+                  .clamp;
+                helper.glyphs = Some(Text::text_layout(
+                  &text.text,
+                  &text.style,
+                  ctx.wnd_ctx().typography_store(),
+                  bound,
+                  Overflow::AutoWrap,
+                ));
+              }
+            }
+
+            PreeditUnderline {
+              id: underline,
+              rects: vec![],
+            }
+
+            DynWidget {
+              dyns: placeholder.map(|holder| {
+                widget! {
+                  Text {
+                    visible: this.text.is_empty() && this.preedit.is_none(),
+                    text: holder.0,
+                    overflow: Overflow::AutoWrap,
+                  }
+                }
+              })
+            }
+
+            Caret {
+              id: caret,
+              top_anchor: 0.,
+              left_anchor: 0.,
+              focused: outbox.has_focus(),
+              height: 0.,
+              on_performed_layout: move |ctx| {
+                // place the candidate window of the input method below the caret.
+                if outbox.has_focus() {
+                  let size = ctx.box_size().unwrap_or_default();
+                  let pos = ctx.map_to_global(Point::new(size.width / 2., size.height));
+                  ctx.wnd_ctx().set_ime_pos(pos);
+                }
+              }
+            }
+          }
+        }
+      }
+      finally {
+        let_watch!((this.caret, this.preedit.clone()))
+          .distinct_until_changed()
+          .sample(tick_of_layout_ready)
+          .subscribe(move |(cursor, preedit)| {
+            let offset = if let Some(preedit) = preedit {
+              selected.rects = vec![];
+              underline.rects = helper.selection(&preedit.range(cursor));
+              preedit.cursor(cursor)
+            } else {
+              selected.rects = helper.selection(&cursor.select_range());
+              underline.rects = vec![];
+              cursor.offset()
+            };
+            let (offset, height) = helper.cursor(offset);
+            caret.top_anchor = PositionUnit::Pixel(offset.y);
+            caret.left_anchor = PositionUnit::Pixel(offset.x);
+            caret.height = height;
+          });
+        let_watch!((caret.left_anchor.abs_value(1.), caret.top_anchor.abs_value(1.), caret.height))
+          .distinct_until_changed()
+          .subscribe(move |(x, y, height)| {
+            let caret = Rect::new(Point::new(x, y), Size::new(1., height));
+            let pos = caret_visible_pos(&container, &caret);
+            container.silent().jump_to(pos);
+          });
+
+        // let_watch!(this.caret).distinct_until_changed() will only be triggered after modify
+        // borrow mut from state_ref to manual triggered after init.
+        let _:&mut TextArea = &mut this;
+      }
+    }
+  }
+}
+
+/// Return the scroll position that make the caret rect (in content coordinate)
+/// visible in the view, keep the current position if it's already visible.
+fn caret_visible_pos(container: &ScrollableWidget, caret: &Rect) -> Point {
+  let view = container.scroll_view_size();
+  let mut pos = container.scroll_pos;
+  if caret.min_x() + pos.x < 0. {
+    pos.x = -caret.min_x();
+  } else if caret.max_x() + pos.x > view.width {
+    pos.x = view.width - caret.max_x();
+  }
+  if caret.min_y() + pos.y < 0. {
+    pos.y = -caret.min_y();
+  } else if caret.max_y() + pos.y > view.height {
+    pos.y = view.height - caret.max_y();
+  }
+  pos
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::input::tests::{edit_window, key_with, key_with_ctrl, EditProbe};
+  use crate::prelude::*;
+  use ribir_core::test::*;
+  use std::time::Duration;
+  use winit::event::{ModifiersState, WindowEvent};
+
+  /// A text area of two rows with `text` and `caret`, return its window, the
+  /// clock of the window and the probe of the text area.
+  fn text_area(text: &'static str, caret: CaretState) -> (Window, ManualClock, EditProbe) {
+    let (wnd, probe) = edit_window(|probe| {
+      widget! {
+        TextArea { id: area, rows: 2. }
+        finally {
+          area.set_text(text);
+          area.set_caret(caret);
+          let_watch!((area.text(), *area.caret()))
+            .subscribe(move |(text, caret)| {
+              probe.set_text(&text);
+              probe.set_caret(caret);
+            });
+        }
+      }
+    });
+    let clock = ManualClock::default();
+    wnd.set_clock(clock.clone());
+    (wnd, clock, probe)
+  }
+
+  fn send(wnd: &mut Window, clock: &ManualClock, events: Vec<WindowEvent<'static>>) {
+    crate::input::tests::send(wnd, events);
+    // Draw frames until the smooth scroll finished.
+    for _ in 0..3 {
+      clock.advance(Duration::from_secs(1));
+      wnd.draw_frame();
+    }
+  }
+
+  /// The scroll position of the content.
+  fn scroll_y(wnd: &Window) -> f32 { layout_position_by_path(wnd, &[0, 0, 0, 0, 0, 0]).y }
+
+  #[test]
+  fn enter_insert_line_break() {
+    let (mut wnd, clock, probe) = text_area("ab", CaretState::Caret(1));
+    let none = ModifiersState::empty();
+    send(&mut wnd, &clock, key_with(VirtualKeyCode::Return, none));
+    assert_eq!(
+      (probe.text(), probe.caret()),
+      ("a\nb".into(), CaretState::Caret(2))
+    );
+
+    send(
+      &mut wnd,
+      &clock,
+      key_with(VirtualKeyCode::NumpadEnter, none),
+    );
+    assert_eq!(
+      (probe.text(), probe.caret()),
+      ("a\n\nb".into(), CaretState::Caret(3))
+    );
+  }
+
+  #[test]
+  fn navigate_lines() {
+    let (mut wnd, clock, probe) = text_area("aaa\naaa\naaa", CaretState::Caret(5));
+    let mut key = |key, modifiers| {
+      send(&mut wnd, &clock, key_with(key, modifiers));
+      probe.caret()
+    };
+    let none = ModifiersState::empty();
+    assert_eq!(key(VirtualKeyCode::Up, none), CaretState::Caret(1));
+    assert_eq!(key(VirtualKeyCode::Up, none), CaretState::Caret(1));
+    assert_eq!(key(VirtualKeyCode::Down, none), CaretState::Caret(5));
+    assert_eq!(key(VirtualKeyCode::Down, none), CaretState::Caret(9));
+    assert_eq!(key(VirtualKeyCode::Home, none), CaretState::Caret(8));
+    assert_eq!(key(VirtualKeyCode::End, none), CaretState::Caret(11));
+    assert_eq!(
+      key(VirtualKeyCode::Up, ModifiersState::SHIFT),
+      CaretState::Select(11, 7)
+    );
+
+    send(&mut wnd, &clock, key_with_ctrl(VirtualKeyCode::Home));
+    assert_eq!(probe.caret(), CaretState::Caret(0));
+    send(&mut wnd, &clock, key_with_ctrl(VirtualKeyCode::End));
+    assert_eq!(probe.caret(), CaretState::Caret(11));
+  }
+
+  #[test]
+  fn page_and_scroll_to_caret() {
+    // ten lines, the text area displays two of them.
+    let (mut wnd, clock, probe) = text_area("0\n1\n2\n3\n4\n5\n6\n7\n8\n9", CaretState::Caret(0));
+    let line_height = layout_size_by_path(&wnd, &[0]).height / 2.;
+    let none = ModifiersState::empty();
+    assert_eq!(scroll_y(&wnd), 0.);
+
+    // page down two lines, and scroll to keep the caret at the bottom.
+    send(&mut wnd, &clock, key_with(VirtualKeyCode::PageDown, none));
+    assert_eq!(probe.caret(), CaretState::Caret(4));
+    assert_eq!(scroll_y(&wnd), -line_height);
+
+    send(&mut wnd, &clock, key_with(VirtualKeyCode::PageDown, none));
+    assert_eq!(probe.caret(), CaretState::Caret(8));
+    assert_eq!(scroll_y(&wnd), -line_height * 3.);
+
+    // the caret is still visible, not scroll.
+    send(&mut wnd, &clock, key_with(VirtualKeyCode::Up, none));
+    assert_eq!(probe.caret(), CaretState::Caret(6));
+    assert_eq!(scroll_y(&wnd), -line_height * 3.);
+
+    // scroll to keep the caret at the top.
+    send(&mut wnd, &clock, key_with(VirtualKeyCode::PageUp, none));
+    assert_eq!(probe.caret(), CaretState::Caret(2));
+    assert_eq!(scroll_y(&wnd), -line_height);
+
+    send(&mut wnd, &clock, key_with_ctrl(VirtualKeyCode::End));
+    assert_eq!(probe.caret(), CaretState::Caret(19));
+    assert_eq!(scroll_y(&wnd), -line_height * 8.);
+
+    send(&mut wnd, &clock, key_with_ctrl(VirtualKeyCode::Home));
+    assert_eq!(probe.caret(), CaretState::Caret(0));
+    assert_eq!(scroll_y(&wnd), 0.);
+  }
+
+  #[test]
+  fn rows_height() {
+    let style: CowArc<TextStyle> = CowArc::owned(TextStyle {
+      font_size: FontSize::Pixel(14.0.into()),
+      ..<_>::default()
+    });
+    let w = {
+      let style = style.clone();
+      widget! { TextArea { rows: 3., style } }
+    };
+    expect_layout_result_with_theme(
+      w,
+      None,
+      material::purple::light(),
+      &[LayoutTestItem {
+        path: &[0],
+        expect: ExpectRect {
+          width: Some(280.),
+          height: Some(42.),
+          ..<_>::default()
+        },
+      }],
+    );
+
+    let w = widget! { TextArea { rows: 1., auto_grow: true, style } };
+    expect_layout_result_with_theme(
+      w,
+      None,
+      material::purple::light(),
+      &[LayoutTestItem {
+        path: &[0],
+        expect: ExpectRect {
+          width: Some(280.),
+          height: Some(14.),
+          ..<_>::default()
+        },
+      }],
+    );
+  }
+}
@@ -13,79 +13,32 @@ pub struct PathPaintKit {
 }
 
 impl PathPaintKit {
-  pub fn path_lerp_fn(
-    prop: impl Property<Value = Path>,
-  ) -> impl Fn(&Path, &Path, f32) -> Path + Clone {
-    let path = prop.get();
-    let measurements = Rc::new(PathMeasurements::from_path(&path.path, 1e-3));
-    move |_, _, rate| {
-      let mut sampler = measurements.create_sampler(&path.path, SampleType::Normalized);
-      let mut path_builder = Path::builder();
-      sampler.split_range(0.0..rate, &mut path_builder.0);
-      Path {
-        path: path_builder.0.build(),
-        style: path.style,
-      }
-    }
+  /// Return a lerp function that morphs the path from `from` to `to`, see the
+  /// [`Lerp`] of [`Path`].
+  pub fn path_lerp_fn() -> impl Fn(&Path, &Path, f32) -> Path + Clone {
+    |from, to, rate| from.lerp(to, rate)
   }
 
-  pub fn paths_lerp_fn(
-    prop: impl Property<Value = Vec<PathPaintKit>> + Clone,
-  ) -> impl Fn(&Vec<PathPaintKit>, &Vec<PathPaintKit>, f32) -> Vec<PathPaintKit> + Clone {
-    let paths = prop.get();
-    let mut measurements_list = vec![];
-    paths.iter().for_each(|path_paint_kit| {
-      let measurements = PathMeasurements::from_path(&path_paint_kit.path.path, 1e-3);
-      measurements_list.push(measurements);
-    });
-    let measurements_list = Rc::new(measurements_list);
-
-    move |_, _, rate| {
-      let mut total_len = 0.;
-      let mut len_list = vec![];
-      measurements_list.iter().enumerate().for_each(|(i, m)| {
-        let sampler = m.create_sampler(&(paths[i].path.path), SampleType::Normalized);
-        let len = sampler.length();
-        len_list.push(len);
-        total_len += len;
-      });
-      let real_len = total_len * rate;
-      let mut rest_len = real_len;
-      let mut rate = 0.;
-      // find current rate at which path index
-      let mut idx: usize = 0;
-      for (i, len) in len_list.into_iter().enumerate() {
-        if rest_len < len {
-          rate = rest_len / len;
-          idx = i;
-          break;
-        } else {
-          rest_len -= len;
-        }
-      }
-      let mut path_list = vec![];
-      // before index path push path-list result
-      for (i, path) in paths.iter().enumerate() {
-        if i < idx {
-          path_list.push(path.clone());
-        } else {
-          break;
-        }
-      }
-      // generate rate rest path
-      let path = paths[idx].clone();
-      let measurements = PathMeasurements::from_path(&path.path.path, 1e-3);
-      let mut sampler = measurements.create_sampler(&path.path.path, SampleType::Normalized);
-      let mut path_builder = Path::builder();
-      sampler.split_range(0.0..rate, &mut path_builder.0);
-      path_list.push(PathPaintKit {
+  /// Return a lerp function that morphs the paths pairwise by their order, the
+  /// path that has no counterpart grows from or shrinks to its center.
+  pub fn paths_lerp_fn()
+  -> impl Fn(&Vec<PathPaintKit>, &Vec<PathPaintKit>, f32) -> Vec<PathPaintKit> + Clone {
+    |from, to, rate| {
+      let vanished = |p: &PathPaintKit| PathPaintKit {
         path: Path {
-          path: path_builder.0.build(),
-          style: paths.get(idx).unwrap().path.style,
+          path: <_>::default(),
+          style: p.path.style,
         },
-        brush: path.brush,
-      });
-      path_list
+        brush: p.brush.clone(),
+      };
+      (0..from.len().max(to.len()))
+        .map(|idx| match (from.get(idx), to.get(idx)) {
+          (Some(from), Some(to)) => from.lerp(to, rate),
+          (Some(from), None) => from.lerp(&vanished(from), rate),
+          (None, Some(to)) => vanished(to).lerp(to, rate),
+          (None, None) => unreachable!(),
+        })
+        .collect()
     }
   }
 
@@ -103,6 +56,15 @@ impl PathPaintKit {
   }
 }
 
+impl Lerp for PathPaintKit {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    PathPaintKit {
+      path: self.path.lerp(&to.path, factor),
+      brush: self.brush.lerp(&to.brush, factor),
+    }
+  }
+}
+
 impl Render for PathPaintKit {
   #[inline]
   fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.max }
@@ -186,3 +148,45 @@ impl Render for PathsPaintKit {
 impl Query for PathsPaintKit {
   impl_query_self_only!();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sub_path_cnt(path: &Path) -> usize {
+    path
+      .path
+      .iter()
+      .filter(|e| matches!(e, lyon_algorithms::path::PathEvent::Begin { .. }))
+      .count()
+  }
+
+  #[test]
+  fn morph_svg_icons() {
+    let menu = include_svg!("./themes/material/icons/menu_FILL0_wght400_GRAD0_opsz48.svg");
+    let close = include_svg!("./themes/material/icons/close_FILL0_wght400_GRAD0_opsz48.svg");
+    let rect_eq =
+      |a: Rect, b: Rect| (a.min() - b.min()).length() + (a.max() - b.max()).length() < 1e-2;
+    let box_rect = |svg: &SvgPaths| {
+      svg
+        .paths
+        .iter()
+        .map(|p| p.transform.outer_transformed_rect(&p.path.box_rect()))
+        .reduce(|a, b| a.union(&b))
+        .unwrap()
+    };
+
+    assert!(rect_eq(box_rect(&menu.lerp(&close, 0.)), box_rect(&menu)));
+    assert!(rect_eq(box_rect(&menu.lerp(&close, 1.)), box_rect(&close)));
+
+    let half = menu.lerp(&close, 0.5);
+    assert_eq!(half.paths.len(), menu.paths.len().max(close.paths.len()));
+    let sub_paths = |svg: &SvgPaths| svg.paths.iter().map(|p| sub_path_cnt(&p.path)).collect();
+    let (menu_subs, close_subs): (Vec<_>, Vec<_>) = (sub_paths(&menu), sub_paths(&close));
+    let half_subs: Vec<_> = sub_paths(&half);
+    half_subs
+      .iter()
+      .zip(menu_subs.iter().zip(close_subs.iter()))
+      .for_each(|(half, (menu, close))| assert_eq!(*half, *menu.max(close)));
+  }
+}
@@ -103,11 +103,7 @@ impl ComposeChild for Tabs {
                             } else {
                               (normal_foreground.clone(), normal_text_style.clone())
                             };
-                            Text {
-                              text: text.0.clone(),
-                              foreground,
-                              style
-                            }
+                            Text::new(text.0.clone(), &foreground, style)
                           }
                         }
                       }
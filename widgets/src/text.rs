@@ -15,6 +15,9 @@ pub struct Text {
   pub foreground: Brush,
   #[declare(default = TypographyTheme::of(ctx).body_medium.text.clone())]
   pub style: CowArc<TextStyle>,
+  /// How to place the text when it over the width of the box, clip by default.
+  #[declare(default = Overflow::Clip)]
+  pub overflow: Overflow,
 }
 
 impl Text {
@@ -23,6 +26,7 @@ impl Text {
       text: str.into(),
       foreground: foreground.clone(),
       style,
+      overflow: Overflow::Clip,
     }
   }
 
@@ -31,6 +35,7 @@ impl Text {
     style: &CowArc<TextStyle>,
     t_store: &TypographyStore,
     bound: BoxClamp,
+    overflow: Overflow,
   ) -> VisualGlyphs {
     let TextStyle {
       font_size,
@@ -53,7 +58,7 @@ impl Text {
         text_align: None,
         bounds: (width, height).into(),
         line_dir: PlaceLineDirection::TopToBottom,
-        overflow: Overflow::Clip,
+        overflow,
       },
     )
   }
@@ -62,7 +67,8 @@ impl Text {
 impl Render for Text {
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
     let wnd_ctx = ctx.wnd_ctx();
-    Text::text_layout(&self.text, &self.style, wnd_ctx.typography_store(), clamp)
+    let t_store = wnd_ctx.typography_store();
+    Text::text_layout(&self.text, &self.style, t_store, clamp, self.overflow)
       .visual_rect()
       .size
       .cast_unit()
@@ -74,12 +80,21 @@ impl Render for Text {
   #[inline]
   fn paint(&self, ctx: &mut PaintingCtx) {
     let rect = ctx.box_rect().unwrap();
-    ctx.painter().paint_text_with_style(
-      self.text.substr(..),
-      &self.style,
-      self.foreground.clone(),
-      Some(rect.size),
-    );
+    if self.overflow == Overflow::Clip {
+      ctx.painter().paint_text_with_style(
+        self.text.substr(..),
+        &self.style,
+        self.foreground.clone(),
+        Some(rect.size),
+      );
+    } else {
+      let t_store = ctx.wnd_ctx().typography_store();
+      let bound = BoxClamp { min: Size::zero(), max: rect.size };
+      let glyphs = Text::text_layout(&self.text, &self.style, t_store, bound, self.overflow);
+      ctx
+        .painter()
+        .paint_glyphs(&glyphs, &self.style, self.foreground.clone());
+    }
   }
 }
 
@@ -365,7 +365,7 @@ fn build_input_area(
       id: input_area,
       visible: !this.text.is_empty() || theme.state == TextFieldState::Focused,
       Option::map(prefix.clone(), move |text| {
-        Text { text, foreground: theme.foreground.clone(), style: theme.text.clone() }
+        Text::new(text, &theme.foreground, theme.text.clone())
       })
       Expanded {
         flex: 1.,
@@ -376,7 +376,7 @@ fn build_input_area(
         }
       }
       Option::map(suffix.clone(),  move |text| {
-        Text { text, foreground: theme.foreground.clone(), style: theme.text.clone()}
+        Text::new(text, &theme.foreground, theme.text.clone())
       })
 
     }
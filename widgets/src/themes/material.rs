@@ -90,6 +90,7 @@ fn init_custom_theme(theme: &mut FullTheme) {
     min_length: 20.,
     select_background: Color::from_rgb(181, 215, 254).into(),
     caret_color: Brush::Color(theme.palette.on_surface()),
+    keymap: EditKeymap::platform(),
   });
   theme.custom_themes.set_custom_theme(FilledButtonStyle {
     height: 40.,
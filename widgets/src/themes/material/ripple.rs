@@ -43,6 +43,7 @@ impl ComposeChild for Ripple {
       states { this: this.into_writable() }
       init ctx => {
         let linear_transition = transitions::LINEAR.of(ctx);
+        let ease_out = transitions::EASE_OUT.of(ctx);
       }
       Stack {
         id: container,
@@ -61,8 +62,16 @@ impl ComposeChild for Ripple {
             (distance_x.powf(2.) + distance_y.powf(2.)).sqrt()
           });
           let linear_transition = linear_transition.clone();
+          let ease_out = ease_out.clone();
           widget!{
             IgnorePointer {
+              id: ripple,
+              opacity: 1.,
+              delay_drop_until: !ripper_fade_out.is_running(),
+              on_disposed: move |_| {
+                ripple.opacity = 0.;
+                ripper_fade_out.run();
+              },
               DynWidget {
                 dyns: (this.bounded != RippleBound::Unbounded).then(|| {
                   let rect = Rect::from_size(container.layout_size());
@@ -100,15 +109,18 @@ impl ComposeChild for Ripple {
               let_watch!(!container.pointer_pressed() && !ripper_enter.is_running())
                 .filter(|b| *b)
                 .subscribe(move |_| {
-                  this.launch_pos.take();
+                  // the ripple may be fading out, only the latest ripple finishes the launch.
+                  if this.launch_pos == Some(launch_at) {
+                    this.launch_pos.take();
+                  }
                 });
             }
-            // todo: support disposed animate
-            // Animate {
-            //   id: ripper_fade_out,
-            //   from: State { ripple_path.opacity: 1.},
-            //   transition: transitions::EASE_OUT.get_from_or_default(ctx.theme()),
-            // }
+            Animate {
+              id: ripper_fade_out,
+              transition: ease_out,
+              prop: prop!(ripple.opacity),
+              from: 1.,
+            }
           }
         })
       }
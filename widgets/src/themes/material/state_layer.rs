@@ -49,7 +49,7 @@ impl ComposeChild for InteractiveLayer {
             path: Path::rect_round(&host.layout_rect(), &this.border_radii, PathStyle::Fill),
             role: if host.pointer_pressed() {
               StateRole::pressed()
-            } else if host.has_focus() {
+            } else if host.focus_visible() {
               StateRole::focus()
             } else if host.mouse_hover() {
               StateRole::hover()